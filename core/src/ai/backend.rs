@@ -0,0 +1,342 @@
+use crate::util::{check_status, retry_with_backoff, send_logged, HttpTimeouts, RetryPolicy, AI_TIMEOUTS};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Default OpenAI chat model
+pub const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+
+/// Default Anthropic model
+pub const DEFAULT_ANTHROPIC_MODEL: &str = "claude-3-5-haiku-latest";
+
+/// Keys that mean "not configured" and should never reach a provider
+const PLACEHOLDER_KEYS: [&str; 3] = ["dummy_key", "your_openai_api_key_here", "your_anthropic_api_key_here"];
+
+/// Returned instead of calling a provider whose API key is missing or a placeholder
+#[derive(Debug, thiserror::Error)]
+#[error("AI not configured, skipping API call")]
+pub struct AiNotConfigured;
+
+/// A usable key is non-empty and not one of `PLACEHOLDER_KEYS`
+fn is_real_key(api_key: &str) -> bool {
+    let api_key = api_key.trim();
+    !api_key.is_empty() && !PLACEHOLDER_KEYS.contains(&api_key)
+}
+
+/// A chat completion provider used for AI rescheduling
+#[async_trait]
+pub trait AiBackend: Send + Sync {
+    /// Send a system and user prompt, returning the model's text reply
+    async fn complete(&self, system: &str, user: &str) -> Result<String>;
+
+    /// Whether `complete` can reach the provider; unconfigured backends fail
+    /// every call with `AiNotConfigured`
+    fn is_configured(&self) -> bool {
+        true
+    }
+}
+
+/// Select a backend from `AI_PROVIDER` ("openai" or "anthropic", default "openai").
+/// `AI_MODEL` overrides the provider's default model.
+pub fn backend_from_env() -> Result<Box<dyn AiBackend>> {
+    let provider = std::env::var("AI_PROVIDER").unwrap_or_else(|_| "openai".to_string());
+    let model = std::env::var("AI_MODEL").ok();
+
+    match provider.to_lowercase().as_str() {
+        "openai" => {
+            let api_key = std::env::var("OPENAI_API_KEY")
+                .context("OPENAI_API_KEY environment variable not set")?;
+
+            // Skip AI if using placeholder key
+            if api_key == "your_openai_api_key_here" {
+                anyhow::bail!("OpenAI API key not configured, using placeholder");
+            }
+
+            Ok(Box::new(OpenAiBackend::new(api_key, model, None)))
+        }
+        "anthropic" => {
+            let api_key = std::env::var("ANTHROPIC_API_KEY")
+                .context("ANTHROPIC_API_KEY environment variable not set")?;
+
+            if api_key == "your_anthropic_api_key_here" {
+                anyhow::bail!("Anthropic API key not configured, using placeholder");
+            }
+
+            Ok(Box::new(AnthropicBackend::new(api_key, model, None)))
+        }
+        other => anyhow::bail!("Unknown AI_PROVIDER: {}. Must be one of: openai, anthropic", other),
+    }
+}
+
+/// OpenAI `chat/completions` backend with JSON response format
+pub struct OpenAiBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl OpenAiBackend {
+    /// Timeouts come from `AI_TIMEOUT_SECS` / `AI_CONNECT_TIMEOUT_SECS`
+    pub fn new(api_key: String, model: Option<String>, base_url: Option<String>) -> Self {
+        Self {
+            client: HttpTimeouts::from_env("AI", AI_TIMEOUTS).build_client(),
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_OPENAI_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| "https://api.openai.com/v1/chat/completions".to_string()),
+        }
+    }
+
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.client = timeouts.build_client();
+        self
+    }
+}
+
+#[async_trait]
+impl AiBackend for OpenAiBackend {
+    async fn complete(&self, system: &str, user: &str) -> Result<String> {
+        // Skip AI call if using dummy/placeholder key
+        if !self.is_configured() {
+            return Err(AiNotConfigured.into());
+        }
+
+        #[derive(Serialize)]
+        struct ChatMessage<'a> {
+            role: &'a str,
+            content: &'a str,
+        }
+
+        #[derive(Serialize)]
+        struct ChatRequest<'a> {
+            model: &'a str,
+            messages: Vec<ChatMessage<'a>>,
+            temperature: f32,
+            response_format: serde_json::Value,
+        }
+
+        let request = ChatRequest {
+            model: &self.model,
+            messages: vec![
+                ChatMessage { role: "system", content: system },
+                ChatMessage { role: "user", content: user },
+            ],
+            temperature: 0.7,
+            response_format: serde_json::json!({ "type": "json_object" }),
+        };
+
+        let response = retry_with_backoff(RetryPolicy::default(), || async {
+            let request = self
+                .client
+                .post(&self.base_url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request);
+            let response = send_logged("OpenAI", request)
+                .await
+                .context("Failed to call OpenAI API")?;
+
+            check_status("OpenAI", response).await
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("OpenAI API error: {:#}", e);
+            e
+        })?;
+
+        #[derive(Deserialize)]
+        struct ChatResponse {
+            choices: Vec<Choice>,
+        }
+
+        #[derive(Deserialize)]
+        struct Choice {
+            message: Message,
+        }
+
+        #[derive(Deserialize)]
+        struct Message {
+            content: String,
+        }
+
+        let chat_response: ChatResponse = response
+            .json()
+            .await
+            .context("Failed to parse OpenAI response")?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .context("No choices in OpenAI response")
+    }
+
+    fn is_configured(&self) -> bool {
+        is_real_key(&self.api_key)
+    }
+}
+
+/// Anthropic Messages API backend
+pub struct AnthropicBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl AnthropicBackend {
+    /// Timeouts come from `AI_TIMEOUT_SECS` / `AI_CONNECT_TIMEOUT_SECS`
+    pub fn new(api_key: String, model: Option<String>, base_url: Option<String>) -> Self {
+        Self {
+            client: HttpTimeouts::from_env("AI", AI_TIMEOUTS).build_client(),
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_ANTHROPIC_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| "https://api.anthropic.com/v1/messages".to_string()),
+        }
+    }
+
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.client = timeouts.build_client();
+        self
+    }
+}
+
+#[async_trait]
+impl AiBackend for AnthropicBackend {
+    async fn complete(&self, system: &str, user: &str) -> Result<String> {
+        if !self.is_configured() {
+            return Err(AiNotConfigured.into());
+        }
+
+        let request = serde_json::json!({
+            "model": self.model,
+            "max_tokens": 1024,
+            "temperature": 0.7,
+            "system": system,
+            "messages": [{ "role": "user", "content": user }],
+        });
+
+        let response = retry_with_backoff(RetryPolicy::default(), || async {
+            let request = self
+                .client
+                .post(&self.base_url)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .header("Content-Type", "application/json")
+                .json(&request);
+            let response = send_logged("Anthropic", request)
+                .await
+                .context("Failed to call Anthropic API")?;
+
+            check_status("Anthropic", response).await
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Anthropic API error: {:#}", e);
+            e
+        })?;
+
+        #[derive(Deserialize)]
+        struct MessagesResponse {
+            content: Vec<ContentBlock>,
+        }
+
+        #[derive(Deserialize)]
+        struct ContentBlock {
+            #[serde(rename = "type")]
+            kind: String,
+            #[serde(default)]
+            text: String,
+        }
+
+        let messages_response: MessagesResponse = response
+            .json()
+            .await
+            .context("Failed to parse Anthropic response")?;
+
+        messages_response
+            .content
+            .into_iter()
+            .find(|block| block.kind == "text")
+            .map(|block| block.text)
+            .context("No text content in Anthropic response")
+    }
+
+    fn is_configured(&self) -> bool {
+        is_real_key(&self.api_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_anthropic_backend_returns_text_block() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("x-api-key", "test_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "content": [{ "type": "text", "text": "{\"options\": []}" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = AnthropicBackend::new("test_key".to_string(), None, Some(server.uri()));
+        let reply = backend.complete("system", "user").await.unwrap();
+
+        assert_eq!(reply, "{\"options\": []}");
+    }
+
+    fn chat_reply(content: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{ "message": { "content": content } }]
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_openai_backend_retries_service_unavailable() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(chat_reply("{\"options\": []}"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = OpenAiBackend::new("test_key".to_string(), None, Some(server.uri()));
+        let reply = backend.complete("system", "user").await.unwrap();
+
+        assert_eq!(reply, "{\"options\": []}");
+    }
+
+    #[tokio::test]
+    async fn test_openai_backend_does_not_retry_unauthorized() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("invalid api key"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = OpenAiBackend::new("bad_key".to_string(), None, Some(server.uri()));
+        let err = backend.complete("system", "user").await.unwrap_err();
+
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[tokio::test]
+    async fn test_placeholder_key_skips_request() {
+        let backend = OpenAiBackend::new("dummy_key".to_string(), None, Some("http://127.0.0.1:9".to_string()));
+        assert!(backend.complete("system", "user").await.is_err());
+    }
+}
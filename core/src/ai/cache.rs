@@ -0,0 +1,168 @@
+use crate::ai::{AiCache, RescheduleResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+
+/// Storage for AI reschedule responses, keyed by booking and scheduled time
+#[async_trait]
+pub trait AiCacheStore: Send + Sync {
+    /// Return the cached response if it is younger than the TTL
+    async fn get(&self, key: &str) -> Option<RescheduleResponse>;
+
+    async fn set(&self, key: String, response: RescheduleResponse);
+
+    /// Drop entries older than the TTL
+    async fn clear_expired(&self);
+}
+
+#[async_trait]
+impl AiCacheStore for AiCache {
+    async fn get(&self, key: &str) -> Option<RescheduleResponse> {
+        AiCache::get(self, key).await
+    }
+
+    async fn set(&self, key: String, response: RescheduleResponse) {
+        AiCache::set(self, key, response).await
+    }
+
+    async fn clear_expired(&self) {
+        AiCache::clear_expired(self).await
+    }
+}
+
+/// AI cache backed by the `ai_reschedule_cache` table, so cached suggestions
+/// survive a restart. Database errors are logged and treated as cache misses.
+pub struct SqliteAiCache {
+    db: SqlitePool,
+    ttl_hours: i64,
+}
+
+impl SqliteAiCache {
+    /// Cache whose entries expire after `hours`; 0 disables caching
+    pub fn with_ttl(db: SqlitePool, hours: i64) -> Self {
+        Self { db, ttl_hours: hours }
+    }
+
+    fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - Duration::hours(self.ttl_hours)
+    }
+}
+
+#[async_trait]
+impl AiCacheStore for SqliteAiCache {
+    async fn get(&self, key: &str) -> Option<RescheduleResponse> {
+        let row: Option<String> = match sqlx::query_scalar(
+            "SELECT response FROM ai_reschedule_cache WHERE key = ? AND created_at > ?"
+        )
+        .bind(key)
+        .bind(self.cutoff())
+        .fetch_optional(&self.db)
+        .await
+        {
+            Ok(row) => row,
+            Err(e) => {
+                tracing::warn!("Failed to read AI cache entry {}: {}", key, e);
+                return None;
+            }
+        };
+
+        row.and_then(|json| match serde_json::from_str(&json) {
+            Ok(response) => Some(response),
+            Err(e) => {
+                tracing::warn!("Discarding unreadable AI cache entry {}: {}", key, e);
+                None
+            }
+        })
+    }
+
+    async fn set(&self, key: String, response: RescheduleResponse) {
+        let json = match serde_json::to_string(&response) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize AI cache entry {}: {}", key, e);
+                return;
+            }
+        };
+
+        if let Err(e) = sqlx::query(
+            "INSERT INTO ai_reschedule_cache (key, response, created_at) VALUES (?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET response = excluded.response, created_at = excluded.created_at"
+        )
+        .bind(&key)
+        .bind(json)
+        .bind(Utc::now())
+        .execute(&self.db)
+        .await
+        {
+            tracing::warn!("Failed to write AI cache entry {}: {}", key, e);
+        }
+    }
+
+    async fn clear_expired(&self) {
+        match sqlx::query("DELETE FROM ai_reschedule_cache WHERE created_at <= ?")
+            .bind(self.cutoff())
+            .execute(&self.db)
+            .await
+        {
+            Ok(result) => {
+                tracing::debug!("Removed {} expired AI cache entries", result.rows_affected());
+            }
+            Err(e) => tracing::warn!("Failed to clear expired AI cache entries: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{RescheduleOption, DEFAULT_AI_CACHE_TTL_HOURS};
+    use crate::test_support::setup_test_db;
+
+    fn sample_response() -> RescheduleResponse {
+        RescheduleResponse {
+            options: vec![RescheduleOption {
+                date_time: Utc::now(),
+                reason: "Clear skies".to_string(),
+                weather_score: 9.5,
+                instructor_available: true,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_round_trip() {
+        let db = setup_test_db().await;
+        let cache = SqliteAiCache::with_ttl(db, DEFAULT_AI_CACHE_TTL_HOURS);
+
+        assert!(cache.get("booking1_1700000000").await.is_none());
+
+        cache.set("booking1_1700000000".to_string(), sample_response()).await;
+        let cached = cache.get("booking1_1700000000").await.unwrap();
+
+        assert_eq!(cached.options.len(), 1);
+        assert_eq!(cached.options[0].reason, "Clear skies");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_clear_expired() {
+        let db = setup_test_db().await;
+        let cache = SqliteAiCache::with_ttl(db.clone(), DEFAULT_AI_CACHE_TTL_HOURS);
+
+        cache.set("fresh".to_string(), sample_response()).await;
+        sqlx::query("INSERT INTO ai_reschedule_cache (key, response, created_at) VALUES (?, ?, ?)")
+            .bind("stale")
+            .bind(serde_json::to_string(&sample_response()).unwrap())
+            .bind(Utc::now() - Duration::hours(cache.ttl_hours + 1))
+            .execute(&db)
+            .await
+            .unwrap();
+
+        cache.clear_expired().await;
+
+        let keys: Vec<String> = sqlx::query_scalar("SELECT key FROM ai_reschedule_cache")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(keys, vec!["fresh".to_string()]);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod backend;
+pub mod cache;
 pub mod reschedule;
 
+pub use backend::*;
+pub use cache::*;
 pub use reschedule::*;
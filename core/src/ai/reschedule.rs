@@ -1,13 +1,19 @@
-use crate::models::{Booking, Student};
-use crate::weather::{is_flight_safe, WeatherData};
+use crate::ai::backend::{backend_from_env, AiBackend, OpenAiBackend};
+use crate::ai::cache::AiCacheStore;
+use crate::models::{
+    booking_interval, intervals_overlap, Booking, BookingStatus, Student, TrainingLevel, WeatherMinimum,
+};
+use crate::weather::{calculate_weather_score, is_daylight, safe_windows, weather_trend, WeatherData};
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RescheduleOption {
     pub date_time: DateTime<Utc>,
     pub reason: String,
@@ -20,22 +26,29 @@ pub struct RescheduleResponse {
     pub options: Vec<RescheduleOption>,
 }
 
-/// AI cache with TTL (6 hours)
+/// How long cached AI suggestions stay fresh when `AI_CACHE_TTL_HOURS` is unset
+pub const DEFAULT_AI_CACHE_TTL_HOURS: i64 = 6;
+
+/// Cache TTL from `AI_CACHE_TTL_HOURS`, falling back to the default
+pub fn ai_cache_ttl_hours_from_env() -> i64 {
+    std::env::var("AI_CACHE_TTL_HOURS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_AI_CACHE_TTL_HOURS)
+}
+
+/// In-memory AI cache with a TTL, `DEFAULT_AI_CACHE_TTL_HOURS` by default
 pub struct AiCache {
     cache: Arc<RwLock<HashMap<String, (RescheduleResponse, DateTime<Utc>)>>>,
     ttl_hours: i64,
 }
 
 impl AiCache {
-    pub fn new() -> Self {
-        let ttl_hours = std::env::var("AI_CACHE_TTL_HOURS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(6); // Default to 6 hours
-
+    /// Cache whose entries expire after `hours`; 0 disables caching
+    pub fn with_ttl(hours: i64) -> Self {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
-            ttl_hours,
+            ttl_hours: hours,
         }
     }
 
@@ -67,163 +80,479 @@ impl AiCache {
 
 impl Default for AiCache {
     fn default() -> Self {
-        Self::new()
+        Self::with_ttl(DEFAULT_AI_CACHE_TTL_HOURS)
+    }
+}
+
+/// Number of reschedule options generated when the caller has no preference
+pub const DEFAULT_RESCHEDULE_OPTIONS: usize = 3;
+
+fn reschedule_system_prompt(num_options: usize) -> String {
+    format!(
+        "You are a flight scheduling assistant. Always return valid JSON with exactly {} reschedule options. Each option must have: date_time (ISO 8601 format), reason (string explaining why this time is good), weather_score (float 0-10), and instructor_available (boolean).",
+        num_options
+    )
+}
+
+/// Default length of a new lesson
+pub const DEFAULT_LESSON_DURATION_MINUTES: i64 = 120;
+
+/// Length of new lessons from `LESSON_DURATION_MINUTES`, or the default
+pub fn lesson_duration_from_env() -> Duration {
+    let lesson_minutes = std::env::var("LESSON_DURATION_MINUTES")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_LESSON_DURATION_MINUTES);
+
+    Duration::minutes(lesson_minutes)
+}
+
+/// Furthest ahead an AI-suggested reschedule may be, when
+/// `RESCHEDULE_MAX_DAYS_AHEAD` is unset
+pub const DEFAULT_RESCHEDULE_MAX_DAYS_AHEAD: i64 = 7;
+
+/// How far ahead AI suggestions may be from `RESCHEDULE_MAX_DAYS_AHEAD`, or the default
+pub fn max_days_ahead_from_env() -> Duration {
+    let days = std::env::var("RESCHEDULE_MAX_DAYS_AHEAD")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|days: &i64| *days > 0)
+        .unwrap_or(DEFAULT_RESCHEDULE_MAX_DAYS_AHEAD);
+
+    Duration::days(days)
+}
+
+/// Span of time the forecast says anything about: from its first entry to
+/// one forecast step past its last, since each entry stands until the next.
+/// None for an empty forecast.
+fn forecast_window(forecast: &[WeatherData]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut times: Vec<DateTime<Utc>> = forecast.iter().map(|weather| weather.date_time).collect();
+    times.sort();
+
+    let (first, last) = (*times.first()?, *times.last()?);
+    let step = match times.len() {
+        0 | 1 => Duration::zero(),
+        n => last - times[n - 2],
+    };
+    Some((first, last + step))
+}
+
+/// Whether the booking's instructor is free for the booking's lesson length
+/// starting at `date_time`, i.e. none of their other lessons overlaps it.
+/// Bookings with a different instructor don't count, and a booking with no
+/// instructor assigned is never blocked.
+pub fn is_instructor_available(date_time: DateTime<Utc>, instructor_schedule: &[Booking], booking: &Booking) -> bool {
+    let candidate = (date_time, date_time + Duration::minutes(booking.lesson_duration_minutes));
+    !instructor_lessons(booking, instructor_schedule).any(|other| intervals_overlap(candidate, booking_interval(other)))
+}
+
+/// Start and end of every other lesson the booking's instructor is teaching,
+/// in time order
+pub fn instructor_busy_windows(booking: &Booking, instructor_schedule: &[Booking]) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut windows: Vec<_> = instructor_lessons(booking, instructor_schedule).map(booking_interval).collect();
+    windows.sort();
+    windows
+}
+
+/// Cached options are keyed by booking and start time, so moving a booking
+/// never serves options computed for its old slot
+pub fn reschedule_cache_key(booking: &Booking) -> String {
+    format!("{}_{}", booking.id, booking.scheduled_date.timestamp())
+}
+
+/// Non-cancelled bookings in `schedule` taught by the booking's instructor,
+/// other than the booking itself
+fn instructor_lessons<'a>(booking: &'a Booking, schedule: &'a [Booking]) -> impl Iterator<Item = &'a Booking> {
+    schedule.iter().filter(move |other| {
+        booking.instructor_id.is_some()
+            && other.instructor_id == booking.instructor_id
+            && other.id != booking.id
+            && other.status != BookingStatus::Cancelled
+    })
+}
+
+/// Night is only excluded when the forecast reports sunrise and sunset
+fn daylight_permits(weather: &WeatherData, allow_night: bool) -> bool {
+    match (weather.sunrise, weather.sunset) {
+        (Some(sunrise), Some(sunset)) if !allow_night => is_daylight(weather.date_time, sunrise, sunset),
+        _ => true,
     }
 }
 
+/// Local hours treated as daytime when the forecast has no sunrise/sunset
+const LOCAL_DAY_START_HOUR: u32 = 7;
+const LOCAL_DAY_END_HOUR: u32 = 19;
+
+/// The slot's time at the departure field, when the forecast reports its UTC offset
+fn local_time(weather: &WeatherData) -> Option<DateTime<FixedOffset>> {
+    let offset = FixedOffset::east_opt(weather.utc_offset_seconds?)?;
+    Some(weather.date_time.with_timezone(&offset))
+}
+
+/// Whether a slot is in local daytime: between sunrise and sunset when known,
+/// otherwise within normal local flying hours. Slots whose local time is
+/// unknown are not penalized.
+fn is_local_daytime(weather: &WeatherData) -> bool {
+    if let (Some(sunrise), Some(sunset)) = (weather.sunrise, weather.sunset) {
+        return is_daylight(weather.date_time, sunrise, sunset);
+    }
+    match local_time(weather) {
+        Some(local) => (LOCAL_DAY_START_HOUR..LOCAL_DAY_END_HOUR).contains(&local.hour()),
+        None => true,
+    }
+}
+
+/// " at 14:00 local" for reason text, or nothing when the offset is unknown
+fn local_time_suffix(weather: &WeatherData) -> String {
+    local_time(weather)
+        .map(|local| format!(" at {} local", local.format("%H:%M")))
+        .unwrap_or_default()
+}
+
+/// Minimum time between any two options picked from the forecast, so
+/// alternatives aren't a few hours apart on the same afternoon
+pub const MIN_OPTION_GAP_HOURS: i64 = 6;
+
+/// How the rule-based fallback picks reschedule options from the safe
+/// forecast slots. Slots where the instructor is free and it is local daytime
+/// always come first; the strategy decides the order after that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionSelection {
+    /// Earliest slots first
+    Soonest,
+    /// Highest weather score first
+    BestScore,
+    /// The best-scoring slot of each day, earliest day first, at most one per day
+    #[default]
+    SpreadAcrossDays,
+}
+
+impl OptionSelection {
+    /// Strategy from `RESCHEDULE_OPTION_SELECTION`, or the default when unset
+    /// or not recognized
+    pub fn from_env() -> Self {
+        match std::env::var("RESCHEDULE_OPTION_SELECTION") {
+            Ok(value) => value.parse().unwrap_or_else(|e| {
+                tracing::warn!("{}, using {:?}", e, Self::default());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Soonest => "soonest",
+            Self::BestScore => "best_score",
+            Self::SpreadAcrossDays => "spread_across_days",
+        }
+    }
+
+    /// Up to `count` slots, in the order they should be offered, no two
+    /// within `MIN_OPTION_GAP_HOURS` of each other
+    fn select<'a>(
+        &self,
+        slots: &[&'a WeatherData],
+        count: usize,
+        training_level: &TrainingLevel,
+        available_at: impl Fn(DateTime<Utc>) -> bool,
+    ) -> Vec<&'a WeatherData> {
+        let score = |weather: &WeatherData| calculate_weather_score(training_level, weather);
+        // Lower sorts first
+        let preference = |weather: &WeatherData| (!available_at(weather.date_time), !is_local_daytime(weather));
+
+        let mut candidates: Vec<&WeatherData> = slots.to_vec();
+        match self {
+            Self::Soonest => candidates.sort_by_key(|weather| (preference(weather), weather.date_time)),
+            Self::BestScore => candidates.sort_by(|a, b| {
+                preference(a)
+                    .cmp(&preference(b))
+                    .then(score(b).total_cmp(&score(a)))
+                    .then(a.date_time.cmp(&b.date_time))
+            }),
+            Self::SpreadAcrossDays => {
+                let mut best_by_day: HashMap<NaiveDate, &WeatherData> = HashMap::new();
+                for weather in candidates {
+                    let day = local_date(weather);
+                    let keep = match best_by_day.get(&day) {
+                        Some(best) => (preference(weather), -score(weather)) < (preference(best), -score(best)),
+                        None => true,
+                    };
+                    if keep {
+                        best_by_day.insert(day, weather);
+                    }
+                }
+                candidates = best_by_day.into_values().collect();
+                candidates.sort_by_key(|weather| (preference(weather), weather.date_time));
+            }
+        }
+
+        let mut chosen: Vec<&WeatherData> = Vec::new();
+        for weather in candidates {
+            if chosen.len() == count {
+                break;
+            }
+            let clear_of_others = chosen.iter().all(|other| {
+                (weather.date_time - other.date_time).num_minutes().abs() >= MIN_OPTION_GAP_HOURS * 60
+            });
+            if clear_of_others {
+                chosen.push(weather);
+            }
+        }
+        chosen
+    }
+}
+
+impl FromStr for OptionSelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "soonest" => Ok(Self::Soonest),
+            "best_score" => Ok(Self::BestScore),
+            "spread_across_days" => Ok(Self::SpreadAcrossDays),
+            other => anyhow::bail!(
+                "Unknown reschedule option selection: {}. Must be one of: soonest, best_score, spread_across_days",
+                other
+            ),
+        }
+    }
+}
+
+/// Wind for a prompt line, e.g. "250° 12.0kt gusting 22kt"; direction is
+/// left out when unknown or variable
+fn wind_summary(weather: &WeatherData) -> String {
+    let direction = weather
+        .wind_deg
+        .map(|deg| format!("{:03.0}° ", deg))
+        .unwrap_or_default();
+    let gusts = weather
+        .wind_gust_knots
+        .filter(|gust| *gust > weather.wind_speed_knots)
+        .map(|gust| format!(" gusting {:.0}kt", gust))
+        .unwrap_or_default();
+    format!("{}{:.1}kt{}", direction, weather.wind_speed_knots, gusts)
+}
+
+/// Calendar day of the slot at the departure field, or in UTC when the
+/// forecast has no offset
+fn local_date(weather: &WeatherData) -> NaiveDate {
+    local_time(weather)
+        .map(|local| local.date_naive())
+        .unwrap_or_else(|| weather.date_time.date_naive())
+}
+
 pub struct AiRescheduleClient {
-    client: reqwest::Client,
-    api_key: String,
-    base_url: String,
-    cache: Arc<AiCache>,
+    backend: Box<dyn AiBackend>,
+    cache: Arc<dyn AiCacheStore>,
+    selection: OptionSelection,
+    max_days_ahead: Duration,
 }
 
 impl AiRescheduleClient {
-    pub fn new(api_key: String, cache: Arc<AiCache>) -> Self {
+    /// Create a client backed by OpenAI with the default model
+    pub fn new(api_key: String, cache: Arc<dyn AiCacheStore>) -> Self {
+        Self::with_backend(Box::new(OpenAiBackend::new(api_key, None, None)), cache)
+    }
+
+    /// Create a client with the default option selection and horizon; the
+    /// environment is only read by `from_env` and `with_options_from_env`
+    pub fn with_backend(backend: Box<dyn AiBackend>, cache: Arc<dyn AiCacheStore>) -> Self {
         Self {
-            client: reqwest::Client::new(),
-            api_key,
-            base_url: "https://api.openai.com/v1/chat/completions".to_string(),
+            backend,
             cache,
+            selection: OptionSelection::default(),
+            max_days_ahead: Duration::days(DEFAULT_RESCHEDULE_MAX_DAYS_AHEAD),
         }
     }
 
-    pub fn from_env(cache: Arc<AiCache>) -> Result<Self> {
-        let api_key = std::env::var("OPENAI_API_KEY")
-            .context("OPENAI_API_KEY environment variable not set")?;
+    /// Use `selection` for rule-based options
+    pub fn with_selection(mut self, selection: OptionSelection) -> Self {
+        self.selection = selection;
+        self
+    }
 
-        // Skip AI if using placeholder key
-        if api_key == "your_openai_api_key_here" {
-            anyhow::bail!("OpenAI API key not configured, using placeholder");
-        }
+    /// Reject AI suggestions more than `max_days_ahead` out
+    pub fn with_max_days_ahead(mut self, max_days_ahead: Duration) -> Self {
+        self.max_days_ahead = max_days_ahead;
+        self
+    }
 
-        Ok(Self::new(api_key, cache))
+    /// Take the option selection and horizon from `RESCHEDULE_OPTION_SELECTION`
+    /// and `RESCHEDULE_MAX_DAYS_AHEAD`
+    pub fn with_options_from_env(self) -> Self {
+        self.with_selection(OptionSelection::from_env())
+            .with_max_days_ahead(max_days_ahead_from_env())
     }
 
+    /// Create a client using the provider selected by `AI_PROVIDER`, with
+    /// options from the environment
+    pub fn from_env(cache: Arc<dyn AiCacheStore>) -> Result<Self> {
+        Ok(Self::with_backend(backend_from_env()?, cache).with_options_from_env())
+    }
+
+    /// False when the backend has no real API key, in which case every
+    /// request goes straight to the rule-based options
+    pub fn is_configured(&self) -> bool {
+        self.backend.is_configured()
+    }
+
+    /// Reschedule options for a booking, served from the cache while it is fresh
     pub async fn generate_reschedule_options(
         &self,
         booking: &Booking,
         student: &Student,
         weather_forecast: &[WeatherData],
         instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+        num_options: usize,
     ) -> Result<Vec<RescheduleOption>> {
-        // Check cache first
-        let cache_key = format!("{}_{}", booking.id, booking.scheduled_date.timestamp());
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            if cached.options.len() >= 3 {
-                return Ok(cached.options);
-            }
+        if let Some(cached) = self.cached_reschedule_options(booking, num_options).await {
+            return Ok(cached);
         }
 
-        // Try AI first
-        match self
-            .generate_with_ai(booking, student, weather_forecast, instructor_schedule)
-            .await
-        {
-            Ok(options) if options.len() >= 3 => {
-                // Cache successful response
-                self.cache
-                    .set(cache_key, RescheduleResponse { options: options.clone() })
-                    .await;
-                Ok(options)
-            }
-            _ => {
-                // Fallback to rule-based
-                tracing::warn!("AI reschedule failed or insufficient options, using fallback");
-                self.generate_fallback_options(booking, student, weather_forecast, instructor_schedule)
-                    .await
-            }
-        }
+        self.refresh_reschedule_options(
+            booking,
+            student,
+            weather_forecast,
+            instructor_schedule,
+            minimums,
+            num_options,
+        )
+        .await
     }
 
-    async fn generate_with_ai(
+    /// The first `num_options` cached options for a booking, if at least that
+    /// many are cached and still fresh
+    pub async fn cached_reschedule_options(&self, booking: &Booking, num_options: usize) -> Option<Vec<RescheduleOption>> {
+        let cached = self.cache.get(&reschedule_cache_key(booking)).await?;
+        (cached.options.len() >= num_options).then(|| cached.options.into_iter().take(num_options).collect())
+    }
+
+    /// Generate new reschedule options without looking at the cache; a
+    /// successful AI response replaces any cached options for the booking.
+    /// AI suggestions outside the forecast or too far ahead are dropped and
+    /// the gap is filled with rule-based options.
+    pub async fn refresh_reschedule_options(
         &self,
         booking: &Booking,
         student: &Student,
         weather_forecast: &[WeatherData],
         instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+        num_options: usize,
     ) -> Result<Vec<RescheduleOption>> {
-        // Skip AI call if using dummy/placeholder key
-        if self.api_key == "dummy_key" || self.api_key == "your_openai_api_key_here" {
-            anyhow::bail!("AI not configured, skipping API call");
+        if !self.is_configured() {
+            // Already reported at startup; don't warn on every request
+            return self
+                .generate_fallback_options(booking, student, weather_forecast, instructor_schedule, minimums, num_options)
+                .await;
         }
 
-        let prompt = self.build_prompt(booking, student, weather_forecast, instructor_schedule);
+        // Try AI first
+        let mut options = match self
+            .generate_with_ai(booking, student, weather_forecast, instructor_schedule, minimums, num_options)
+            .await
+        {
+            Ok(options) => self.within_horizon(options, weather_forecast),
+            Err(e) => {
+                tracing::warn!("AI reschedule failed: {}", e);
+                Vec::new()
+            }
+        };
 
-        #[derive(Serialize)]
-        struct ChatMessage {
-            role: String,
-            content: String,
+        if options.len() >= num_options {
+            options.truncate(num_options);
+            // Cache successful response
+            self.cache
+                .set(reschedule_cache_key(booking), RescheduleResponse { options: options.clone() })
+                .await;
+            return Ok(options);
         }
 
-        #[derive(Serialize)]
-        struct ChatRequest {
-            model: String,
-            messages: Vec<ChatMessage>,
-            temperature: f32,
-            response_format: serde_json::Value,
+        // Backfill with rule-based options at times the AI didn't already offer
+        tracing::warn!(
+            "AI returned {} usable of {} reschedule options, filling the rest from the fallback",
+            options.len(),
+            num_options
+        );
+        let fallback = self
+            .generate_fallback_options(booking, student, weather_forecast, instructor_schedule, minimums, num_options)
+            .await?;
+        for option in fallback {
+            if options.len() >= num_options {
+                break;
+            }
+            if !options.iter().any(|offered| offered.date_time == option.date_time) {
+                options.push(option);
+            }
         }
+        Ok(options)
+    }
 
-        let request = ChatRequest {
-            model: "gpt-4o-mini".to_string(),
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: "You are a flight scheduling assistant. Always return valid JSON with exactly 3 reschedule options. Each option must have: date_time (ISO 8601 format), reason (string explaining why this time is good), weather_score (float 0-10), and instructor_available (boolean).".to_string(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: prompt,
-                },
-            ],
-            temperature: 0.7,
-            response_format: serde_json::json!({ "type": "json_object" }),
+    /// AI options that fall inside the forecast window and no further ahead
+    /// than `max_days_ahead`; anything else was not grounded in real weather
+    fn within_horizon(&self, options: Vec<RescheduleOption>, weather_forecast: &[WeatherData]) -> Vec<RescheduleOption> {
+        let Some((window_start, window_end)) = forecast_window(weather_forecast) else {
+            tracing::warn!("No forecast to check AI reschedule options against, discarding them");
+            return Vec::new();
         };
+        let latest = Utc::now() + self.max_days_ahead;
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to call OpenAI API")?;
+        options
+            .into_iter()
+            .filter(|option| {
+                let in_range = option.date_time >= window_start
+                    && option.date_time <= window_end
+                    && option.date_time <= latest;
+                if !in_range {
+                    tracing::warn!(
+                        "Discarding AI reschedule option at {}: outside the forecast ({} to {}) or more than {} days ahead",
+                        option.date_time,
+                        window_start,
+                        window_end,
+                        self.max_days_ahead.num_days()
+                    );
+                }
+                in_range
+            })
+            .collect()
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            tracing::error!("OpenAI API error - Status: {}, Body: {}", status, error_text);
-            anyhow::bail!("OpenAI API returned status: {} - {}", status, error_text);
-        }
+    async fn generate_with_ai(
+        &self,
+        booking: &Booking,
+        student: &Student,
+        weather_forecast: &[WeatherData],
+        instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+        num_options: usize,
+    ) -> Result<Vec<RescheduleOption>> {
+        let prompt = self.build_prompt(booking, student, weather_forecast, instructor_schedule, minimums, num_options);
 
-        #[derive(Deserialize)]
-        struct ChatResponse {
-            choices: Vec<Choice>,
-        }
+        let system_prompt = reschedule_system_prompt(num_options);
 
-        #[derive(Deserialize)]
-        struct Choice {
-            message: Message,
-        }
+        let content = self.backend.complete(&system_prompt, &prompt).await?;
+        tracing::debug!("AI reschedule response (attempt 1): {}", content);
 
-        #[derive(Deserialize)]
-        struct Message {
-            content: String,
-        }
+        let parse_error = match serde_json::from_str::<RescheduleResponse>(&content) {
+            Ok(response) => return Ok(response.options),
+            Err(e) => e,
+        };
 
-        let chat_response: ChatResponse = response
-            .json()
-            .await
-            .context("Failed to parse OpenAI response")?;
+        // Give the model one chance to repair its own output before falling back
+        tracing::debug!("AI response was not a valid RescheduleResponse ({}), retrying", parse_error);
+
+        let repair_prompt = format!(
+            "{}\n\nYour previous response was not valid JSON matching this schema ({}). Fix it and return only the corrected JSON.\n\nPrevious response:\n{}",
+            prompt, parse_error, content
+        );
 
-        let content = chat_response
-            .choices
-            .first()
-            .map(|c| &c.message.content)
-            .context("No choices in OpenAI response")?;
+        let repaired = self.backend.complete(&system_prompt, &repair_prompt).await?;
+        tracing::debug!("AI reschedule response (attempt 2): {}", repaired);
 
-        let reschedule_response: RescheduleResponse = serde_json::from_str(content)
+        let reschedule_response: RescheduleResponse = serde_json::from_str(&repaired)
             .context("Failed to parse AI response as RescheduleResponse")?;
 
         Ok(reschedule_response.options)
@@ -234,38 +563,112 @@ impl AiRescheduleClient {
         booking: &Booking,
         student: &Student,
         weather_forecast: &[WeatherData],
-        _instructor_schedule: &[Booking],
+        instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+        num_options: usize,
     ) -> String {
         let weather_summary: String = weather_forecast
             .iter()
             .take(7)
             .map(|w| {
+                let available =
+                    is_instructor_available(w.date_time, instructor_schedule, booking);
+                let local = local_time(w)
+                    .map(|local| format!(" ({} local)", local.format("%H:%M")))
+                    .unwrap_or_default();
+                // A small temperature/dew point spread warns of fog
+                let dew_point = w
+                    .dew_point_f
+                    .map(|dew_point| format!(", dew point {:.0}°F", dew_point))
+                    .unwrap_or_default();
+                let precip = w
+                    .precip_probability
+                    .map(|pop| format!(", {:.0}% chance of precip", pop * 100.0))
+                    .unwrap_or_default();
                 format!(
-                    "{}: vis {:.1}mi, wind {:.1}kt, temp {:.0}°F, {}",
+                    "{} UTC{}: vis {:.1}mi, wind {}, temp {:.0}°F{}{}, {}, instructor {}",
                     w.date_time.format("%Y-%m-%d %H:%M"),
+                    local,
                     w.visibility_miles,
-                    w.wind_speed_knots,
+                    wind_summary(w),
                     w.temperature_f,
-                    w.conditions
+                    dew_point,
+                    precip,
+                    w.conditions,
+                    if available { "available" } else { "booked" }
                 )
             })
             .collect::<Vec<_>>()
             .join("\n");
 
+        let daylight_window = weather_forecast
+            .iter()
+            .find_map(|w| w.sunrise.zip(w.sunset))
+            .map(|(sunrise, sunset)| {
+                format!(
+                    "\nDaylight window: sunrise {} UTC, sunset {} UTC\n",
+                    sunrise.format("%H:%M"),
+                    sunset.format("%H:%M")
+                )
+            })
+            .unwrap_or_default();
+
+        let busy_windows = instructor_busy_windows(booking, instructor_schedule);
+        let instructor_schedule_summary = if booking.instructor_id.is_none() {
+            "No instructor assigned yet".to_string()
+        } else if busy_windows.is_empty() {
+            "Instructor has no other lessons booked".to_string()
+        } else {
+            let windows = busy_windows
+                .iter()
+                .map(|(start, end)| format!("- {} to {} UTC", start.format("%Y-%m-%d %H:%M"), end.format("%H:%M")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("Instructor is already booked (do not suggest overlapping times):\n{}", windows)
+        };
+
+        let timezone = weather_forecast
+            .iter()
+            .find_map(local_time)
+            .map(|local| format!("Local time at the departure field is UTC{}\n", local.format("%:z")))
+            .unwrap_or_default();
+
+        let trend = weather_trend(&student.training_level, weather_forecast, booking.scheduled_date)
+            .map(|trend| format!(" (weather {} toward this time)", trend.as_str()))
+            .unwrap_or_default();
+
+        let gusts = if student.training_level == TrainingLevel::StudentPilot {
+            "\n5. Avoid gusty conditions: student pilots should not train when gusts are forecast well above the sustained wind"
+        } else {
+            ""
+        };
+
+        let time_of_day = if minimums.allow_night {
+            "Time of day (prefer local daylight hours, not just daytime in UTC)".to_string()
+        } else {
+            format!(
+                "Daylight only: night flight is not allowed for {:?}, so every option must fall between sunrise and sunset",
+                student.training_level
+            )
+        };
+
         format!(
             r#"Flight booking needs rescheduling due to weather conflict.
 
 Student: {} (Training Level: {:?})
-Original booking: {}
+Original booking: {}{}
 Departure location: {}
 
 7-day weather forecast:
 {}
+{}{}
+{}
 
-Please suggest 3 alternative times for rescheduling this flight lesson. Consider:
+Please suggest {} alternative times for rescheduling this flight lesson. Consider:
 1. Weather conditions suitable for {:?} training level
-2. Time of day (prefer daylight hours)
+2. {}
 3. Spread options across different days
+4. Only suggest times when the instructor is available; set instructor_available accordingly{}
 
 Return JSON with this exact structure:
 {{
@@ -282,9 +685,16 @@ Return JSON with this exact structure:
             student.name,
             student.training_level,
             booking.scheduled_date.format("%Y-%m-%d %H:%M UTC"),
+            trend,
             booking.departure_location.name,
             weather_summary,
-            student.training_level
+            daylight_window,
+            timezone,
+            instructor_schedule_summary,
+            num_options,
+            student.training_level,
+            time_of_day,
+            gusts
         )
     }
 
@@ -293,50 +703,62 @@ Return JSON with this exact structure:
         booking: &Booking,
         student: &Student,
         weather_forecast: &[WeatherData],
-        _instructor_schedule: &[Booking],
+        instructor_schedule: &[Booking],
+        student_minimums: &WeatherMinimum,
+        num_options: usize,
     ) -> Result<Vec<RescheduleOption>> {
-        use crate::weather::{calculate_weather_score, default_weather_minimums};
-
-        let minimums = default_weather_minimums();
-        let student_minimums = minimums
-            .get(&student.training_level)
-            .context("No minimums for training level")?;
-
-        let mut options = Vec::new();
-
-        for weather in weather_forecast.iter().take(14) {
-            if options.len() >= 3 {
-                break;
-            }
+        let available_at = |date_time: DateTime<Utc>| {
+            is_instructor_available(date_time, instructor_schedule, booking)
+        };
 
-            let (is_safe, _) = is_flight_safe(&student.training_level, weather, student_minimums);
+        let safe_slots: Vec<&WeatherData> = safe_windows(
+            &student.training_level,
+            weather_forecast,
+            student_minimums,
+            booking.departure_location.elevation_ft,
+        )
+        .into_iter()
+        .filter(|weather| daylight_permits(weather, student_minimums.allow_night))
+        .collect();
 
-            if is_safe {
-                let score = calculate_weather_score(&student.training_level, weather);
-                options.push(RescheduleOption {
-                    date_time: weather.date_time,
-                    reason: format!("Good weather conditions: {} with {:.0}kt winds", weather.conditions, weather.wind_speed_knots),
-                    weather_score: score,
-                    instructor_available: true, // Simplified assumption
-                });
-            }
-        }
+        let mut options: Vec<RescheduleOption> = self
+            .selection
+            .select(&safe_slots, num_options, &student.training_level, available_at)
+            .into_iter()
+            .map(|weather| RescheduleOption {
+                date_time: weather.date_time,
+                reason: format!(
+                    "Good weather conditions{}: {} with {:.0}kt winds",
+                    local_time_suffix(weather),
+                    weather.conditions,
+                    weather.wind_speed_knots
+                ),
+                weather_score: calculate_weather_score(&student.training_level, weather),
+                instructor_available: available_at(weather.date_time),
+            })
+            .collect();
 
-        // If still not enough options, add marginal weather days
-        if options.len() < 3 {
-            for weather in weather_forecast.iter().skip(options.len()).take(3 - options.len()) {
+        // If still not enough options, add marginal weather slots not already offered
+        if options.len() < num_options {
+            let offered: Vec<DateTime<Utc>> = options.iter().map(|option| option.date_time).collect();
+            for weather in weather_forecast
+                .iter()
+                .filter(|weather| daylight_permits(weather, student_minimums.allow_night))
+                .filter(|weather| !offered.contains(&weather.date_time))
+                .take(num_options - options.len())
+            {
                 let score = calculate_weather_score(&student.training_level, weather);
                 options.push(RescheduleOption {
                     date_time: weather.date_time,
-                    reason: format!("Marginal conditions: {}", weather.conditions),
+                    reason: format!("Marginal conditions{}: {}", local_time_suffix(weather), weather.conditions),
                     weather_score: score,
-                    instructor_available: true,
+                    instructor_available: available_at(weather.date_time),
                 });
             }
         }
 
         // If STILL not enough options (forecast too short), add placeholder options
-        while options.len() < 3 {
+        while options.len() < num_options {
             let days_ahead = options.len() + 1;
             let placeholder_date = booking.scheduled_date + chrono::Duration::days(days_ahead as i64);
             options.push(RescheduleOption {
@@ -355,19 +777,34 @@ Return JSON with this exact structure:
 mod tests {
     use super::*;
     use crate::models::{BookingStatus, Location, TrainingLevel};
+    use crate::weather::default_weather_minimums;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Cache with the default TTL, independent of `AI_CACHE_TTL_HOURS`
+    fn default_ttl_cache() -> Arc<AiCache> {
+        Arc::new(AiCache::with_ttl(DEFAULT_AI_CACHE_TTL_HOURS))
+    }
 
     fn create_test_booking() -> Booking {
         Booking {
             id: "test123".to_string(),
             student_id: "student1".to_string(),
+            instructor_id: Some("instructor1".to_string()),
+            aircraft_id: None,
             aircraft_type: "Cessna 172".to_string(),
             scheduled_date: Utc::now(),
             departure_location: Location {
                 lat: 33.8113,
                 lon: -118.1515,
                 name: "KTOA".to_string(),
+                elevation_ft: None,
             },
             status: BookingStatus::Scheduled,
+            lesson_duration_minutes: DEFAULT_LESSON_DURATION_MINUTES,
+            weather_minimum_override: None,
+            created_at: None,
+            updated_at: None,
+            deleted_at: None,
         }
     }
 
@@ -378,9 +815,20 @@ mod tests {
             email: "john@example.com".to_string(),
             phone: "+1234567890".to_string(),
             training_level: TrainingLevel::StudentPilot,
+            notify_email: true,
+            notify_sms: true,
+            created_at: None,
+            updated_at: None,
+            deleted_at: None,
         }
     }
 
+    fn student_minimums() -> WeatherMinimum {
+        default_weather_minimums()
+            .remove(&TrainingLevel::StudentPilot)
+            .unwrap()
+    }
+
     fn create_test_weather() -> Vec<WeatherData> {
         vec![
             WeatherData {
@@ -392,6 +840,7 @@ mod tests {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now(),
+                ..Default::default()
             },
             WeatherData {
                 visibility_miles: 8.0,
@@ -402,6 +851,7 @@ mod tests {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now() + chrono::Duration::hours(24),
+                ..Default::default()
             },
             WeatherData {
                 visibility_miles: 6.0,
@@ -412,13 +862,14 @@ mod tests {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now() + chrono::Duration::hours(48),
+                ..Default::default()
             },
         ]
     }
 
     #[tokio::test]
     async fn test_cache() {
-        let cache = AiCache::new();
+        let cache = AiCache::with_ttl(DEFAULT_AI_CACHE_TTL_HOURS);
         let key = "test_key".to_string();
 
         // Cache miss
@@ -434,9 +885,21 @@ mod tests {
         assert!(cache.get(&key).await.is_some());
     }
 
+    #[tokio::test]
+    async fn test_zero_ttl_expires_immediately() {
+        let cache = AiCache::with_ttl(0);
+        let key = "test_key".to_string();
+
+        cache.set(key.clone(), RescheduleResponse { options: vec![] }).await;
+        assert!(cache.get(&key).await.is_none());
+
+        cache.clear_expired().await;
+        assert!(cache.cache.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_fallback_generation() {
-        let cache = Arc::new(AiCache::new());
+        let cache = default_ttl_cache();
         // Use dummy key since we won't make real API calls
         let client = AiRescheduleClient::new("dummy_key".to_string(), cache);
 
@@ -445,11 +908,445 @@ mod tests {
         let weather = create_test_weather();
 
         let options = client
-            .generate_fallback_options(&booking, &student, &weather, &[])
+            .generate_fallback_options(&booking, &student, &weather, &[], &student_minimums(), 5)
             .await
             .unwrap();
 
-        assert_eq!(options.len(), 3);
+        assert_eq!(options.len(), 5);
         assert!(options[0].weather_score > 0.0);
     }
+
+    #[tokio::test]
+    async fn test_fallback_prefers_available_instructor() {
+        let cache = default_ttl_cache();
+        let client = AiRescheduleClient::new("dummy_key".to_string(), cache);
+
+        let booking = create_test_booking();
+        let student = create_test_student();
+        let mut weather = create_test_weather();
+        weather.push(WeatherData {
+            date_time: Utc::now() + chrono::Duration::hours(72),
+            ..weather[0].clone()
+        });
+
+        // Another lesson 30 minutes after the first forecast slot
+        let conflicting = Booking {
+            id: "other".to_string(),
+            scheduled_date: weather[0].date_time + chrono::Duration::minutes(30),
+            ..create_test_booking()
+        };
+
+        assert!(!is_instructor_available(
+            weather[0].date_time,
+            std::slice::from_ref(&conflicting),
+            &booking,
+        ));
+
+        // Another instructor's lesson at the same time doesn't block this one
+        let other_instructor = Booking {
+            instructor_id: Some("instructor2".to_string()),
+            ..conflicting.clone()
+        };
+        assert!(is_instructor_available(
+            weather[0].date_time,
+            std::slice::from_ref(&other_instructor),
+            &booking,
+        ));
+
+        let options = client
+            .generate_fallback_options(
+                &booking,
+                &student,
+                &weather,
+                &[conflicting],
+                &student_minimums(),
+                DEFAULT_RESCHEDULE_OPTIONS,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(options.len(), 3);
+        assert!(options.iter().all(|o| o.instructor_available));
+        assert!(options.iter().all(|o| o.date_time != weather[0].date_time));
+    }
+
+    #[test]
+    fn test_prompt_names_instructor_busy_windows() {
+        let client = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache());
+        let booking = create_test_booking();
+        let lesson_at = "2030-06-01T15:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let schedule = vec![
+            Booking {
+                id: "mine".to_string(),
+                scheduled_date: lesson_at,
+                ..create_test_booking()
+            },
+            Booking {
+                id: "someone_else".to_string(),
+                instructor_id: Some("instructor2".to_string()),
+                scheduled_date: lesson_at + chrono::Duration::days(1),
+                ..create_test_booking()
+            },
+        ];
+
+        let prompt = client.build_prompt(
+            &booking,
+            &create_test_student(),
+            &create_test_weather(),
+            &schedule,
+            &student_minimums(),
+            DEFAULT_RESCHEDULE_OPTIONS,
+        );
+
+        assert!(prompt.contains("- 2030-06-01 15:00 to 17:00 UTC"), "{}", prompt);
+        assert!(!prompt.contains("2030-06-02"), "Other instructors' lessons are not listed");
+    }
+
+    /// Backend that replays canned replies in order
+    struct ScriptedBackend {
+        replies: std::sync::Mutex<Vec<String>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl AiBackend for ScriptedBackend {
+        async fn complete(&self, _system: &str, _user: &str) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.replies.lock().unwrap().remove(0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_malformed_ai_response_retried() {
+        let valid = serde_json::json!({
+            "options": (1..=3).map(|day| serde_json::json!({
+                "date_time": (Utc::now() + chrono::Duration::days(day)).to_rfc3339(),
+                "reason": "Clear skies",
+                "weather_score": 9.0,
+                "instructor_available": true,
+            })).collect::<Vec<_>>()
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let backend = ScriptedBackend {
+            replies: std::sync::Mutex::new(vec![
+                "Sure! Here are some options: {\"options\": [".to_string(),
+                valid.to_string(),
+            ]),
+            calls: calls.clone(),
+        };
+        let client = AiRescheduleClient::with_backend(Box::new(backend), default_ttl_cache());
+
+        let options = client
+            .generate_with_ai(
+                &create_test_booking(),
+                &create_test_student(),
+                &create_test_weather(),
+                &[],
+                &student_minimums(),
+                3,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].reason, "Clear skies");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ignores_cached_options() {
+        let booking = create_test_booking();
+        let fresh = serde_json::json!({
+            "options": (1..=3).map(|day| serde_json::json!({
+                "date_time": (Utc::now() + chrono::Duration::days(day)).to_rfc3339(),
+                "reason": "Forecast improved",
+                "weather_score": 9.5,
+                "instructor_available": true,
+            })).collect::<Vec<_>>()
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let backend = ScriptedBackend {
+            replies: std::sync::Mutex::new(vec![fresh.to_string()]),
+            calls: calls.clone(),
+        };
+
+        let cache = default_ttl_cache();
+        let stale = RescheduleOption {
+            date_time: Utc::now() + chrono::Duration::days(1),
+            reason: "Stale".to_string(),
+            weather_score: 5.0,
+            instructor_available: true,
+        };
+        cache
+            .set(reschedule_cache_key(&booking), RescheduleResponse { options: vec![stale; 3] })
+            .await;
+        let client = AiRescheduleClient::with_backend(Box::new(backend), cache.clone());
+
+        let cached = client
+            .generate_reschedule_options(&booking, &create_test_student(), &create_test_weather(), &[], &student_minimums(), 3)
+            .await
+            .unwrap();
+        assert_eq!(cached[0].reason, "Stale");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let refreshed = client
+            .refresh_reschedule_options(&booking, &create_test_student(), &create_test_weather(), &[], &student_minimums(), 3)
+            .await
+            .unwrap();
+        assert_eq!(refreshed[0].reason, "Forecast improved");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // The fresh options replace the stale ones in the cache
+        let cached = cache.get(&reschedule_cache_key(&booking)).await.unwrap();
+        assert_eq!(cached.options[0].reason, "Forecast improved");
+    }
+
+    #[tokio::test]
+    async fn test_ai_option_beyond_forecast_replaced() {
+        let suggestion = |date_time: DateTime<Utc>| {
+            serde_json::json!({
+                "date_time": date_time.to_rfc3339(),
+                "reason": "AI pick",
+                "weather_score": 9.0,
+                "instructor_available": true,
+            })
+        };
+        let weather = create_test_weather();
+        let beyond_forecast = Utc::now() + chrono::Duration::days(30);
+        let reply = serde_json::json!({
+            "options": [
+                suggestion(weather[0].date_time + chrono::Duration::hours(3)),
+                suggestion(weather[1].date_time + chrono::Duration::hours(3)),
+                suggestion(beyond_forecast),
+            ]
+        });
+        let backend = ScriptedBackend {
+            replies: std::sync::Mutex::new(vec![reply.to_string()]),
+            calls: Arc::new(AtomicUsize::new(0)),
+        };
+        let booking = create_test_booking();
+        let cache = default_ttl_cache();
+        let client = AiRescheduleClient::with_backend(Box::new(backend), cache.clone())
+            .with_max_days_ahead(chrono::Duration::days(DEFAULT_RESCHEDULE_MAX_DAYS_AHEAD));
+
+        let options = client
+            .refresh_reschedule_options(&booking, &create_test_student(), &weather, &[], &student_minimums(), 3)
+            .await
+            .unwrap();
+
+        assert_eq!(options.len(), 3);
+        assert!(options.iter().all(|option| option.date_time != beyond_forecast));
+        assert_eq!(options.iter().filter(|option| option.reason == "AI pick").count(), 2);
+        assert!(weather.iter().any(|w| w.date_time == options[2].date_time), "{:?}", options[2]);
+        // A partly rule-based answer isn't cached as the AI's
+        assert!(cache.get(&reschedule_cache_key(&booking)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_client_never_calls_provider() {
+        use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let cache = default_ttl_cache();
+        let backend = OpenAiBackend::new("dummy_key".to_string(), None, Some(server.uri()));
+        let client = AiRescheduleClient::with_backend(Box::new(backend), cache.clone());
+        assert!(!client.is_configured());
+        assert!(!AiRescheduleClient::new(String::new(), cache.clone()).is_configured());
+        assert!(AiRescheduleClient::new("sk-test".to_string(), cache.clone()).is_configured());
+
+        let booking = create_test_booking();
+        let options = client
+            .generate_reschedule_options(
+                &booking,
+                &create_test_student(),
+                &create_test_weather(),
+                &[],
+                &student_minimums(),
+                1,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(options.len(), 1);
+        assert!(options[0].reason.starts_with("Good weather conditions"), "{}", options[0].reason);
+        assert!(cache.get(&reschedule_cache_key(&booking)).await.is_none());
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_stalled_ai_times_out_to_fallback() {
+        use crate::util::HttpTimeouts;
+        use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let backend = OpenAiBackend::new("test_key".to_string(), None, Some(server.uri())).with_timeouts(HttpTimeouts {
+            total: std::time::Duration::from_millis(100),
+            connect: std::time::Duration::from_millis(100),
+        });
+        let client = AiRescheduleClient::with_backend(Box::new(backend), default_ttl_cache());
+
+        let started = std::time::Instant::now();
+        let options = client
+            .generate_reschedule_options(
+                &create_test_booking(),
+                &create_test_student(),
+                &create_test_weather(),
+                &[],
+                &student_minimums(),
+                1,
+            )
+            .await
+            .unwrap();
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(options.len(), 1);
+        assert!(options[0].reason.starts_with("Good weather conditions"), "{}", options[0].reason);
+    }
+
+    #[tokio::test]
+    async fn test_local_midnight_slot_deprioritized() {
+        let client = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache());
+        let booking = create_test_booking();
+        let day = (Utc::now() + chrono::Duration::days(1)).date_naive();
+        let pacific = Some(-8 * 3600);
+
+        // 08:00 UTC is midnight in UTC-8; 20:00 UTC is local noon
+        let local_midnight = WeatherData {
+            date_time: day.and_hms_opt(8, 0, 0).unwrap().and_utc(),
+            utc_offset_seconds: pacific,
+            ..create_test_weather()[0].clone()
+        };
+        let local_noon = WeatherData {
+            date_time: day.and_hms_opt(20, 0, 0).unwrap().and_utc(),
+            ..local_midnight.clone()
+        };
+        let forecast = vec![local_midnight.clone(), local_noon.clone()];
+
+        let options = client
+            .generate_fallback_options(&booking, &create_test_student(), &forecast, &[], &student_minimums(), 1)
+            .await
+            .unwrap();
+        assert_eq!(options[0].date_time, local_noon.date_time);
+        assert!(options[0].reason.contains("12:00 local"), "{}", options[0].reason);
+
+        let prompt = client.build_prompt(
+            &booking,
+            &create_test_student(),
+            &forecast,
+            &[],
+            &student_minimums(),
+            DEFAULT_RESCHEDULE_OPTIONS,
+        );
+        assert!(prompt.contains("08:00 UTC (00:00 local)"), "{}", prompt);
+        assert!(prompt.contains("UTC-08:00"));
+    }
+
+    #[test]
+    fn test_prompt_includes_gusts_and_wind_direction() {
+        let client = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache());
+        let mut forecast = create_test_weather();
+        forecast[0].wind_deg = Some(250.0);
+        forecast[0].wind_gust_knots = Some(22.0);
+
+        let prompt = client.build_prompt(
+            &create_test_booking(),
+            &create_test_student(),
+            &forecast,
+            &[],
+            &student_minimums(),
+            DEFAULT_RESCHEDULE_OPTIONS,
+        );
+
+        assert!(prompt.contains("wind 250° 5.0kt gusting 22kt"), "{}", prompt);
+        assert!(prompt.contains("wind 8.0kt, "), "{}", prompt);
+        assert!(prompt.contains("Avoid gusty conditions"));
+    }
+
+    #[tokio::test]
+    async fn test_spread_selection_offers_one_slot_per_day() {
+        let client = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache());
+        let booking = create_test_booking();
+        let start = (Utc::now() + chrono::Duration::days(1))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+
+        // Three days of good weather every three hours, clearest mid-afternoon
+        let forecast: Vec<WeatherData> = (0..24)
+            .map(|i| {
+                let date_time = start + chrono::Duration::hours(i * 3);
+                WeatherData {
+                    date_time,
+                    visibility_miles: 10.0 - (date_time.hour() as f64 - 15.0).abs() / 5.0,
+                    ..create_test_weather()[0].clone()
+                }
+            })
+            .collect();
+
+        let spread = client
+            .generate_fallback_options(&booking, &create_test_student(), &forecast, &[], &student_minimums(), 3)
+            .await
+            .unwrap();
+        let mut days: Vec<_> = spread.iter().map(|o| o.date_time.date_naive()).collect();
+        days.dedup();
+        assert_eq!(days.len(), 3, "{:?}", spread);
+        assert!(spread.iter().all(|o| o.date_time.hour() == 15), "{:?}", spread);
+
+        let soonest = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache())
+            .with_selection(OptionSelection::Soonest)
+            .generate_fallback_options(&booking, &create_test_student(), &forecast, &[], &student_minimums(), 3)
+            .await
+            .unwrap();
+        let times: Vec<_> = soonest.iter().map(|o| o.date_time).collect();
+        assert_eq!(times, vec![start, start + chrono::Duration::hours(6), start + chrono::Duration::hours(12)]);
+        assert_eq!("best_score".parse::<OptionSelection>().unwrap(), OptionSelection::BestScore);
+    }
+
+    #[tokio::test]
+    async fn test_night_slot_rejected_for_student_pilot() {
+        let client = AiRescheduleClient::new("dummy_key".to_string(), default_ttl_cache());
+        let booking = create_test_booking();
+        let day = (Utc::now() + chrono::Duration::days(1)).date_naive();
+        let at = |hour: u32| day.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+
+        let night_slot = WeatherData {
+            date_time: at(22),
+            sunrise: Some(at(6)),
+            sunset: Some(at(19)),
+            utc_offset_seconds: None,
+            ..create_test_weather()[0].clone()
+        };
+        let forecast = vec![night_slot];
+
+        let student_options = client
+            .generate_fallback_options(&booking, &create_test_student(), &forecast, &[], &student_minimums(), 1)
+            .await
+            .unwrap();
+        assert_ne!(student_options[0].date_time, at(22));
+
+        let instrument_minimums = default_weather_minimums()
+            .remove(&TrainingLevel::InstrumentRated)
+            .unwrap();
+        let instrument_student = Student {
+            training_level: TrainingLevel::InstrumentRated,
+            ..create_test_student()
+        };
+        let instrument_options = client
+            .generate_fallback_options(&booking, &instrument_student, &forecast, &[], &instrument_minimums, 1)
+            .await
+            .unwrap();
+        assert_eq!(instrument_options[0].date_time, at(22));
+    }
 }
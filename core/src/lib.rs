@@ -2,5 +2,9 @@ pub mod models;
 pub mod weather;
 pub mod ai;
 pub mod notifications;
+pub mod prelude;
+pub mod util;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 pub use models::*;
@@ -1,10 +1,13 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::types::Json;
+use sqlx::{FromRow, SqlitePool};
+use utoipa::ToSchema;
 
 /// Training level of a student pilot
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+#[schema(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TrainingLevel {
     StudentPilot,
     PrivatePilot,
@@ -12,21 +15,39 @@ pub enum TrainingLevel {
 }
 
 /// Status of a booking
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+#[schema(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BookingStatus {
     Scheduled,
+    /// Forecast is below minimums; cancelled if still unsafe close to the
+    /// flight, back to `Scheduled` if the weather improves first
+    WeatherHold,
     Cancelled,
     Rescheduled,
     Completed,
 }
 
 /// Geographic location with coordinates
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Location {
     pub lat: f64,
     pub lon: f64,
     pub name: String,
+    /// Field elevation, used for density altitude checks
+    #[serde(default)]
+    pub elevation_ft: Option<f64>,
+}
+
+impl Location {
+    /// The location's ICAO station identifier, when its name is one (e.g. "KTOA")
+    pub fn icao_id(&self) -> Option<&str> {
+        let name = self.name.as_str();
+        let is_icao = name.len() == 4
+            && name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        is_icao.then_some(name)
+    }
 }
 
 /// Student pilot information
@@ -38,13 +59,35 @@ pub struct Student {
     pub phone: String,
     #[sqlx(try_from = "String")]
     pub training_level: TrainingLevel,
+    /// Whether cancellation notices go out by email
+    pub notify_email: bool,
+    /// Whether cancellation notices go out by SMS
+    pub notify_sms: bool,
+    /// Row timestamps; default to None when a query doesn't select them
+    #[sqlx(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Set when the student is soft-deleted
+    #[sqlx(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
+/// Columns selected into `Booking`, for `SELECT {BOOKING_COLUMNS} FROM bookings`
+pub const BOOKING_COLUMNS: &str = "id, student_id, instructor_id, aircraft_id, aircraft_type, scheduled_date, \
+     departure_location, status, lesson_duration_minutes, weather_minimum_override, created_at, updated_at, deleted_at";
+
 /// Flight booking
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Booking {
     pub id: String,
     pub student_id: String,
+    /// Instructor teaching the lesson; absent for bookings made before
+    /// instructors were tracked
+    pub instructor_id: Option<String>,
+    /// Fleet aircraft; absent for bookings made with a free-text aircraft type
+    pub aircraft_id: Option<String>,
+    /// Model of the aircraft, e.g. "Cessna 172"
     pub aircraft_type: String,
     pub scheduled_date: DateTime<Utc>,
     /// Stored as JSON TEXT in SQLite
@@ -52,6 +95,54 @@ pub struct Booking {
     pub departure_location: Location,
     #[sqlx(try_from = "String")]
     pub status: BookingStatus,
+    /// Length of the lesson starting at `scheduled_date`
+    pub lesson_duration_minutes: i64,
+    /// Minimums for this flight in place of the training level's; stored as
+    /// JSON TEXT, NULL when the level's minimums apply
+    pub weather_minimum_override: Option<Json<WeatherMinimumOverride>>,
+    /// Row timestamps; default to None when a query doesn't select them
+    #[sqlx(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Set when the booking is soft-deleted along with its student
+    #[sqlx(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Time a booking occupies, as a half-open `[start, end)` interval
+pub fn booking_interval(booking: &Booking) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = booking.scheduled_date;
+    (start, start + Duration::minutes(booking.lesson_duration_minutes))
+}
+
+/// Whether two `[start, end)` intervals share any time; back-to-back lessons
+/// don't overlap
+pub fn intervals_overlap(a: (DateTime<Utc>, DateTime<Utc>), b: (DateTime<Utc>, DateTime<Utc>)) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// Flight instructor
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Instructor {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    /// Stored as a JSON array in SQLite, e.g. ["CFI", "CFII"]
+    #[sqlx(json)]
+    pub certifications: Vec<String>,
+}
+
+/// Aircraft in the school's fleet
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Aircraft {
+    pub id: String,
+    pub tail_number: String,
+    pub model: String,
+    /// Maximum demonstrated crosswind from the POH; caps the crosswind limit
+    /// of any student flying this aircraft
+    pub max_demonstrated_crosswind_kt: Option<f64>,
+    pub ifr_capable: bool,
 }
 
 /// Weather check record
@@ -66,6 +157,15 @@ pub struct WeatherCheck {
     pub reason: Option<String>,
 }
 
+/// `suggested_by` for events created by the weather scheduler
+pub const SUGGESTED_BY_SYSTEM: &str = "SYSTEM";
+
+/// `suggested_by` for events created through the API on a user's behalf
+pub const SUGGESTED_BY_USER: &str = "USER";
+
+/// `suggested_by` for events recording the reschedule options offered to a student
+pub const SUGGESTED_BY_AI: &str = "AI";
+
 /// Reschedule event tracking
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct RescheduleEvent {
@@ -76,6 +176,24 @@ pub struct RescheduleEvent {
     pub suggested_by: String,
     /// AI suggestions as JSON
     pub ai_suggestions: Option<String>,
+    /// Why the booking moved, e.g. the weather violations or "User requested reschedule"
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A status change that didn't move the flight, such as a weather hold being
+/// placed or released, or a student choosing to keep a held booking (from and
+/// to are then both `WeatherHold`); reschedules are `RescheduleEvent`s
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookingStatusEvent {
+    pub id: String,
+    pub booking_id: String,
+    pub from_status: BookingStatus,
+    pub to_status: BookingStatus,
+    /// `SUGGESTED_BY_SYSTEM` or `SUGGESTED_BY_USER`
+    pub changed_by: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Weather minimums for each training level
@@ -86,11 +204,213 @@ pub struct WeatherMinimum {
     pub training_level: TrainingLevel,
     pub min_visibility_sm: f64,
     pub max_wind_speed_kt: f64,
+    /// Maximum crosswind component; only checked when a runway heading is known
+    pub max_crosswind_kt: Option<f64>,
+    /// Gusts may exceed `max_wind_speed_kt` by up to this many knots
+    pub gust_margin_kt: f64,
+    /// Maximum density altitude; only checked when the field elevation is known
+    pub max_density_altitude_ft: Option<f64>,
     pub min_ceiling_ft: Option<f64>,
     #[sqlx(rename = "allow_imc")]
     pub allow_imc: bool,
     pub no_thunderstorms: bool,
     pub no_icing: bool,
+    /// Whether flights may be scheduled between sunset and sunrise
+    pub allow_night: bool,
+}
+
+impl WeatherMinimum {
+    /// These minimums with every field set in `overrides` replaced
+    pub fn with_override(&self, overrides: &WeatherMinimumOverride) -> WeatherMinimum {
+        WeatherMinimum {
+            min_visibility_sm: overrides.min_visibility_sm.unwrap_or(self.min_visibility_sm),
+            max_wind_speed_kt: overrides.max_wind_speed_kt.unwrap_or(self.max_wind_speed_kt),
+            max_crosswind_kt: overrides.max_crosswind_kt.or(self.max_crosswind_kt),
+            gust_margin_kt: overrides.gust_margin_kt.unwrap_or(self.gust_margin_kt),
+            max_density_altitude_ft: overrides.max_density_altitude_ft.or(self.max_density_altitude_ft),
+            min_ceiling_ft: overrides.min_ceiling_ft.or(self.min_ceiling_ft),
+            allow_imc: overrides.allow_imc.unwrap_or(self.allow_imc),
+            no_thunderstorms: overrides.no_thunderstorms.unwrap_or(self.no_thunderstorms),
+            no_icing: overrides.no_icing.unwrap_or(self.no_icing),
+            allow_night: overrides.allow_night.unwrap_or(self.allow_night),
+            ..self.clone()
+        }
+    }
+}
+
+/// Minimums an instructor sets for a single booking, e.g. tighter limits for
+/// a first solo; omitted fields keep the training level's value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherMinimumOverride {
+    pub min_visibility_sm: Option<f64>,
+    pub max_wind_speed_kt: Option<f64>,
+    pub max_crosswind_kt: Option<f64>,
+    pub gust_margin_kt: Option<f64>,
+    pub max_density_altitude_ft: Option<f64>,
+    pub min_ceiling_ft: Option<f64>,
+    pub allow_imc: Option<bool>,
+    pub no_thunderstorms: Option<bool>,
+    pub no_icing: Option<bool>,
+    pub allow_night: Option<bool>,
+}
+
+impl WeatherMinimumOverride {
+    /// Numeric limits must be non-negative, as they are for `WeatherMinimum`
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = [
+            ("min_visibility_sm", self.min_visibility_sm),
+            ("max_wind_speed_kt", self.max_wind_speed_kt),
+            ("max_crosswind_kt", self.max_crosswind_kt),
+            ("gust_margin_kt", self.gust_margin_kt),
+            ("max_density_altitude_ft", self.max_density_altitude_ft),
+            ("min_ceiling_ft", self.min_ceiling_ft),
+        ];
+        for (field, value) in limits {
+            if value.is_some_and(|v| v < 0.0 || !v.is_finite()) {
+                return Err(format!("{} must be a non-negative number", field));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Real-time notification pushed to WebSocket clients, tagged by `type`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Notification {
+    /// A booking was cancelled because the forecast is below the student's minimums
+    WeatherConflict {
+        booking_id: String,
+        student_id: String,
+        student_name: String,
+        message: String,
+        original_date: DateTime<Utc>,
+    },
+    /// A weather alert, either for a booking or a government-issued area alert
+    WeatherAlert {
+        id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        booking_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        student_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        student_name: Option<String>,
+        message: String,
+        severity: String,
+        location: String,
+        timestamp: DateTime<Utc>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        original_date: Option<DateTime<Utc>>,
+    },
+    BookingRescheduled {
+        booking_id: String,
+        student_id: String,
+        student_name: String,
+        old_date: DateTime<Utc>,
+        new_date: DateTime<Utc>,
+    },
+    BookingCancelled {
+        booking_id: String,
+        student_id: String,
+        original_date: DateTime<Utc>,
+    },
+    /// A booking was put on weather hold; it is cancelled if the forecast
+    /// hasn't improved shortly before the flight
+    WeatherHold {
+        booking_id: String,
+        student_id: String,
+        student_name: String,
+        message: String,
+        original_date: DateTime<Utc>,
+    },
+    /// The forecast improved and a held booking is scheduled again
+    WeatherHoldReleased {
+        booking_id: String,
+        student_id: String,
+        student_name: String,
+        original_date: DateTime<Utc>,
+    },
+    /// A weather alert was dismissed and should be removed from dashboards
+    AlertDismissed {
+        id: String,
+        dismissed_at: DateTime<Utc>,
+    },
+}
+
+impl RescheduleEvent {
+    /// Insert a reschedule event and return its id. Every writer goes through
+    /// here so scheduler and user events share one column set.
+    pub async fn record(
+        db: &SqlitePool,
+        booking_id: &str,
+        original_date: DateTime<Utc>,
+        new_date: DateTime<Utc>,
+        suggested_by: &str,
+        reason: Option<&str>,
+    ) -> sqlx::Result<String> {
+        Self::record_with_suggestions(db, booking_id, original_date, new_date, suggested_by, reason, None).await
+    }
+
+    /// `record`, also storing the reschedule options (as JSON) that were offered
+    pub async fn record_with_suggestions(
+        db: &SqlitePool,
+        booking_id: &str,
+        original_date: DateTime<Utc>,
+        new_date: DateTime<Utc>,
+        suggested_by: &str,
+        reason: Option<&str>,
+        ai_suggestions: Option<&str>,
+    ) -> sqlx::Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+
+        sqlx::query(
+            "INSERT INTO reschedule_events (id, booking_id, original_date, new_date, suggested_by, ai_suggestions, reason, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(booking_id)
+        .bind(original_date)
+        .bind(new_date)
+        .bind(suggested_by)
+        .bind(ai_suggestions)
+        .bind(reason)
+        .bind(Utc::now())
+        .execute(db)
+        .await?;
+
+        Ok(id)
+    }
+}
+
+impl BookingStatusEvent {
+    /// Insert a status change event and return its id
+    pub async fn record(
+        db: &SqlitePool,
+        booking_id: &str,
+        from_status: BookingStatus,
+        to_status: BookingStatus,
+        changed_by: &str,
+        reason: Option<&str>,
+    ) -> sqlx::Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+
+        sqlx::query(
+            "INSERT INTO booking_status_events (id, booking_id, from_status, to_status, changed_by, reason, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(booking_id)
+        .bind(from_status.as_str())
+        .bind(to_status.as_str())
+        .bind(changed_by)
+        .bind(reason)
+        .bind(Utc::now())
+        .execute(db)
+        .await?;
+
+        Ok(id)
+    }
 }
 
 impl TrainingLevel {
@@ -120,6 +440,7 @@ impl BookingStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
             BookingStatus::Scheduled => "SCHEDULED",
+            BookingStatus::WeatherHold => "WEATHER_HOLD",
             BookingStatus::Cancelled => "CANCELLED",
             BookingStatus::Rescheduled => "RESCHEDULED",
             BookingStatus::Completed => "COMPLETED",
@@ -133,6 +454,7 @@ impl TryFrom<String> for BookingStatus {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         match value.as_str() {
             "SCHEDULED" => Ok(BookingStatus::Scheduled),
+            "WEATHER_HOLD" => Ok(BookingStatus::WeatherHold),
             "CANCELLED" => Ok(BookingStatus::Cancelled),
             "RESCHEDULED" => Ok(BookingStatus::Rescheduled),
             "COMPLETED" => Ok(BookingStatus::Completed),
@@ -151,6 +473,7 @@ mod tests {
             lat: 33.8113,
             lon: -118.1515,
             name: "KTOA".to_string(),
+            elevation_ft: None,
         };
 
         let json = serde_json::to_string(&location).unwrap();
@@ -171,6 +494,163 @@ mod tests {
         assert_eq!(level, deserialized);
     }
 
+    #[test]
+    fn test_notification_wire_format() {
+        let date = DateTime::parse_from_rfc3339("2024-01-15T14:00:00Z").unwrap().with_timezone(&Utc);
+
+        let conflict = Notification::WeatherConflict {
+            booking_id: "b1".to_string(),
+            student_id: "s1".to_string(),
+            student_name: "John Doe".to_string(),
+            message: "Flight cancelled: High winds".to_string(),
+            original_date: date,
+        };
+        assert_eq!(
+            serde_json::to_value(&conflict).unwrap(),
+            serde_json::json!({
+                "type": "weather_conflict",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "student_name": "John Doe",
+                "message": "Flight cancelled: High winds",
+                "original_date": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let government_alert = Notification::WeatherAlert {
+            id: "a1".to_string(),
+            booking_id: None,
+            student_id: None,
+            student_name: None,
+            message: "Tornado Warning".to_string(),
+            severity: "severe".to_string(),
+            location: "(33.8113, -118.1515)".to_string(),
+            timestamp: date,
+            original_date: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&government_alert).unwrap(),
+            serde_json::json!({
+                "type": "weather_alert",
+                "id": "a1",
+                "message": "Tornado Warning",
+                "severity": "severe",
+                "location": "(33.8113, -118.1515)",
+                "timestamp": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let booking_alert = Notification::WeatherAlert {
+            id: "a2".to_string(),
+            booking_id: Some("b1".to_string()),
+            student_id: Some("s1".to_string()),
+            student_name: Some("John Doe".to_string()),
+            message: "Gusty winds".to_string(),
+            severity: "moderate".to_string(),
+            location: "(33.8113, -118.1515)".to_string(),
+            timestamp: date,
+            original_date: Some(date),
+        };
+        assert_eq!(
+            serde_json::to_value(&booking_alert).unwrap(),
+            serde_json::json!({
+                "type": "weather_alert",
+                "id": "a2",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "student_name": "John Doe",
+                "message": "Gusty winds",
+                "severity": "moderate",
+                "location": "(33.8113, -118.1515)",
+                "timestamp": "2024-01-15T14:00:00Z",
+                "original_date": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let rescheduled = Notification::BookingRescheduled {
+            booking_id: "b1".to_string(),
+            student_id: "s1".to_string(),
+            student_name: "John Doe".to_string(),
+            old_date: date,
+            new_date: date + chrono::Duration::days(1),
+        };
+        assert_eq!(
+            serde_json::to_value(&rescheduled).unwrap(),
+            serde_json::json!({
+                "type": "booking_rescheduled",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "student_name": "John Doe",
+                "old_date": "2024-01-15T14:00:00Z",
+                "new_date": "2024-01-16T14:00:00Z",
+            })
+        );
+
+        let cancelled = Notification::BookingCancelled {
+            booking_id: "b1".to_string(),
+            student_id: "s1".to_string(),
+            original_date: date,
+        };
+        assert_eq!(
+            serde_json::to_value(&cancelled).unwrap(),
+            serde_json::json!({
+                "type": "booking_cancelled",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "original_date": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let held = Notification::WeatherHold {
+            booking_id: "b1".to_string(),
+            student_id: "s1".to_string(),
+            student_name: "John Doe".to_string(),
+            message: "Flight on hold: Thunderstorms forecast".to_string(),
+            original_date: date,
+        };
+        assert_eq!(
+            serde_json::to_value(&held).unwrap(),
+            serde_json::json!({
+                "type": "weather_hold",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "student_name": "John Doe",
+                "message": "Flight on hold: Thunderstorms forecast",
+                "original_date": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let released = Notification::WeatherHoldReleased {
+            booking_id: "b1".to_string(),
+            student_id: "s1".to_string(),
+            student_name: "John Doe".to_string(),
+            original_date: date,
+        };
+        assert_eq!(
+            serde_json::to_value(&released).unwrap(),
+            serde_json::json!({
+                "type": "weather_hold_released",
+                "booking_id": "b1",
+                "student_id": "s1",
+                "student_name": "John Doe",
+                "original_date": "2024-01-15T14:00:00Z",
+            })
+        );
+
+        let dismissed = Notification::AlertDismissed {
+            id: "a1".to_string(),
+            dismissed_at: date,
+        };
+        assert_eq!(
+            serde_json::to_value(&dismissed).unwrap(),
+            serde_json::json!({
+                "type": "alert_dismissed",
+                "id": "a1",
+                "dismissed_at": "2024-01-15T14:00:00Z",
+            })
+        );
+    }
+
     #[test]
     fn test_booking_status_serialization() {
         let status = BookingStatus::Scheduled;
@@ -179,5 +659,56 @@ mod tests {
         let json = serde_json::to_string(&status).unwrap();
         let deserialized: BookingStatus = serde_json::from_str(&json).unwrap();
         assert_eq!(status, deserialized);
+
+        let hold = BookingStatus::WeatherHold;
+        assert_eq!(hold.as_str(), "WEATHER_HOLD");
+        assert_eq!(BookingStatus::try_from(hold.as_str().to_string()), Ok(hold));
+    }
+
+    fn booking_at(start: DateTime<Utc>, lesson_duration_minutes: i64) -> Booking {
+        Booking {
+            id: "b1".to_string(),
+            student_id: "student1".to_string(),
+            instructor_id: None,
+            aircraft_id: None,
+            aircraft_type: "Cessna 172".to_string(),
+            scheduled_date: start,
+            departure_location: Location {
+                lat: 33.8113,
+                lon: -118.1515,
+                name: "KTOA".to_string(),
+                elevation_ft: None,
+            },
+            status: BookingStatus::Scheduled,
+            lesson_duration_minutes,
+            weather_minimum_override: None,
+            created_at: None,
+            updated_at: None,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_adjacent_lessons_do_not_overlap() {
+        let start = Utc::now();
+        let first = booking_interval(&booking_at(start, 120));
+        let second = booking_interval(&booking_at(start + Duration::minutes(120), 60));
+
+        assert_eq!(first.1, second.0);
+        assert!(!intervals_overlap(first, second));
+        assert!(!intervals_overlap(second, first));
+    }
+
+    #[test]
+    fn test_intersecting_lessons_overlap() {
+        let start = Utc::now();
+        let long = booking_interval(&booking_at(start, 120));
+
+        // Starts inside the other lesson
+        assert!(intervals_overlap(long, booking_interval(&booking_at(start + Duration::minutes(90), 60))));
+        // Entirely contained in the other lesson
+        assert!(intervals_overlap(long, booking_interval(&booking_at(start + Duration::minutes(30), 30))));
+        // Ends inside the other lesson
+        assert!(intervals_overlap(long, booking_interval(&booking_at(start - Duration::minutes(30), 60))));
     }
 }
@@ -1,8 +1,25 @@
 use crate::ai::RescheduleOption;
-use crate::models::Booking;
+use crate::models::{Booking, BookingStatus};
+use crate::util::{check_status, is_retryable_send, retry_with_backoff_if, send_logged, HttpTimeouts, RetryPolicy, NOTIFICATION_TIMEOUTS};
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use serde::Serialize;
+use std::sync::Mutex;
 
+#[async_trait]
+pub trait EmailProvider: Send + Sync {
+    /// Email the student about a weather conflict; `cc` (e.g. the instructor
+    /// and a dispatch inbox) may be empty
+    async fn send_conflict_email(
+        &self,
+        to: &str,
+        cc: &[String],
+        booking: &Booking,
+        options: &[RescheduleOption],
+    ) -> Result<()>;
+}
+
+/// Resend email client
 pub struct EmailClient {
     client: reqwest::Client,
     api_key: String,
@@ -13,19 +30,29 @@ pub struct EmailClient {
 struct ResendEmailRequest {
     from: String,
     to: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<String>,
     subject: String,
     html: String,
+    /// Plain-text alternative for text-only clients
+    text: String,
 }
 
 impl EmailClient {
+    /// Timeouts come from `EMAIL_TIMEOUT_SECS` / `EMAIL_CONNECT_TIMEOUT_SECS`
     pub fn new(api_key: String, from_email: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: HttpTimeouts::from_env("EMAIL", NOTIFICATION_TIMEOUTS).build_client(),
             api_key,
             from_email,
         }
     }
 
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.client = timeouts.build_client();
+        self
+    }
+
     pub fn from_env() -> Result<Self> {
         let api_key = std::env::var("RESEND_API_KEY")
             .context("RESEND_API_KEY environment variable not set")?;
@@ -35,45 +62,19 @@ impl EmailClient {
         Ok(Self::new(api_key, from_email))
     }
 
-    pub async fn send_conflict_email(
-        &self,
-        to: &str,
-        booking: &Booking,
-        options: &[RescheduleOption],
-    ) -> Result<()> {
-        let html = self.build_email_html(booking, options);
-
-        let request = ResendEmailRequest {
+    fn build_request(&self, to: &str, cc: &[String], booking: &Booking, options: &[RescheduleOption]) -> ResendEmailRequest {
+        ResendEmailRequest {
             from: self.from_email.clone(),
             to: vec![to.to_string()],
-            subject: format!(
-                "Flight Lesson Cancelled Due to Weather - {}",
-                booking.scheduled_date.format("%Y-%m-%d %H:%M")
-            ),
-            html,
-        };
-
-        let response = self
-            .client
-            .post("https://api.resend.com/emails")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send email")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Resend API returned status {}: {}", status, body);
+            cc: cc.to_vec(),
+            subject: conflict_email_subject(booking),
+            html: self.build_email_html(booking, options),
+            text: self.build_email_text(booking, options),
         }
-
-        tracing::info!("Email sent to {} for booking {}", to, booking.id);
-        Ok(())
     }
 
     fn build_email_html(&self, booking: &Booking, options: &[RescheduleOption]) -> String {
+        let wording = ConflictWording::for_booking(booking);
         let options_html: String = options
             .iter()
             .map(|opt| {
@@ -117,12 +118,12 @@ impl EmailClient {
 <body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
     <div style="background: linear-gradient(135deg, #667eea 0%, #764ba2 100%); color: white; padding: 30px; border-radius: 8px; text-align: center;">
         <h1 style="margin: 0; font-size: 28px;">⛈️ Weather Alert</h1>
-        <p style="margin: 10px 0 0 0; font-size: 16px;">Your flight lesson has been cancelled</p>
+        <p style="margin: 10px 0 0 0; font-size: 16px;">{}</p>
     </div>
 
     <div style="background: #fff; padding: 24px; margin: 20px 0; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);">
-        <h2 style="color: #dc2626; margin-top: 0;">Flight Lesson Cancelled</h2>
-        <p>Unfortunately, your scheduled flight lesson has been cancelled due to unsafe weather conditions.</p>
+        <h2 style="color: #dc2626; margin-top: 0;">{}</h2>
+        <p>{}</p>
 
         <div style="background: #fef2f2; border-left: 4px solid #dc2626; padding: 16px; margin: 16px 0; border-radius: 4px;">
             <p style="margin: 0;"><strong>Original Booking:</strong></p>
@@ -157,11 +158,207 @@ impl EmailClient {
 </body>
 </html>
             "#,
+            wording.headline,
+            wording.heading,
+            wording.lead,
             booking.scheduled_date.format("%A, %B %d, %Y"),
             booking.scheduled_date.format("%I:%M %p UTC"),
             options_html
         )
     }
+
+    /// Plain-text version of the conflict email, with the same content as the HTML
+    fn build_email_text(&self, booking: &Booking, options: &[RescheduleOption]) -> String {
+        let wording = ConflictWording::for_booking(booking);
+        let options_text: String = options
+            .iter()
+            .enumerate()
+            .map(|(i, opt)| {
+                format!(
+                    "{}. {}\n   Reason: {}\n   Weather Score: {:.1}/10\n   Instructor: {}\n\n",
+                    i + 1,
+                    opt.date_time.format("%A, %B %d, %Y at %I:%M %p UTC"),
+                    opt.reason,
+                    opt.weather_score,
+                    if opt.instructor_available {
+                        "Available"
+                    } else {
+                        "Check availability"
+                    }
+                )
+            })
+            .collect();
+
+        format!(
+            "WEATHER ALERT: {}
+
+{}
+
+Original Booking: {} at {}
+
+SUGGESTED RESCHEDULE OPTIONS
+
+We've identified the following alternative times with better weather conditions:
+
+{}What's Next?
+Please log in to your dashboard to select one of these options or choose a different time that works for you:
+https://flightschedulepro.com/dashboard
+
+--
+Flight Schedule Pro - Weather-Aware Flight Training
+Questions? Contact us at support@flightschedulepro.com
+",
+            wording.headline,
+            wording.lead,
+            booking.scheduled_date.format("%A, %B %d, %Y"),
+            booking.scheduled_date.format("%I:%M %p UTC"),
+            options_text
+        )
+    }
+}
+
+#[async_trait]
+impl EmailProvider for EmailClient {
+    async fn send_conflict_email(
+        &self,
+        to: &str,
+        cc: &[String],
+        booking: &Booking,
+        options: &[RescheduleOption],
+    ) -> Result<()> {
+        let request = self.build_request(to, cc, booking, options);
+
+        retry_with_backoff_if(RetryPolicy::default(), is_retryable_send, || async {
+            let request = self
+                .client
+                .post("https://api.resend.com/emails")
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request);
+            let response = send_logged("Resend", request)
+                .await
+                .context("Failed to send email")?;
+
+            check_status("Resend", response).await
+        })
+        .await?;
+
+        if cc.is_empty() {
+            tracing::info!("Email sent to {} for booking {}", to, booking.id);
+        } else {
+            tracing::info!("Email sent to {} (cc {}) for booking {}", to, cc.join(", "), booking.id);
+        }
+        Ok(())
+    }
+}
+
+/// A sent email captured by `MockEmailProvider`
+#[derive(Debug, Clone)]
+pub struct SentEmail {
+    pub to: String,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub booking_id: String,
+}
+
+/// Email provider that records messages in memory instead of sending them
+pub struct MockEmailProvider {
+    sent: Mutex<Vec<SentEmail>>,
+}
+
+impl MockEmailProvider {
+    pub fn new() -> Self {
+        Self {
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Emails "sent" so far, oldest first
+    pub fn sent_emails(&self) -> Vec<SentEmail> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl Default for MockEmailProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EmailProvider for MockEmailProvider {
+    async fn send_conflict_email(
+        &self,
+        to: &str,
+        cc: &[String],
+        booking: &Booking,
+        _options: &[RescheduleOption],
+    ) -> Result<()> {
+        let subject = conflict_email_subject(booking);
+        tracing::info!("📧 [MOCK EMAIL] To: {}, Cc: {:?}, Subject: {}", to, cc, subject);
+
+        self.sent.lock().unwrap().push(SentEmail {
+            to: to.to_string(),
+            cc: cc.to_vec(),
+            subject,
+            booking_id: booking.id.clone(),
+        });
+        Ok(())
+    }
+}
+
+/// Create email provider based on environment variables
+///
+/// Returns the Resend EmailClient if RESEND_API_KEY is available,
+/// otherwise returns MockEmailProvider
+pub fn create_email_provider() -> Box<dyn EmailProvider> {
+    match EmailClient::from_env() {
+        Ok(client) => {
+            tracing::info!("Using Resend email provider");
+            Box::new(client)
+        }
+        Err(_) => {
+            tracing::info!("Resend credentials not found, using mock email provider");
+            Box::new(MockEmailProvider::new())
+        }
+    }
+}
+
+/// Conflict email copy; a booking on weather hold isn't cancelled yet, so it
+/// gets its own wording
+struct ConflictWording {
+    subject: &'static str,
+    headline: &'static str,
+    heading: &'static str,
+    lead: &'static str,
+}
+
+impl ConflictWording {
+    fn for_booking(booking: &Booking) -> Self {
+        match booking.status {
+            BookingStatus::WeatherHold => Self {
+                subject: "Flight Lesson On Weather Hold",
+                headline: "Your flight lesson is on weather hold",
+                heading: "Flight Lesson On Hold",
+                lead: "The forecast for your scheduled flight lesson is below your weather minimums. \
+                       If it improves the lesson goes ahead as planned; otherwise it will be cancelled shortly before the flight.",
+            },
+            _ => Self {
+                subject: "Flight Lesson Cancelled Due to Weather",
+                headline: "Your flight lesson has been cancelled",
+                heading: "Flight Lesson Cancelled",
+                lead: "Unfortunately, your scheduled flight lesson has been cancelled due to unsafe weather conditions.",
+            },
+        }
+    }
+}
+
+fn conflict_email_subject(booking: &Booking) -> String {
+    format!(
+        "{} - {}",
+        ConflictWording::for_booking(booking).subject,
+        booking.scheduled_date.format("%Y-%m-%d %H:%M")
+    )
 }
 
 #[cfg(test)]
@@ -170,22 +367,33 @@ mod tests {
     use crate::models::{BookingStatus, Location};
     use chrono::Utc;
 
-    #[test]
-    fn test_email_html_generation() {
-        let client = EmailClient::new("test_key".to_string(), "test@example.com".to_string());
-
-        let booking = Booking {
+    fn create_test_booking() -> Booking {
+        Booking {
             id: "test123".to_string(),
             student_id: "student1".to_string(),
+            instructor_id: None,
+            aircraft_id: None,
             aircraft_type: "Cessna 172".to_string(),
             scheduled_date: Utc::now(),
             departure_location: Location {
                 lat: 33.8113,
                 lon: -118.1515,
                 name: "KTOA".to_string(),
+                elevation_ft: None,
             },
             status: BookingStatus::Cancelled,
-        };
+            lesson_duration_minutes: 120,
+            weather_minimum_override: None,
+            created_at: None,
+            updated_at: None,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_email_html_generation() {
+        let client = EmailClient::new("test_key".to_string(), "test@example.com".to_string());
+        let booking = create_test_booking();
 
         let options = vec![
             RescheduleOption {
@@ -202,4 +410,78 @@ mod tests {
         assert!(html.contains("Clear skies"));
         assert!(html.contains("9.5/10"));
     }
+
+    #[test]
+    fn test_email_text_generation() {
+        let client = EmailClient::new("test_key".to_string(), "test@example.com".to_string());
+        let booking = create_test_booking();
+
+        let options = vec![
+            RescheduleOption {
+                date_time: Utc::now() + chrono::Duration::days(1),
+                reason: "Clear skies".to_string(),
+                weather_score: 9.5,
+                instructor_available: true,
+            },
+            RescheduleOption {
+                date_time: Utc::now() + chrono::Duration::days(2),
+                reason: "Light winds after the front passes".to_string(),
+                weather_score: 8.0,
+                instructor_available: false,
+            },
+        ];
+
+        let text = client.build_email_text(&booking, &options);
+
+        assert!(text.contains(&booking.scheduled_date.format("%A, %B %d, %Y").to_string()));
+        for option in &options {
+            assert!(text.contains(&option.reason), "missing option: {}", option.reason);
+        }
+        assert!(!text.contains('<'), "text body should not contain HTML");
+    }
+
+    #[test]
+    fn test_request_payload_includes_cc() {
+        let client = EmailClient::new("test_key".to_string(), "test@example.com".to_string());
+        let booking = create_test_booking();
+        let cc = vec!["cfi@example.com".to_string(), "dispatch@example.com".to_string()];
+
+        let payload = serde_json::to_value(client.build_request("student@example.com", &cc, &booking, &[])).unwrap();
+        assert_eq!(payload["to"], serde_json::json!(["student@example.com"]));
+        assert_eq!(payload["cc"], serde_json::json!(["cfi@example.com", "dispatch@example.com"]));
+
+        let payload = serde_json::to_value(client.build_request("student@example.com", &[], &booking, &[])).unwrap();
+        assert!(payload.get("cc").is_none());
+    }
+
+    #[test]
+    fn test_weather_hold_email_wording() {
+        let client = EmailClient::new("test_key".to_string(), "test@example.com".to_string());
+        let booking = Booking {
+            status: BookingStatus::WeatherHold,
+            ..create_test_booking()
+        };
+
+        assert!(conflict_email_subject(&booking).starts_with("Flight Lesson On Weather Hold"));
+        let text = client.build_email_text(&booking, &[]);
+        assert!(text.contains("on weather hold"));
+        assert!(!text.contains("has been cancelled"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_email_provider_records_message() {
+        let provider = MockEmailProvider::new();
+        let booking = create_test_booking();
+
+        provider
+            .send_conflict_email("student@example.com", &[], &booking, &[])
+            .await
+            .unwrap();
+
+        let sent = provider.sent_emails();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "student@example.com");
+        assert!(sent[0].subject.starts_with("Flight Lesson Cancelled Due to Weather"));
+        assert_eq!(sent[0].booking_id, booking.id);
+    }
 }
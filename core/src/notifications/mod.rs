@@ -1,5 +1,7 @@
 pub mod email;
 pub mod sms;
+pub mod webhook;
 
 pub use email::*;
 pub use sms::*;
+pub use webhook::*;
@@ -1,6 +1,11 @@
+use crate::util::{check_status, is_retryable_send, retry_with_backoff_if, send_logged, HttpTimeouts, RetryPolicy, NOTIFICATION_TIMEOUTS};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::Serialize;
+use std::sync::Mutex;
+
+/// Characters in a single GSM-7 SMS segment
+pub const SMS_SEGMENT_LEN: usize = 160;
 
 #[async_trait]
 pub trait SmsProvider: Send + Sync {
@@ -15,15 +20,21 @@ pub struct TwilioProvider {
 }
 
 impl TwilioProvider {
+    /// Timeouts come from `SMS_TIMEOUT_SECS` / `SMS_CONNECT_TIMEOUT_SECS`
     pub fn new(account_sid: String, auth_token: String, from_number: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: HttpTimeouts::from_env("SMS", NOTIFICATION_TIMEOUTS).build_client(),
             account_sid,
             auth_token,
             from_number,
         }
     }
 
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.client = timeouts.build_client();
+        self
+    }
+
     pub fn from_env() -> Result<Self> {
         let account_sid = std::env::var("TWILIO_ACCOUNT_SID")
             .context("TWILIO_ACCOUNT_SID environment variable not set")?;
@@ -38,7 +49,20 @@ impl TwilioProvider {
 
 #[async_trait]
 impl SmsProvider for TwilioProvider {
+    /// Long messages go out as several segments, sent in order
     async fn send_sms(&self, to: &str, message: &str) -> Result<()> {
+        let segments = split_sms(message, SMS_SEGMENT_LEN);
+        for segment in &segments {
+            self.send_segment(to, segment).await?;
+        }
+
+        tracing::info!("SMS sent to {} via Twilio ({} segment(s))", to, segments.len());
+        Ok(())
+    }
+}
+
+impl TwilioProvider {
+    async fn send_segment(&self, to: &str, message: &str) -> Result<()> {
         let url = format!(
             "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
             self.account_sid
@@ -60,31 +84,46 @@ impl SmsProvider for TwilioProvider {
             body: message.to_string(),
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .basic_auth(&self.account_sid, Some(&self.auth_token))
-            .form(&request)
-            .send()
-            .await
-            .context("Failed to send SMS via Twilio")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Twilio API returned status {}: {}", status, body);
-        }
+        retry_with_backoff_if(RetryPolicy::default(), is_retryable_send, || async {
+            let request = self
+                .client
+                .post(&url)
+                .basic_auth(&self.account_sid, Some(&self.auth_token))
+                .form(&request);
+            let response = send_logged("Twilio", request)
+                .await
+                .context("Failed to send SMS via Twilio")?;
+
+            check_status("Twilio", response).await
+        })
+        .await?;
 
-        tracing::info!("SMS sent to {} via Twilio", to);
         Ok(())
     }
 }
 
-pub struct MockSmsProvider;
+/// A text captured by `MockSmsProvider`
+#[derive(Debug, Clone)]
+pub struct SentSms {
+    pub to: String,
+    pub message: String,
+}
+
+/// SMS provider that records messages in memory instead of sending them
+pub struct MockSmsProvider {
+    sent: Mutex<Vec<SentSms>>,
+}
 
 impl MockSmsProvider {
     pub fn new() -> Self {
-        Self
+        Self {
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Messages "sent" so far, oldest first
+    pub fn sent_messages(&self) -> Vec<SentSms> {
+        self.sent.lock().unwrap().clone()
     }
 }
 
@@ -98,6 +137,10 @@ impl Default for MockSmsProvider {
 impl SmsProvider for MockSmsProvider {
     async fn send_sms(&self, to: &str, message: &str) -> Result<()> {
         tracing::info!("📱 [MOCK SMS] To: {}, Message: {}", to, message);
+        self.sent.lock().unwrap().push(SentSms {
+            to: to.to_string(),
+            message: message.to_string(),
+        });
         Ok(())
     }
 }
@@ -126,6 +169,76 @@ pub fn format_conflict_sms(student_name: &str, original_date: &str) -> String {
     )
 }
 
+pub fn format_weather_hold_sms(student_name: &str, original_date: &str) -> String {
+    format!(
+        "Hi {}, your flight lesson on {} is on weather hold and will be cancelled if the forecast doesn't improve. Check your email for reschedule options. - Flight Schedule Pro",
+        student_name, original_date
+    )
+}
+
+pub fn format_hold_released_sms(student_name: &str, original_date: &str) -> String {
+    format!(
+        "Hi {}, the weather has improved and your flight lesson on {} is back on. - Flight Schedule Pro",
+        student_name, original_date
+    )
+}
+
+/// Split `message` into segments of at most `max_len` characters, breaking on
+/// word boundaries. When more than one segment is needed, each ends with a
+/// " (1/3)" style counter that counts toward `max_len`.
+pub fn split_sms(message: &str, max_len: usize) -> Vec<String> {
+    if message.chars().count() <= max_len {
+        return vec![message.to_string()];
+    }
+
+    // The counter's width depends on the segment count, so retry with a
+    // wider counter until the count fits in it
+    let mut digits = 1;
+    loop {
+        let counter_len = " (/)".len() + 2 * digits;
+        let chunks = pack_words(message, max_len.saturating_sub(counter_len).max(1));
+        let total = chunks.len();
+
+        if total.to_string().len() <= digits {
+            return chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| format!("{} ({}/{})", chunk, i + 1, total))
+                .collect();
+        }
+        digits += 1;
+    }
+}
+
+/// Greedily pack words into chunks of at most `capacity` characters; a word
+/// longer than a whole chunk is broken mid-word
+fn pack_words(message: &str, capacity: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in message.split_whitespace() {
+        let chars: Vec<char> = word.chars().collect();
+        for piece in chars.chunks(capacity) {
+            if current_len > 0 && current_len + 1 + piece.len() > capacity {
+                chunks.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            if current_len > 0 {
+                current.push(' ');
+                current_len += 1;
+            }
+            current.extend(piece);
+            current_len += piece.len();
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +258,33 @@ mod tests {
         assert!(message.contains("cancelled"));
     }
 
+    #[test]
+    fn test_split_sms_short_message_is_one_segment() {
+        let message = format_conflict_sms("John Doe", "2024-01-15 14:00 UTC");
+        assert!(message.len() <= SMS_SEGMENT_LEN);
+
+        assert_eq!(split_sms(&message, SMS_SEGMENT_LEN), vec![message]);
+    }
+
+    #[test]
+    fn test_split_sms_long_message_segments_in_order() {
+        let segments = split_sms("one two three four five six seven eight nine ten", 20);
+        assert_eq!(
+            segments,
+            vec!["one two three (1/4)", "four five six (2/4)", "seven eight (3/4)", "nine ten (4/4)"]
+        );
+
+        let message = format_conflict_sms(
+            "Alexandra Montgomery-Worthington",
+            "Wednesday, September 27, 2024 at 02:00 PM UTC",
+        );
+        let segments = split_sms(&message, SMS_SEGMENT_LEN);
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().all(|s| s.chars().count() <= SMS_SEGMENT_LEN));
+        assert!(segments[0].starts_with("Hi Alexandra") && segments[0].ends_with("(1/2)"));
+        assert!(segments[1].ends_with("Flight Schedule Pro (2/2)"));
+    }
+
     #[test]
     fn test_create_sms_provider_without_credentials() {
         // This should return MockSmsProvider when no env vars are set
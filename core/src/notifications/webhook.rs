@@ -0,0 +1,155 @@
+use crate::models::Notification;
+use crate::util::{check_status, retry_with_backoff, send_logged, HttpTimeouts, RetryPolicy, NOTIFICATION_TIMEOUTS};
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying `sha256=<hex HMAC of the body>` when a secret is configured
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// POSTs notifications as JSON to a school's own endpoint (Slack/Teams
+/// relays, in-house systems)
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    secret: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl WebhookNotifier {
+    /// Timeouts come from `WEBHOOK_TIMEOUT_SECS` / `WEBHOOK_CONNECT_TIMEOUT_SECS`
+    pub fn new(url: String) -> Self {
+        Self {
+            client: HttpTimeouts::from_env("WEBHOOK", NOTIFICATION_TIMEOUTS).build_client(),
+            url,
+            secret: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sign every payload with HMAC-SHA256 using `secret`
+    pub fn with_secret(mut self, secret: String) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.client = timeouts.build_client();
+        self
+    }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// `WEBHOOK_URL` is required; `WEBHOOK_SECRET` enables signing
+    pub fn from_env() -> Result<Self> {
+        let url = std::env::var("WEBHOOK_URL").context("WEBHOOK_URL environment variable not set")?;
+        let notifier = Self::new(url);
+
+        Ok(match std::env::var("WEBHOOK_SECRET") {
+            Ok(secret) if !secret.is_empty() => notifier.with_secret(secret),
+            _ => notifier,
+        })
+    }
+
+    /// Deliver `notification`, retrying rate limits, server errors and
+    /// connection failures with backoff
+    pub async fn send(&self, notification: &Notification) -> Result<()> {
+        let body = serde_json::to_vec(notification).context("Failed to serialize webhook payload")?;
+        let signature = self.secret.as_deref().map(|secret| sign_payload(secret, &body));
+
+        retry_with_backoff(self.retry_policy, || async {
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+            }
+            let response = send_logged("Webhook", request)
+                .await
+                .context("Failed to send webhook")?;
+
+            check_status("Webhook", response).await
+        })
+        .await?;
+
+        tracing::info!("Webhook delivered to {}", self.url);
+        Ok(())
+    }
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` keyed with `secret`,
+/// for receivers to recompute over the raw request body
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Webhook notifier from the environment, or `None` when `WEBHOOK_URL` is unset
+pub fn create_webhook_notifier() -> Option<WebhookNotifier> {
+    match WebhookNotifier::from_env() {
+        Ok(notifier) => {
+            tracing::info!("Sending weather notifications to webhook {}", notifier.url);
+            Some(notifier)
+        }
+        Err(_) => {
+            tracing::info!("WEBHOOK_URL not set, webhook notifications disabled");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_webhook_posts_signed_notification() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/weather"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/weather"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let notification = Notification::WeatherConflict {
+            booking_id: "booking1".to_string(),
+            student_id: "student1".to_string(),
+            student_name: "John Doe".to_string(),
+            message: "Flight cancelled: Wind speed 25kt exceeds 15kt".to_string(),
+            original_date: Utc.with_ymd_and_hms(2024, 1, 15, 14, 0, 0).unwrap(),
+        };
+        let notifier = WebhookNotifier::new(format!("{}/hooks/weather", server.uri()))
+            .with_secret("shared_secret".to_string())
+            .with_retry_policy(RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1) });
+
+        notifier.send(&notification).await.unwrap();
+
+        // The 503 is retried and the second delivery succeeds
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let delivered = &requests[1];
+
+        let payload: Notification = serde_json::from_slice(&delivered.body).unwrap();
+        assert_eq!(payload, notification);
+        assert_eq!(
+            delivered.headers.get(WEBHOOK_SIGNATURE_HEADER).unwrap().to_str().unwrap(),
+            "sha256=3410f4b425a603b7292ed06588634afa5c143361042be17166a3a53c8422f99d"
+        );
+    }
+}
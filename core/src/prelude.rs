@@ -0,0 +1,11 @@
+//! The types most consumers need, for a single `use weather_core::prelude::*`
+
+pub use crate::ai::{AiRescheduleClient, RescheduleOption};
+pub use crate::models::{
+    Aircraft, Booking, BookingStatus, Instructor, Location, Notification, RescheduleEvent, Student, TrainingLevel,
+    WeatherCheck, WeatherMinimum, WeatherMinimumOverride,
+};
+pub use crate::weather::{
+    calculate_weather_score, default_weather_minimums, is_flight_safe, WeatherClient, WeatherData, WeatherError,
+    WeatherSource,
+};
@@ -0,0 +1,208 @@
+use reqwest::{RequestBuilder, Response, Url};
+use std::time::{Duration, Instant};
+
+/// Total and connect timeouts for an outbound HTTP client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpTimeouts {
+    /// Limit on the whole request, from connecting to reading the body
+    pub total: Duration,
+    pub connect: Duration,
+}
+
+/// AI completions can legitimately take a while to generate
+pub const AI_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+    total: Duration::from_secs(60),
+    connect: Duration::from_secs(10),
+};
+
+/// Email and SMS sends should answer quickly
+pub const NOTIFICATION_TIMEOUTS: HttpTimeouts = HttpTimeouts {
+    total: Duration::from_secs(15),
+    connect: Duration::from_secs(5),
+};
+
+impl HttpTimeouts {
+    /// Override the defaults from `{prefix}_TIMEOUT_SECS` and
+    /// `{prefix}_CONNECT_TIMEOUT_SECS`, ignoring unset or invalid values
+    pub fn from_env(prefix: &str, defaults: HttpTimeouts) -> Self {
+        let var = |name: String| std::env::var(name).ok();
+
+        Self::from_values(
+            var(format!("{}_TIMEOUT_SECS", prefix)).as_deref(),
+            var(format!("{}_CONNECT_TIMEOUT_SECS", prefix)).as_deref(),
+            defaults,
+        )
+    }
+
+    /// Timeouts from raw seconds values, falling back to `defaults` for any
+    /// that are missing, unparseable or zero
+    pub fn from_values(total: Option<&str>, connect: Option<&str>, defaults: HttpTimeouts) -> Self {
+        let secs = |value: Option<&str>| {
+            value
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs)
+        };
+
+        Self {
+            total: secs(total).unwrap_or(defaults.total),
+            connect: secs(connect).unwrap_or(defaults.connect),
+        }
+    }
+
+    pub fn build_client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(self.total)
+            .connect_timeout(self.connect)
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+}
+
+/// Send a request and log it as a structured event (target `external_api`)
+/// with the service, method, endpoint, status and latency.
+///
+/// The endpoint is host and path only, so API keys passed in the query
+/// string never reach the logs. Returned errors have their URL stripped for
+/// the same reason.
+pub async fn send_logged(service: &'static str, request: RequestBuilder) -> reqwest::Result<Response> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(|e| e.without_url())?;
+
+    let method = request.method().clone();
+    let endpoint = sanitize_endpoint(request.url());
+    let started = Instant::now();
+
+    let result = client.execute(request).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    match &result {
+        Ok(response) if response.status().is_success() => tracing::debug!(
+            target: "external_api",
+            service,
+            %method,
+            %endpoint,
+            status = response.status().as_u16(),
+            elapsed_ms,
+            "External API call"
+        ),
+        Ok(response) => tracing::warn!(
+            target: "external_api",
+            service,
+            %method,
+            %endpoint,
+            status = response.status().as_u16(),
+            elapsed_ms,
+            "External API call returned an error status"
+        ),
+        Err(e) => tracing::warn!(
+            target: "external_api",
+            service,
+            %method,
+            %endpoint,
+            error = error_kind(e),
+            elapsed_ms,
+            "External API call failed"
+        ),
+    }
+
+    result.map_err(|e| e.without_url())
+}
+
+/// Host, port and path of a URL, dropping the query string and any credentials
+pub fn sanitize_endpoint(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{}:{}{}", host, port, url.path()),
+        None => format!("{}{}", host, url.path()),
+    }
+}
+
+fn error_kind(error: &reqwest::Error) -> &'static str {
+    if error.is_timeout() {
+        "timeout"
+    } else if error.is_connect() {
+        "connect"
+    } else {
+        "request"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    type Fields = HashMap<String, String>;
+
+    /// Records the fields of every `external_api` event
+    #[derive(Clone, Default)]
+    struct CaptureLayer(Arc<Mutex<Vec<Fields>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            if event.metadata().target() == "external_api" {
+                let mut fields = FieldRecorder::default();
+                event.record(&mut fields);
+                self.0.lock().unwrap().push(fields.0);
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct FieldRecorder(Fields);
+
+    impl Visit for FieldRecorder {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_logged_records_sanitized_call() {
+        use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/data/2.5/weather"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let capture = CaptureLayer::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let url = format!("{}/data/2.5/weather?lat=1&lon=2&appid=secret-key", server.uri());
+        let response = send_logged("OpenWeatherMap", reqwest::Client::new().get(&url)).await.unwrap();
+        assert_eq!(response.status(), 503);
+
+        let events = capture.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let fields = &events[0];
+        let address = server.address();
+
+        assert_eq!(fields["service"], "OpenWeatherMap");
+        assert_eq!(fields["method"], "GET");
+        assert_eq!(fields["endpoint"], format!("{}:{}/data/2.5/weather", address.ip(), address.port()));
+        assert_eq!(fields["status"], "503");
+        assert!(fields.contains_key("elapsed_ms"));
+        assert!(fields.values().all(|value| !value.contains("secret-key")));
+    }
+
+    #[test]
+    fn test_from_values_overrides_and_ignores_invalid() {
+        let timeouts = HttpTimeouts::from_values(Some("7"), Some("not-a-number"), NOTIFICATION_TIMEOUTS);
+        assert_eq!(timeouts.total, Duration::from_secs(7));
+        assert_eq!(timeouts.connect, NOTIFICATION_TIMEOUTS.connect);
+
+        let timeouts = HttpTimeouts::from_values(Some("0"), None, AI_TIMEOUTS);
+        assert_eq!(timeouts, AI_TIMEOUTS);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod http;
+pub mod retry;
+
+pub use http::*;
+pub use retry::*;
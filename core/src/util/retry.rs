@@ -0,0 +1,233 @@
+use anyhow::Result;
+use reqwest::{Response, StatusCode};
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How many times to try a network call and how long to back off between tries
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each one after it
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `attempt` (0-based), with jitter so that
+    /// clients failing together don't all retry together. The result falls
+    /// between half and all of `base_delay * 2^attempt`.
+    fn delay(&self, attempt: u32) -> Duration {
+        let full = self.base_delay.saturating_mul(2_u32.saturating_pow(attempt));
+        let half = full / 2;
+        half + half.mul_f64(jitter_fraction())
+    }
+}
+
+/// A non-success HTTP response from an upstream API
+#[derive(Debug, thiserror::Error)]
+#[error("{service} API returned status {status}: {body}")]
+pub struct HttpStatusError {
+    pub service: &'static str,
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl HttpStatusError {
+    /// Rate limiting and server errors may clear up; other client errors
+    /// (bad credentials, bad requests) will fail the same way every time
+    pub fn is_retryable(&self) -> bool {
+        self.status == StatusCode::TOO_MANY_REQUESTS || self.status.is_server_error()
+    }
+}
+
+/// Pass a successful response through, or turn a failed one into an
+/// `HttpStatusError` carrying the response body
+pub async fn check_status(service: &'static str, response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(HttpStatusError { service, status, body }.into())
+}
+
+/// Whether a failed call is worth repeating: retryable HTTP statuses and
+/// connection or timeout errors. Anything else (4xx, parse errors) is final.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    if let Some(status_error) = error.downcast_ref::<HttpStatusError>() {
+        return status_error.is_retryable();
+    }
+    if let Some(request_error) = error.downcast_ref::<reqwest::Error>() {
+        return request_error.is_connect() || request_error.is_timeout();
+    }
+    false
+}
+
+/// Whether a failed send of a non-idempotent request (an email or SMS POST)
+/// is safe to repeat: only when the request can't have been acted on, i.e.
+/// the connection never opened or the provider rate-limited it. A 5xx or a
+/// timeout may come after the message went out, so retrying could send it twice.
+pub fn is_retryable_send(error: &anyhow::Error) -> bool {
+    if let Some(status_error) = error.downcast_ref::<HttpStatusError>() {
+        return status_error.status == StatusCode::TOO_MANY_REQUESTS;
+    }
+    if let Some(request_error) = error.downcast_ref::<reqwest::Error>() {
+        return request_error.is_connect() && !request_error.is_timeout();
+    }
+    false
+}
+
+/// An error that knows whether repeating the failed call could succeed
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for anyhow::Error {
+    fn is_retryable(&self) -> bool {
+        is_retryable(self)
+    }
+}
+
+/// Run `f` until it succeeds, fails with a non-retryable error, or runs out
+/// of attempts, backing off exponentially between tries
+pub async fn retry_with_backoff<F, Fut, T, E>(policy: RetryPolicy, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Retryable + std::fmt::Display,
+{
+    retry_with_backoff_if(policy, E::is_retryable, f).await
+}
+
+/// Like `retry_with_backoff`, but `should_retry` decides which errors are
+/// worth another attempt
+pub async fn retry_with_backoff_if<F, Fut, T, E, P>(policy: RetryPolicy, should_retry: P, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+    E: std::fmt::Display,
+{
+    let mut attempt = 0;
+
+    loop {
+        match f().await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt + 1 < policy.max_attempts && should_retry(&e) => {
+                let delay = policy.delay(attempt);
+                tracing::warn!(
+                    "Attempt {} of {} failed, retrying in {:?}: {:#}",
+                    attempt + 1,
+                    policy.max_attempts,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// A value in [0, 1) taken from the clock's sub-second nanos; plenty for
+/// spreading out retries without pulling in an RNG
+fn jitter_fraction() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    f64::from(nanos % 1_000_000) / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn status_error(status: StatusCode) -> anyhow::Error {
+        HttpStatusError { service: "Test", status, body: String::new() }.into()
+    }
+
+    #[test]
+    fn test_only_rate_limits_and_server_errors_are_retryable() {
+        assert!(is_retryable(&status_error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(is_retryable(&status_error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!is_retryable(&status_error(StatusCode::UNAUTHORIZED)));
+        assert!(!is_retryable(&status_error(StatusCode::BAD_REQUEST)));
+        assert!(!is_retryable(&anyhow::anyhow!("Failed to parse response")));
+    }
+
+    #[test]
+    fn test_sends_retry_only_on_rate_limits() {
+        assert!(is_retryable_send(&status_error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(!is_retryable_send(&status_error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!is_retryable_send(&status_error(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(!is_retryable_send(&status_error(StatusCode::BAD_REQUEST)));
+        assert!(!is_retryable_send(&anyhow::anyhow!("Failed to parse response")));
+    }
+
+    #[tokio::test]
+    async fn test_send_is_retried_when_connection_is_refused() {
+        // Bind then drop a listener so the port is known to refuse connections
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let error: anyhow::Error = reqwest::Client::new()
+            .post(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+
+        assert!(is_retryable_send(&error));
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_stops_on_rejected_error() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1) };
+
+        let result: Result<()> = retry_with_backoff_if(policy, is_retryable_send, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(status_error(StatusCode::BAD_GATEWAY))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_delay_doubles_within_jitter_bounds() {
+        let policy = RetryPolicy { max_attempts: 4, base_delay: Duration::from_millis(100) };
+
+        for attempt in 0..3 {
+            let full = Duration::from_millis(100 * 2_u64.pow(attempt));
+            let delay = policy.delay(attempt);
+            assert!(delay >= full / 2 && delay <= full, "attempt {}: {:?}", attempt, delay);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1) };
+
+        let result: Result<()> = retry_with_backoff(policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(status_error(StatusCode::BAD_GATEWAY))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -2,21 +2,143 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use anyhow::{Context, Result};
-
-const METERS_TO_MILES: f64 = 0.000621371;
-const MS_TO_KNOTS: f64 = 1.94384;
+use async_trait::async_trait;
+use futures::{future::join_all, stream::{self, StreamExt}};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use crate::models::Location;
+use crate::util::{retry_with_backoff, send_logged, RetryPolicy};
+use crate::weather::{
+    check_weather_status, derive_ceiling, hourly_taf_windows, CloudInfo, SqliteForecastCache, WeatherError,
+};
+
+pub(crate) const METERS_TO_MILES: f64 = 0.000621371;
+pub(crate) const MS_TO_KNOTS: f64 = 1.94384;
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// OpenWeatherMap condition codes that mean icing on their own: freezing
+/// rain (511) and sleet (611-613)
+const FREEZING_PRECIPITATION_CODES: [i64; 4] = [511, 611, 612, 613];
+
+/// Dew point spread (°F) at or below which the air is treated as holding
+/// visible moisture
+const VISIBLE_MOISTURE_SPREAD_F: f64 = 5.0;
+
+/// Concurrent requests in a batch fetch, overridable with WEATHER_BATCH_CONCURRENCY
+const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+const DEFAULT_BASE_URL: &str = "https://api.openweathermap.org/data/2.5";
+const DEFAULT_ONECALL_BASE_URL: &str = "https://api.openweathermap.org/data/3.0";
 
 /// Weather data normalized to aviation units
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Default` leaves every optional field `None`, so literals only need to
+/// name what a source actually reports and can end in `..Default::default()`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct WeatherData {
     pub visibility_miles: f64,
     pub wind_speed_knots: f64,
+    /// Direction the wind is blowing from, in degrees true
+    #[serde(default)]
+    pub wind_deg: Option<f64>,
+    #[serde(default)]
+    pub wind_gust_knots: Option<f64>,
     pub ceiling_ft: Option<f64>,
     pub temperature_f: f64,
     pub conditions: String,
     pub has_thunderstorms: bool,
     pub has_icing: bool,
     pub date_time: DateTime<Utc>,
+    /// Sunrise and sunset for the day of `date_time`, when the source reports them
+    #[serde(default)]
+    pub sunrise: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sunset: Option<DateTime<Utc>>,
+    /// Offset of local time at the location from UTC, when the source reports it
+    #[serde(default)]
+    pub utc_offset_seconds: Option<i32>,
+    /// Relative humidity, 0-100
+    #[serde(default)]
+    pub humidity_pct: Option<f64>,
+    /// A temperature close to the dew point means fog or low cloud is likely
+    #[serde(default)]
+    pub dew_point_f: Option<f64>,
+    /// Chance of precipitation, 0-1
+    #[serde(default)]
+    pub precip_probability: Option<f64>,
+}
+
+/// A provider of current and forecast weather for a location
+#[async_trait]
+pub trait WeatherSource: Send + Sync {
+    async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData>;
+    async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>>;
+
+    /// Forecast for a named location; sources that can use the station's
+    /// identifier override this, the rest forecast its coordinates
+    async fn fetch_location_forecast(&self, location: &Location) -> Result<Vec<WeatherData>> {
+        self.fetch_forecast(location.lat, location.lon).await
+    }
+
+    /// Day-by-day outlook starting today; sources without daily data return an error
+    async fn fetch_daily_forecast(&self, _lat: f64, _lon: f64) -> Result<Vec<DailyForecast>> {
+        anyhow::bail!("Daily forecast is not available from this weather source")
+    }
+
+    /// Current weather for each of `locations`, in the same order
+    async fn fetch_current_weather_batch(&self, locations: &[(f64, f64)]) -> Vec<Result<WeatherData>> {
+        join_all(locations.iter().map(|&(lat, lon)| self.fetch_current_weather(lat, lon))).await
+    }
+
+    /// Government weather alerts active at a location; sources without
+    /// alert data report none
+    async fn fetch_active_alerts(&self, _lat: f64, _lon: f64) -> Result<Vec<OneCallAlert>> {
+        Ok(Vec::new())
+    }
+
+    /// Drop cached responses that have outlived their TTL; sources that
+    /// don't cache have nothing to do
+    async fn clear_expired(&self) {}
+}
+
+/// One day of a multi-day outlook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyForecast {
+    /// Representative conditions: daytime temperature with the day's wind and
+    /// cloud cover, stamped around local midday
+    pub weather: WeatherData,
+    pub temp_high_f: f64,
+    pub temp_low_f: f64,
+    /// Probability of precipitation, 0.0 to 1.0
+    pub precipitation_probability: f64,
+}
+
+/// Cache key: coordinates rounded to 2 decimal places (~1km)
+type LocationKey = (i64, i64);
+
+/// Upstream APIs the client calls, each with its own request counter
+#[derive(Debug, Clone, Copy)]
+enum WeatherApi {
+    OpenWeatherMap,
+    AviationWeather,
+}
+
+impl WeatherApi {
+    fn service(self) -> &'static str {
+        match self {
+            Self::OpenWeatherMap => "OpenWeatherMap",
+            Self::AviationWeather => "AviationWeather",
+        }
+    }
+
+    fn requests_metric(self) -> &'static str {
+        match self {
+            Self::OpenWeatherMap => "openweathermap_requests_total",
+            Self::AviationWeather => "aviationweather_requests_total",
+        }
+    }
 }
 
 /// OpenWeatherMap API client
@@ -24,6 +146,17 @@ pub struct WeatherClient {
     client: reqwest::Client,
     api_key: String,
     base_url: String,
+    cache: RwLock<HashMap<LocationKey, (WeatherData, Instant)>>,
+    cache_ttl: Duration,
+    batch_concurrency: usize,
+    /// NOAA Aviation Weather Center API, used for TAFs
+    aviation_weather_url: String,
+    /// One Call 3.0 lives under a different path than the 2.5 endpoints
+    onecall_base_url: String,
+    /// Take current weather from One Call instead of the 2.5 `/weather` endpoint
+    use_onecall_current: bool,
+    /// Forecasts persisted across restarts; `fetch_forecast` reads it first
+    forecast_cache: Option<SqliteForecastCache>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,10 +167,14 @@ struct OpenWeatherMapResponse {
     wind: WindData,
     clouds: Option<CloudData>,
     dt: i64,
+    /// Only forecast entries carry a precipitation probability
+    pop: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
 struct WeatherCondition {
+    #[serde(default)]
+    id: i64,
     main: String,
     description: String,
 }
@@ -45,11 +182,14 @@ struct WeatherCondition {
 #[derive(Debug, Deserialize)]
 struct MainWeatherData {
     temp: f64,
+    humidity: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
 struct WindData {
     speed: f64,
+    deg: Option<f64>,
+    gust: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -60,6 +200,13 @@ struct CloudData {
 #[derive(Debug, Deserialize)]
 struct ForecastResponse {
     list: Vec<OpenWeatherMapResponse>,
+    city: Option<ForecastCity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastCity {
+    /// Shift in seconds from UTC
+    timezone: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -67,7 +214,7 @@ struct OneCallResponse {
     lat: f64,
     lon: f64,
     timezone: String,
-    timezone_offset: i64,
+    timezone_offset: i32,
     current: OneCallWeatherData,
     hourly: Vec<OneCallWeatherData>,
     daily: Vec<OneCallDailyData>,
@@ -133,14 +280,18 @@ struct OneCallWeatherCondition {
     icon: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct OneCallAlert {
-    sender_name: String,
-    event: String,
-    start: i64,
-    end: i64,
-    description: String,
-    tags: Vec<String>,
+/// Government-issued weather alert (e.g. an NWS tornado warning)
+#[derive(Debug, Clone, Deserialize)]
+pub struct OneCallAlert {
+    pub sender_name: String,
+    pub event: String,
+    /// Start of the alert, unix timestamp
+    pub start: i64,
+    /// End of the alert, unix timestamp
+    pub end: i64,
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl WeatherClient {
@@ -153,6 +304,11 @@ impl WeatherClient {
     }
 
     pub fn new(api_key: String, base_url: Option<String>) -> Self {
+        Self::with_cache_ttl(api_key, base_url, DEFAULT_CACHE_TTL)
+    }
+
+    /// Create a client whose current-weather responses are reused for `cache_ttl`
+    pub fn with_cache_ttl(api_key: String, base_url: Option<String>, cache_ttl: Duration) -> Self {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
@@ -161,120 +317,327 @@ impl WeatherClient {
         Self {
             client,
             api_key,
-            base_url: base_url.unwrap_or_else(|| "https://api.openweathermap.org/data/2.5".to_string()),
+            base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            cache: RwLock::new(HashMap::new()),
+            cache_ttl,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            aviation_weather_url: "https://aviationweather.gov/api/data".to_string(),
+            onecall_base_url: DEFAULT_ONECALL_BASE_URL.to_string(),
+            use_onecall_current: false,
+            forecast_cache: None,
         }
     }
 
+    /// Override the One Call 3.0 base URL used for forecasts, alerts and
+    /// (when enabled) current weather
+    pub fn with_onecall_base_url(mut self, url: String) -> Self {
+        self.onecall_base_url = url;
+        self
+    }
+
+    /// Fetch current weather from One Call 3.0, falling back to the 2.5 API
+    /// if it fails
+    pub fn with_onecall_current(mut self, enabled: bool) -> Self {
+        self.use_onecall_current = enabled;
+        self
+    }
+
+    /// Limit how many requests `fetch_current_weather_batch` runs at once
+    pub fn with_batch_concurrency(mut self, batch_concurrency: usize) -> Self {
+        self.batch_concurrency = batch_concurrency.max(1);
+        self
+    }
+
+    /// Keep forecasts in the database so restarts and repeated requests
+    /// don't fetch them again
+    pub fn with_forecast_cache(mut self, cache: SqliteForecastCache) -> Self {
+        self.forecast_cache = Some(cache);
+        self
+    }
+
+    /// Override the Aviation Weather Center base URL used by `fetch_taf`
+    pub fn with_aviation_weather_url(mut self, url: String) -> Self {
+        self.aviation_weather_url = url;
+        self
+    }
+
     pub fn from_env() -> Result<Self> {
         let api_key = std::env::var("WEATHER_API_KEY")
             .context("WEATHER_API_KEY environment variable not set")?;
         let base_url = std::env::var("WEATHER_API_BASE_URL").ok();
 
-        tracing::debug!("WeatherClient::from_env - api_key: {}, base_url: {:?}", api_key, base_url);
+        tracing::debug!("WeatherClient::from_env - base_url: {:?}", base_url);
 
-        Ok(Self::new(api_key, base_url))
+        let batch_concurrency = std::env::var("WEATHER_BATCH_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_BATCH_CONCURRENCY);
+
+        let use_onecall_current = std::env::var("WEATHER_USE_ONECALL_CURRENT")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+
+        let mut client = Self::new(api_key, base_url)
+            .with_batch_concurrency(batch_concurrency)
+            .with_onecall_current(use_onecall_current);
+        if let Ok(url) = std::env::var("METAR_API_BASE_URL") {
+            client = client.with_aviation_weather_url(url);
+        }
+        if let Ok(url) = std::env::var("WEATHER_ONECALL_BASE_URL") {
+            client = client.with_onecall_base_url(url);
+        }
+
+        Ok(client)
     }
 
-    pub async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData> {
-        tracing::debug!("WeatherClient base_url: {}", self.base_url);
+    /// Fetch current weather, reusing a cached response for nearby coordinates within the TTL
+    pub async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData, WeatherError> {
+        let key = location_key(lat, lon);
+
+        if let Some((weather, fetched_at)) = self.cache.read().await.get(&key) {
+            if fetched_at.elapsed() < self.cache_ttl {
+                tracing::debug!("Using cached weather for lat={}, lon={}", lat, lon);
+                return Ok(weather.clone());
+            }
+        }
+
+        let weather = self.fetch_current_weather_uncached(lat, lon).await?;
+        self.cache
+            .write()
+            .await
+            .insert(key, (weather.clone(), Instant::now()));
 
-        // For now, always use 2.5 API to avoid One Call issues
-        tracing::debug!("Using 2.5 API: {}", self.base_url);
-        self.retry_with_backoff(|| self.fetch_current_weather_inner(lat, lon), 3).await
+        Ok(weather)
     }
 
-    pub async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>> {
+    /// Evict current weather past the TTL and forecasts past the forecast
+    /// cache's max age. Lookups already ignore stale entries; this keeps the
+    /// in-memory map and the table from growing with every location seen.
+    pub async fn clear_expired(&self) {
+        self.cache
+            .write()
+            .await
+            .retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.cache_ttl);
+
+        if let Some(cache) = &self.forecast_cache {
+            cache.clear_expired().await;
+        }
+    }
+
+    /// Fetch current weather for many locations concurrently
+    ///
+    /// Coordinates that share a cache key are fetched once. Results are
+    /// returned in the same order as `locations`.
+    pub async fn fetch_current_weather_batch(&self, locations: &[(f64, f64)]) -> Vec<Result<WeatherData, WeatherError>> {
+        let mut unique: Vec<(LocationKey, f64, f64)> = Vec::new();
+        for &(lat, lon) in locations {
+            let key = location_key(lat, lon);
+            if !unique.iter().any(|(k, _, _)| *k == key) {
+                unique.push((key, lat, lon));
+            }
+        }
+
+        tracing::debug!(
+            "Batch fetching weather for {} locations ({} unique)",
+            locations.len(),
+            unique.len()
+        );
+
+        let fetched: HashMap<LocationKey, Result<WeatherData, WeatherError>> = stream::iter(unique)
+            .map(|(key, lat, lon)| async move { (key, self.fetch_current_weather(lat, lon).await) })
+            .buffer_unordered(self.batch_concurrency)
+            .collect()
+            .await;
+
+        locations
+            .iter()
+            .map(|&(lat, lon)| fetched[&location_key(lat, lon)].clone())
+            .collect()
+    }
+
+    /// Fetch current weather directly from the API, bypassing the cache
+    pub async fn fetch_current_weather_uncached(&self, lat: f64, lon: f64) -> Result<WeatherData, WeatherError> {
+        if self.use_onecall_current {
+            match self.fetch_onecall_data(lat, lon).await {
+                Ok(data) => return Ok(Self::onecall_weather(&data, &data.current)),
+                Err(e) => tracing::debug!("One Call current weather failed, falling back to 2.5 API: {}", e),
+            }
+        }
+
+        retry_with_backoff(RetryPolicy::default(), || self.fetch_current_weather_inner(lat, lon)).await
+    }
+
+    /// Fetch the forecast, reusing one from the forecast cache while it is fresh
+    pub async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>, WeatherError> {
+        let Some(cache) = &self.forecast_cache else {
+            return self.fetch_forecast_uncached(lat, lon).await;
+        };
+
+        if let Some(forecast) = cache.get(lat, lon).await {
+            tracing::debug!("Using cached forecast for lat={}, lon={}", lat, lon);
+            return Ok(forecast);
+        }
+
+        let forecast = self.fetch_forecast_uncached(lat, lon).await?;
+        cache.set(lat, lon, &forecast).await;
+        Ok(forecast)
+    }
+
+    /// Fetch the forecast for a location, preferring its station's TAF
+    /// windows over the OpenWeatherMap forecast where the TAF covers them.
+    /// Locations without an ICAO identifier, or whose TAF can't be fetched,
+    /// get the OpenWeatherMap forecast alone.
+    pub async fn fetch_location_forecast(&self, location: &Location) -> Result<Vec<WeatherData>, WeatherError> {
+        let Some(icao) = location.icao_id() else {
+            return self.fetch_forecast(location.lat, location.lon).await;
+        };
+
+        let (taf, forecast) =
+            futures::join!(self.fetch_taf(icao), self.fetch_forecast(location.lat, location.lon));
+        match (taf, forecast) {
+            (Ok(taf), Ok(forecast)) => Ok(prefer_taf(taf, forecast)),
+            (Ok(taf), Err(e)) => {
+                tracing::debug!("Forecast failed for {}, using its TAF alone: {}", icao, e);
+                Ok(taf)
+            }
+            (Err(e), forecast) => {
+                tracing::debug!("TAF unavailable for {}, using the forecast: {}", icao, e);
+                forecast
+            }
+        }
+    }
+
+    /// Fetch the forecast directly from the API, bypassing the forecast cache
+    pub async fn fetch_forecast_uncached(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>, WeatherError> {
         // Try One Call API 3.0 first, fallback to 2.5 API
         match self.fetch_onecall_data(lat, lon).await {
-            Ok(data) => Ok(data.hourly.into_iter().map(|h| Self::convert_to_weather_data_from_onecall(&h)).collect()),
-            Err(_) => {
-                tracing::debug!("One Call API failed, falling back to 2.5 API");
-                self.retry_with_backoff(|| self.fetch_forecast_inner(lat, lon), 3).await
+            Ok(data) => Ok(data
+                .hourly
+                .iter()
+                .map(|h| Self::onecall_weather(&data, h))
+                .collect()),
+            Err(e) => {
+                tracing::debug!("One Call API failed, falling back to 2.5 API: {}", e);
+                retry_with_backoff(RetryPolicy::default(), || self.fetch_forecast_inner(lat, lon)).await
             }
         }
     }
 
-    async fn fetch_current_weather_inner(&self, lat: f64, lon: f64) -> Result<WeatherData> {
-        let url = format!(
-            "{}/weather?lat={}&lon={}&appid={}",
-            self.base_url, lat, lon, self.api_key
-        );
+    /// Send a GET to a weather API, counting it in that API's request
+    /// counter by response status
+    async fn send(&self, api: WeatherApi, url: &str) -> reqwest::Result<reqwest::Response> {
+        let result = send_logged(api.service(), self.client.get(url)).await;
+
+        let status = match &result {
+            Ok(response) => response.status().as_u16().to_string(),
+            Err(_) => "error".to_string(),
+        };
+        metrics::counter!(api.requests_metric(), "status" => status).increment(1);
+
+        result
+    }
+
+    /// 2.5 current weather endpoint
+    fn current_weather_url(&self, lat: f64, lon: f64) -> String {
+        // NOTE: OpenWeatherMap API requires API key in query parameter
+        format!("{}/weather?lat={}&lon={}&appid={}", self.base_url, lat, lon, self.api_key)
+    }
+
+    /// 2.5 five-day / three-hour forecast endpoint
+    fn forecast_url(&self, lat: f64, lon: f64) -> String {
+        format!("{}/forecast?lat={}&lon={}&appid={}&cnt=56", self.base_url, lat, lon, self.api_key)
+    }
+
+    /// One Call 3.0 endpoint: current, hourly and daily weather plus alerts
+    fn onecall_url(&self, lat: f64, lon: f64) -> String {
+        format!("{}/onecall?lat={}&lon={}&appid={}", self.onecall_base_url, lat, lon, self.api_key)
+    }
+
+    async fn fetch_current_weather_inner(&self, lat: f64, lon: f64) -> Result<WeatherData, WeatherError> {
+        let url = self.current_weather_url(lat, lon);
 
         // Log without exposing API key
         tracing::debug!("Fetching current weather for lat={}, lon={}", lat, lon);
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to fetch current weather")?;
+        let response = self.send(WeatherApi::OpenWeatherMap, &url).await?;
+        let response = check_weather_status("Weather", response).await?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Weather API returned status: {}", response.status());
-        }
-
-        let data: OpenWeatherMapResponse = response
-            .json()
-            .await
-            .context("Failed to parse weather response")?;
+        let data: OpenWeatherMapResponse = response.json().await?;
 
         Ok(Self::convert_to_weather_data(data))
     }
 
-    async fn fetch_forecast_inner(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>> {
-        // NOTE: OpenWeatherMap API requires API key in query parameter
-        let url = format!(
-            "{}/forecast?lat={}&lon={}&appid={}&cnt=56",
-            self.base_url, lat, lon, self.api_key
-        );
+    async fn fetch_forecast_inner(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>, WeatherError> {
+        let url = self.forecast_url(lat, lon);
 
         // Log without exposing API key - only log coordinates, not the URL
         tracing::debug!("Fetching weather forecast for lat={}, lon={}", lat, lon);
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to fetch forecast")?;
+        let response = self.send(WeatherApi::OpenWeatherMap, &url).await?;
+        let response = check_weather_status("Weather", response).await?;
+
+        let data: ForecastResponse = response.json().await?;
+
+        let utc_offset_seconds = data.city.and_then(|city| city.timezone);
+        Ok(data
+            .list
+            .into_iter()
+            .map(|entry| WeatherData {
+                utc_offset_seconds,
+                ..Self::convert_to_weather_data(entry)
+            })
+            .collect())
+    }
+
+    /// Fetch the TAF for an ICAO station (e.g. "KTOA") and expand it into
+    /// hourly forecast windows across its validity period
+    pub async fn fetch_taf(&self, icao: &str) -> Result<Vec<WeatherData>, WeatherError> {
+        let url = format!("{}/taf?ids={}&format=raw", self.aviation_weather_url, icao);
 
-        if !response.status().is_success() {
-            anyhow::bail!("Weather API returned status: {}", response.status());
+        tracing::debug!("Fetching TAF for station {}", icao);
+
+        let response = self.send(WeatherApi::AviationWeather, &url).await?;
+        let response = check_weather_status("AviationWeather", response).await?;
+
+        let raw = response.text().await?;
+        match hourly_taf_windows(&raw, Utc::now()) {
+            Ok(Some(hours)) => Ok(hours),
+            Ok(None) => {
+                tracing::debug!("No TAF available for station {}", icao);
+                Err(WeatherError::NotFound)
+            }
+            Err(e) => Err(WeatherError::Parse(format!("TAF for station {}: {:#}", icao, e))),
         }
+    }
 
-        let data: ForecastResponse = response
-            .json()
-            .await
-            .context("Failed to parse forecast response")?;
+    /// Fetch government weather alerts currently active for a location
+    pub async fn fetch_active_alerts(&self, lat: f64, lon: f64) -> Result<Vec<OneCallAlert>, WeatherError> {
+        let data = self.fetch_onecall_data(lat, lon).await?;
+        Ok(data.alerts.unwrap_or_default())
+    }
+
+    /// Fetch the One Call daily outlook for a location
+    pub async fn fetch_daily_forecast(&self, lat: f64, lon: f64) -> Result<Vec<DailyForecast>, WeatherError> {
+        let data = self.fetch_onecall_data(lat, lon).await?;
 
-        Ok(data.list.into_iter().map(Self::convert_to_weather_data).collect())
+        if data.daily.is_empty() {
+            tracing::debug!("One Call response has no daily forecast for lat={}, lon={}", lat, lon);
+            return Err(WeatherError::NotFound);
+        }
+
+        Ok(data.daily.iter().map(|day| Self::convert_daily(&data, day)).collect())
     }
 
-    async fn fetch_onecall_data(&self, lat: f64, lon: f64) -> Result<OneCallResponse> {
-        // NOTE: OpenWeatherMap API requires API key in query parameter
-        let url = format!(
-            "{}/onecall?lat={}&lon={}&appid={}",
-            self.base_url, lat, lon, self.api_key
-        );
+    async fn fetch_onecall_data(&self, lat: f64, lon: f64) -> Result<OneCallResponse, WeatherError> {
+        let url = self.onecall_url(lat, lon);
 
         // Log without exposing API key
         tracing::debug!("Fetching One Call weather data for lat={}, lon={}", lat, lon);
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to fetch One Call data")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("One Call API returned status: {}", response.status());
-        }
+        let response = self.send(WeatherApi::OpenWeatherMap, &url).await?;
+        let response = check_weather_status("One Call", response).await?;
 
-        let data: OneCallResponse = response
-            .json()
-            .await
-            .context("Failed to parse One Call response")?;
-
-        Ok(data)
+        Ok(response.json().await?)
     }
 
     fn convert_to_weather_data(data: OpenWeatherMapResponse) -> WeatherData {
@@ -289,30 +652,85 @@ impl WeatherClient {
         let has_thunderstorms = data.weather.iter()
             .any(|w| w.main.to_lowercase().contains("thunderstorm"));
 
-        // Icing risk: temperature below freezing and cloudy conditions
-        let has_icing = temperature_f < 32.0 &&
-            data.clouds.as_ref().map(|c| c.all > 50.0).unwrap_or(false);
-
-        // Estimate ceiling from cloud data (simplified)
-        let ceiling_ft = data.clouds.as_ref().and_then(|c| {
-            if c.all > 80.0 {
-                Some(2000.0) // Low clouds
-            } else if c.all > 50.0 {
-                Some(5000.0) // Mid clouds
-            } else {
-                None // Clear or scattered
-            }
-        });
+        let condition_ids: Vec<i64> = data.weather.iter().map(|w| w.id).collect();
+        // The 2.5 API has no dew point, so cloud cover stands in for moisture
+        let has_icing = detect_icing(&condition_ids, temperature_f, None, data.clouds.as_ref().map(|c| c.all));
+
+        let ceiling_ft = data.clouds.as_ref().and_then(|c| derive_ceiling(&CloudInfo::from_coverage(c.all)));
 
         WeatherData {
             visibility_miles,
             wind_speed_knots,
+            wind_deg: data.wind.deg,
+            wind_gust_knots: data.wind.gust.map(|g| g * MS_TO_KNOTS),
             ceiling_ft,
             temperature_f,
             conditions,
             has_thunderstorms,
             has_icing,
             date_time: DateTime::from_timestamp(data.dt, 0).unwrap_or_else(Utc::now),
+            humidity_pct: data.main.humidity,
+            precip_probability: data.pop,
+            ..Default::default()
+        }
+    }
+
+    /// Convert a One Call current or hourly entry, adding the sun times for
+    /// its day and the location's UTC offset
+    fn onecall_weather(response: &OneCallResponse, entry: &OneCallWeatherData) -> WeatherData {
+        let mut weather = Self::convert_to_weather_data_from_onecall(entry);
+        if let Some(day) = nearest_daily(&response.daily, entry.dt) {
+            weather.sunrise = DateTime::from_timestamp(day.sunrise, 0);
+            weather.sunset = DateTime::from_timestamp(day.sunset, 0);
+        }
+        weather.utc_offset_seconds = Some(response.timezone_offset);
+        weather
+    }
+
+    /// Daily entries carry no visibility, so it is assumed unrestricted unless
+    /// the conditions say otherwise
+    fn convert_daily(response: &OneCallResponse, day: &OneCallDailyData) -> DailyForecast {
+        let temperature_f = kelvin_to_fahrenheit(day.temp.day);
+
+        let conditions = day.weather.first()
+            .map(|w| w.description.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let has_thunderstorms = day.weather.iter()
+            .any(|w| w.main.to_lowercase().contains("thunderstorm"));
+
+        let condition_ids: Vec<i64> = day.weather.iter().map(|w| w.id).collect();
+        let has_icing = detect_icing(
+            &condition_ids,
+            temperature_f,
+            Some(kelvin_to_fahrenheit(day.dew_point)),
+            Some(day.clouds),
+        );
+
+        let weather = WeatherData {
+            visibility_miles: 10000.0 * METERS_TO_MILES,
+            wind_speed_knots: day.wind_speed * MS_TO_KNOTS,
+            wind_deg: Some(day.wind_deg),
+            wind_gust_knots: day.wind_gust.map(|g| g * MS_TO_KNOTS),
+            ceiling_ft: derive_ceiling(&CloudInfo::from_coverage(day.clouds)),
+            temperature_f,
+            conditions,
+            has_thunderstorms,
+            has_icing,
+            date_time: DateTime::from_timestamp(day.dt, 0).unwrap_or_else(Utc::now),
+            sunrise: DateTime::from_timestamp(day.sunrise, 0),
+            sunset: DateTime::from_timestamp(day.sunset, 0),
+            utc_offset_seconds: Some(response.timezone_offset),
+            humidity_pct: Some(day.humidity),
+            dew_point_f: Some(kelvin_to_fahrenheit(day.dew_point)),
+            precip_probability: Some(day.pop),
+        };
+
+        DailyForecast {
+            weather,
+            temp_high_f: kelvin_to_fahrenheit(day.temp.max),
+            temp_low_f: kelvin_to_fahrenheit(day.temp.min),
+            precipitation_probability: day.pop,
         }
     }
 
@@ -320,6 +738,7 @@ impl WeatherClient {
         let visibility_miles = data.visibility.unwrap_or(10000.0) * METERS_TO_MILES;
         let wind_speed_knots = data.wind_speed * MS_TO_KNOTS;
         let temperature_f = kelvin_to_fahrenheit(data.temp);
+        let dew_point_f = kelvin_to_fahrenheit(data.dew_point);
 
         let conditions = data.weather.first()
             .map(|w| w.description.clone())
@@ -328,58 +747,123 @@ impl WeatherClient {
         let has_thunderstorms = data.weather.iter()
             .any(|w| w.main.to_lowercase().contains("thunderstorm"));
 
-        // Icing risk: temperature below freezing and cloudy conditions
-        let has_icing = temperature_f < 32.0 && data.clouds > 50.0;
+        let condition_ids: Vec<i64> = data.weather.iter().map(|w| w.id).collect();
+        let has_icing = detect_icing(&condition_ids, temperature_f, Some(dew_point_f), Some(data.clouds));
 
-        // Estimate ceiling from cloud data (simplified)
-        let ceiling_ft = if data.clouds > 80.0 {
-            Some(2000.0) // Low clouds
-        } else if data.clouds > 50.0 {
-            Some(5000.0) // Mid clouds
-        } else {
-            None // Clear or scattered
-        };
+        let ceiling_ft = derive_ceiling(&CloudInfo::from_coverage(data.clouds));
 
         WeatherData {
             visibility_miles,
             wind_speed_knots,
+            wind_deg: Some(data.wind_deg),
+            wind_gust_knots: data.wind_gust.map(|g| g * MS_TO_KNOTS),
             ceiling_ft,
             temperature_f,
             conditions,
             has_thunderstorms,
             has_icing,
             date_time: DateTime::from_timestamp(data.dt, 0).unwrap_or_else(Utc::now),
+            humidity_pct: Some(data.humidity),
+            dew_point_f: Some(dew_point_f),
+            precip_probability: data.pop,
+            ..Default::default()
         }
     }
+}
 
-    async fn retry_with_backoff<F, Fut, T>(&self, mut f: F, max_attempts: u32) -> Result<T>
-    where
-        F: FnMut() -> Fut,
-        Fut: std::future::Future<Output = Result<T>>,
-    {
-        let mut last_error = None;
-
-        for attempt in 0..max_attempts {
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    last_error = Some(e);
-                    if attempt < max_attempts - 1 {
-                        let delay = Duration::from_millis(100 * 2_u64.pow(attempt));
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
+#[async_trait]
+impl WeatherSource for WeatherClient {
+    async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData> {
+        Ok(WeatherClient::fetch_current_weather(self, lat, lon).await?)
+    }
+
+    async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>> {
+        Ok(WeatherClient::fetch_forecast(self, lat, lon).await?)
+    }
 
-        Err(last_error.unwrap())
+    async fn fetch_location_forecast(&self, location: &Location) -> Result<Vec<WeatherData>> {
+        Ok(WeatherClient::fetch_location_forecast(self, location).await?)
     }
+
+    async fn fetch_daily_forecast(&self, lat: f64, lon: f64) -> Result<Vec<DailyForecast>> {
+        Ok(WeatherClient::fetch_daily_forecast(self, lat, lon).await?)
+    }
+
+    async fn fetch_current_weather_batch(&self, locations: &[(f64, f64)]) -> Vec<Result<WeatherData>> {
+        WeatherClient::fetch_current_weather_batch(self, locations)
+            .await
+            .into_iter()
+            .map(|result| result.map_err(Into::into))
+            .collect()
+    }
+
+    async fn fetch_active_alerts(&self, lat: f64, lon: f64) -> Result<Vec<OneCallAlert>> {
+        Ok(WeatherClient::fetch_active_alerts(self, lat, lon).await?)
+    }
+
+    async fn clear_expired(&self) {
+        WeatherClient::clear_expired(self).await
+    }
+}
+
+/// Hourly TAF windows with the forecast entries that fall outside the
+/// TAF's validity, in time order
+fn prefer_taf(taf: Vec<WeatherData>, forecast: Vec<WeatherData>) -> Vec<WeatherData> {
+    let (Some(first), Some(last)) = (taf.first(), taf.last()) else {
+        return forecast;
+    };
+    let (start, end) = (first.date_time, last.date_time + chrono::Duration::hours(1));
+
+    let mut merged: Vec<WeatherData> = forecast
+        .into_iter()
+        .filter(|entry| entry.date_time < start || entry.date_time >= end)
+        .chain(taf)
+        .collect();
+    merged.sort_by_key(|entry| entry.date_time);
+    merged
+}
+
+/// Daily entries are stamped around local midday, so the closest one to an
+/// hourly timestamp is the same day
+fn nearest_daily(daily: &[OneCallDailyData], dt: i64) -> Option<&OneCallDailyData> {
+    daily.iter().min_by_key(|day| (day.dt - dt).abs())
+}
+
+/// Coordinates rounded to 0.01° (about 1km), so nearby requests share cache entries
+pub(crate) fn location_key(lat: f64, lon: f64) -> LocationKey {
+    ((lat * 100.0).round() as i64, (lon * 100.0).round() as i64)
 }
 
 fn kelvin_to_fahrenheit(kelvin: f64) -> f64 {
     (kelvin - 273.15) * 9.0 / 5.0 + 32.0
 }
 
+/// Icing risk from OpenWeatherMap condition codes and moisture
+///
+/// Freezing rain and sleet mean icing regardless of surface temperature or
+/// cloud cover. Otherwise icing needs sub-freezing air with visible moisture:
+/// precipitation, fog or mist, a dew point within `VISIBLE_MOISTURE_SPREAD_F`
+/// of the temperature, or, when the dew point is unknown, over half cloud cover.
+fn detect_icing(condition_ids: &[i64], temperature_f: f64, dew_point_f: Option<f64>, cloud_cover: Option<f64>) -> bool {
+    if condition_ids.iter().any(|id| FREEZING_PRECIPITATION_CODES.contains(id)) {
+        return true;
+    }
+    if temperature_f >= 32.0 {
+        return false;
+    }
+
+    // 2xx-6xx are thunderstorms, drizzle, rain and snow; 701 mist, 741 fog
+    let moisture_reported = condition_ids
+        .iter()
+        .any(|&id| (200..700).contains(&id) || id == 701 || id == 741);
+    let saturated = match dew_point_f {
+        Some(dew_point_f) => temperature_f - dew_point_f <= VISIBLE_MOISTURE_SPREAD_F,
+        None => cloud_cover.is_some_and(|c| c > 50.0),
+    };
+
+    moisture_reported || saturated
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -402,6 +886,382 @@ mod tests {
         assert!((absolute_zero - (-459.67)).abs() < 0.1);
     }
 
+    fn sample_current_weather() -> serde_json::Value {
+        serde_json::json!({
+            "weather": [{ "main": "Clear", "description": "clear sky" }],
+            "main": { "temp": 293.15 },
+            "visibility": 10000,
+            "wind": { "speed": 3.0, "deg": 270 },
+            "clouds": { "all": 10 },
+            "dt": 1700000000
+        })
+    }
+
+    #[tokio::test]
+    async fn test_current_weather_cached_within_ttl() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/weather"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(sample_current_weather()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::with_cache_ttl(
+            "test_key".to_string(),
+            Some(server.uri()),
+            Duration::from_secs(600),
+        );
+
+        let first = client.fetch_current_weather(33.8113, -118.1515).await.unwrap();
+        let second = client.fetch_current_weather(33.8114, -118.1516).await.unwrap();
+
+        assert_eq!(first.conditions, "clear sky");
+        assert_eq!(second.date_time, first.date_time);
+    }
+
+    #[tokio::test]
+    async fn test_clear_expired_evicts_stale_current_weather() {
+        let client = WeatherClient::with_cache_ttl("test_key".to_string(), None, Duration::from_millis(50));
+        let stale_key = location_key(40.71, -74.01);
+        client.cache.write().await.insert(stale_key, (WeatherData::default(), Instant::now()));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        client
+            .cache
+            .write()
+            .await
+            .insert(location_key(33.81, -118.15), (WeatherData::default(), Instant::now()));
+
+        client.clear_expired().await;
+
+        let keys: Vec<_> = client.cache.read().await.keys().copied().collect();
+        assert_eq!(keys, vec![location_key(33.81, -118.15)]);
+    }
+
+    #[tokio::test]
+    async fn test_auth_failure_not_retried_and_rate_limit_retried() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bad-key/weather"))
+            .respond_with(ResponseTemplate::new(401).set_body_string(r#"{"cod":401,"message":"Invalid API key"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/busy/weather"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(RetryPolicy::default().max_attempts as u64)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), Some(format!("{}/bad-key", server.uri())));
+        let err = client.fetch_current_weather(33.81, -118.15).await.unwrap_err();
+        assert_eq!(err, WeatherError::AuthFailed);
+
+        let client = WeatherClient::new("test_key".to_string(), Some(format!("{}/busy", server.uri())));
+        let err = client.fetch_current_weather(33.81, -118.15).await.unwrap_err();
+        assert_eq!(err, WeatherError::RateLimited);
+    }
+
+    #[test]
+    fn test_urls_for_each_api_version() {
+        let client = WeatherClient::new("key".to_string(), None);
+
+        assert_eq!(
+            client.current_weather_url(33.8, -118.1),
+            "https://api.openweathermap.org/data/2.5/weather?lat=33.8&lon=-118.1&appid=key"
+        );
+        assert_eq!(
+            client.forecast_url(33.8, -118.1),
+            "https://api.openweathermap.org/data/2.5/forecast?lat=33.8&lon=-118.1&appid=key&cnt=56"
+        );
+        assert_eq!(
+            client.onecall_url(33.8, -118.1),
+            "https://api.openweathermap.org/data/3.0/onecall?lat=33.8&lon=-118.1&appid=key"
+        );
+
+        let client = client.with_onecall_base_url("http://localhost:8080/3.0".to_string());
+        assert_eq!(
+            client.onecall_url(33.8, -118.1),
+            "http://localhost:8080/3.0/onecall?lat=33.8&lon=-118.1&appid=key"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_onecall_current_weather_when_enabled() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data/3.0/onecall"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lat": 33.81,
+                "lon": -118.15,
+                "timezone": "America/Los_Angeles",
+                "timezone_offset": -28800,
+                "current": {
+                    "dt": 1700000000, "temp": 293.15, "feels_like": 293.15, "pressure": 1013,
+                    "humidity": 50, "dew_point": 283.15, "uvi": 3, "clouds": 10,
+                    "visibility": 10000, "wind_speed": 3.0, "wind_deg": 270,
+                    "weather": [{ "id": 800, "main": "Clear", "description": "clear sky", "icon": "01d" }]
+                },
+                "hourly": [],
+                "daily": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), Some(format!("{}/data/2.5", server.uri())))
+            .with_onecall_base_url(format!("{}/data/3.0", server.uri()))
+            .with_onecall_current(true);
+
+        let weather = client.fetch_current_weather(33.81, -118.15).await.unwrap();
+
+        assert_eq!(weather.conditions, "clear sky");
+        assert_eq!(weather.utc_offset_seconds, Some(-28800));
+        assert_eq!(weather.humidity_pct, Some(50.0));
+        assert!((weather.dew_point_f.unwrap() - 50.0).abs() < 0.01);
+        assert_eq!(weather.precip_probability, None);
+    }
+
+    #[tokio::test]
+    async fn test_daily_forecast_from_onecall() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let current = serde_json::json!({
+            "dt": 1700000000, "temp": 293.15, "feels_like": 293.15, "pressure": 1013,
+            "humidity": 50, "dew_point": 283.15, "uvi": 3, "clouds": 10,
+            "visibility": 10000, "wind_speed": 3.0, "wind_deg": 270,
+            "weather": [{ "id": 800, "main": "Clear", "description": "clear sky", "icon": "01d" }]
+        });
+        let temp = serde_json::json!({
+            "day": 293.15, "min": 283.15, "max": 298.15, "night": 285.0, "eve": 290.0, "morn": 284.0
+        });
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data/3.0/onecall"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lat": 33.81,
+                "lon": -118.15,
+                "timezone": "America/Los_Angeles",
+                "timezone_offset": -28800,
+                "current": current,
+                "hourly": [],
+                "daily": [{
+                    "dt": 1700020800, "sunrise": 1699998000, "sunset": 1700035200,
+                    "temp": temp, "feels_like": temp, "pressure": 1013, "humidity": 60,
+                    "dew_point": 283.15, "wind_speed": 5.0, "wind_deg": 250, "wind_gust": 8.0,
+                    "weather": [{ "id": 500, "main": "Rain", "description": "light rain", "icon": "10d" }],
+                    "clouds": 20, "pop": 0.4, "rain": 1.2, "uvi": 4
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), Some(format!("{}/data/2.5", server.uri())))
+            .with_onecall_base_url(format!("{}/data/3.0", server.uri()));
+
+        let days = client.fetch_daily_forecast(33.81, -118.15).await.unwrap();
+
+        assert_eq!(days.len(), 1);
+        let day = &days[0];
+        assert!((day.temp_high_f - 77.0).abs() < 0.01);
+        assert!((day.temp_low_f - 50.0).abs() < 0.01);
+        assert_eq!(day.precipitation_probability, 0.4);
+        assert_eq!(day.weather.conditions, "light rain");
+        assert_eq!(day.weather.wind_gust_knots, Some(8.0 * MS_TO_KNOTS));
+        assert_eq!(day.weather.sunrise, DateTime::from_timestamp(1699998000, 0));
+        assert_eq!(day.weather.utc_offset_seconds, Some(-28800));
+    }
+
+    #[tokio::test]
+    async fn test_batch_fetch_dedups_and_runs_concurrently() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/weather"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(sample_current_weather())
+                    .set_delay(Duration::from_millis(300)),
+            )
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), Some(server.uri())).with_batch_concurrency(4);
+        let locations = [
+            (33.81, -118.15),
+            (34.20, -118.49),
+            (33.81, -118.15), // duplicate
+            (32.73, -117.19),
+            (34.20, -118.49), // duplicate
+            (33.68, -117.87),
+        ];
+
+        let started = Instant::now();
+        let results = client.fetch_current_weather_batch(&locations).await;
+
+        assert_eq!(results.len(), locations.len());
+        assert!(results.iter().all(|r| r.is_ok()));
+        // Four unique requests of 300ms each, run in parallel rather than back to back
+        assert!(started.elapsed() < Duration::from_millis(1000), "took {:?}", started.elapsed());
+    }
+
+    #[test]
+    fn test_freezing_rain_is_icing_regardless_of_clouds() {
+        let data: OpenWeatherMapResponse = serde_json::from_value(serde_json::json!({
+            "weather": [{ "id": 511, "main": "Rain", "description": "freezing rain" }],
+            "main": { "temp": 274.15 },
+            "visibility": 8000,
+            "wind": { "speed": 3.0 },
+            "clouds": { "all": 20 },
+            "dt": 1700000000
+        }))
+        .unwrap();
+
+        let weather = WeatherClient::convert_to_weather_data(data);
+
+        assert!(weather.temperature_f > 32.0);
+        assert!(weather.has_icing);
+        assert!(detect_icing(&[611], 40.0, Some(20.0), Some(0.0)));
+    }
+
+    #[test]
+    fn test_cold_dry_day_is_not_icing() {
+        // 25°F under broken cloud, but a 20°F dew point spread: no visible moisture
+        assert!(!detect_icing(&[803], 25.0, Some(5.0), Some(75.0)));
+        assert!(!detect_icing(&[800], 20.0, None, Some(10.0)));
+
+        // Same temperature with near-saturated air, or light snow, does ice
+        assert!(detect_icing(&[803], 25.0, Some(22.0), Some(75.0)));
+        assert!(detect_icing(&[600], 25.0, Some(5.0), Some(75.0)));
+        // Nothing freezes above 32°F without freezing precipitation
+        assert!(!detect_icing(&[500], 40.0, Some(39.0), Some(100.0)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_taf_expands_hourly_windows() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = Utc::now();
+        let issued = now - chrono::Duration::hours(1);
+        let valid_from = issued + chrono::Duration::minutes(30);
+        let fm = valid_from + chrono::Duration::hours(6);
+        let valid_to = valid_from + chrono::Duration::hours(24);
+        let raw = format!(
+            "TAF KTOA {} {}/{} 27008KT P6SM SCT030\n  FM{} 25015G24KT 4SM BR BKN012\n",
+            issued.format("%d%H%MZ"),
+            valid_from.format("%d%H"),
+            valid_to.format("%d%H"),
+            fm.format("%d%H00"),
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/taf"))
+            .and(query_param("ids", "KTOA"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(raw))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), None).with_aviation_weather_url(server.uri());
+        let hours = client.fetch_taf("KTOA").await.unwrap();
+
+        assert_eq!(hours.len(), 24);
+        assert_eq!(hours[0].ceiling_ft, None);
+        assert_eq!(hours[23].ceiling_ft, Some(1200.0));
+        assert_eq!(hours[23].wind_gust_knots, Some(24.0));
+    }
+
+    #[tokio::test]
+    async fn test_location_forecast_prefers_taf_windows() {
+        use crate::models::Location;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = Utc::now();
+        let issued = now - chrono::Duration::hours(1);
+        let valid_from = issued + chrono::Duration::minutes(30);
+        let raw = format!(
+            "TAF KTOA {} {}/{} 27008KT P6SM SCT030\n",
+            issued.format("%d%H%MZ"),
+            valid_from.format("%d%H"),
+            (valid_from + chrono::Duration::hours(24)).format("%d%H"),
+        );
+        // A thunderstorm the TAF doesn't forecast, and an hour past the TAF's end
+        let hour = |offset: i64, id: i64, description: &str| {
+            serde_json::json!({
+                "dt": (now + chrono::Duration::hours(offset)).timestamp(), "temp": 293.15,
+                "feels_like": 293.15, "pressure": 1013, "humidity": 50, "dew_point": 283.15,
+                "uvi": 3, "clouds": 10, "visibility": 10000, "wind_speed": 3.0, "wind_deg": 270,
+                "weather": [{ "id": id, "main": "", "description": description, "icon": "" }]
+            })
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/taf"))
+            .and(query_param("ids", "KTOA"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(raw))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/data/3.0/onecall"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lat": 33.81,
+                "lon": -118.15,
+                "timezone": "America/Los_Angeles",
+                "timezone_offset": -28800,
+                "current": hour(0, 800, "clear sky"),
+                "hourly": [hour(2, 211, "thunderstorm"), hour(30, 500, "light rain")],
+                "daily": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = WeatherClient::new("test_key".to_string(), Some(format!("{}/data/2.5", server.uri())))
+            .with_onecall_base_url(format!("{}/data/3.0", server.uri()))
+            .with_aviation_weather_url(server.uri());
+        let location = Location { lat: 33.81, lon: -118.15, name: "KTOA".to_string(), elevation_ft: None };
+
+        let forecast = client.fetch_location_forecast(&location).await.unwrap();
+
+        assert_eq!(forecast.len(), 25);
+        assert!(forecast.windows(2).all(|pair| pair[0].date_time <= pair[1].date_time));
+        assert!(forecast.iter().all(|entry| !entry.has_thunderstorms));
+        assert_eq!(forecast.last().unwrap().conditions, "light rain");
+    }
+
+    #[test]
+    fn test_icao_id_only_for_station_names() {
+        use crate::models::Location;
+
+        let named = |name: &str| Location { lat: 0.0, lon: 0.0, name: name.to_string(), elevation_ft: None };
+
+        assert_eq!(named("KTOA").icao_id(), Some("KTOA"));
+        assert_eq!(named("K1G4").icao_id(), Some("K1G4"));
+        assert_eq!(named("ktoa").icao_id(), None);
+        assert_eq!(named("1G4").icao_id(), None);
+        assert_eq!(named("Torrance").icao_id(), None);
+    }
+
     #[test]
     fn test_location_serialization() {
         use crate::models::Location;
@@ -410,6 +1270,7 @@ mod tests {
             lat: 33.8113,
             lon: -118.1515,
             name: "KTOA".to_string(),
+            elevation_ft: None,
         };
 
         let json = serde_json::to_string(&location).unwrap();
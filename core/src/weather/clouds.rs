@@ -0,0 +1,99 @@
+/// Cloud cover codes that constitute a ceiling: broken, overcast, obscured,
+/// or vertical visibility into an indefinite ceiling
+const CEILING_COVERS: [&str; 4] = ["BKN", "OVC", "OVX", "VV"];
+
+/// One reported cloud layer, e.g. "BKN025" is `BKN` at 2,500 ft
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudLayer {
+    pub cover: String,
+    pub base_ft: Option<f64>,
+}
+
+impl CloudLayer {
+    pub fn new(cover: impl Into<String>, base_ft: Option<f64>) -> Self {
+        Self { cover: cover.into(), base_ft }
+    }
+
+    pub fn is_ceiling(&self) -> bool {
+        CEILING_COVERS.contains(&self.cover.as_str())
+    }
+}
+
+/// What a weather source tells us about clouds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloudInfo {
+    /// Reported layers (METAR, TAF); empty when the source only gives coverage
+    pub layers: Vec<CloudLayer>,
+    /// Total cloud cover percentage (OpenWeatherMap)
+    pub coverage_percent: Option<f64>,
+}
+
+impl CloudInfo {
+    pub fn from_layers(layers: Vec<CloudLayer>) -> Self {
+        Self { layers, coverage_percent: None }
+    }
+
+    pub fn from_coverage(coverage_percent: f64) -> Self {
+        Self { layers: Vec::new(), coverage_percent: Some(coverage_percent) }
+    }
+}
+
+/// Ceiling in feet: the base of the lowest broken or overcast layer when
+/// layers are reported, otherwise a rough estimate from total coverage
+pub fn derive_ceiling(clouds: &CloudInfo) -> Option<f64> {
+    if !clouds.layers.is_empty() {
+        return clouds
+            .layers
+            .iter()
+            .filter(|layer| layer.is_ceiling())
+            .filter_map(|layer| layer.base_ft)
+            .min_by(|a, b| a.total_cmp(b));
+    }
+
+    clouds.coverage_percent.and_then(estimate_ceiling_from_coverage)
+}
+
+/// OpenWeatherMap only reports total cover, not cloud bases, so mostly
+/// cloudy skies are bucketed into assumed low and mid-level ceilings
+fn estimate_ceiling_from_coverage(coverage_percent: f64) -> Option<f64> {
+    if coverage_percent > 80.0 {
+        Some(2000.0) // Low clouds
+    } else if coverage_percent > 50.0 {
+        Some(5000.0) // Mid clouds
+    } else {
+        None // Clear or scattered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceiling_from_lowest_broken_or_overcast_layer() {
+        let clouds = CloudInfo::from_layers(vec![
+            CloudLayer::new("FEW", Some(1200.0)),
+            CloudLayer::new("OVC", Some(4500.0)),
+            CloudLayer::new("BKN", Some(2500.0)),
+        ]);
+        assert_eq!(derive_ceiling(&clouds), Some(2500.0));
+
+        // Real layers win over coverage, even when coverage would suggest a ceiling
+        let scattered = CloudInfo {
+            layers: vec![CloudLayer::new("SCT", Some(3000.0))],
+            coverage_percent: Some(90.0),
+        };
+        assert_eq!(derive_ceiling(&scattered), None);
+
+        let obscured = CloudInfo::from_layers(vec![CloudLayer::new("VV", Some(300.0))]);
+        assert_eq!(derive_ceiling(&obscured), Some(300.0));
+    }
+
+    #[test]
+    fn test_coverage_fallback_buckets() {
+        assert_eq!(derive_ceiling(&CloudInfo::from_coverage(90.0)), Some(2000.0));
+        assert_eq!(derive_ceiling(&CloudInfo::from_coverage(60.0)), Some(5000.0));
+        assert_eq!(derive_ceiling(&CloudInfo::from_coverage(30.0)), None);
+        assert_eq!(derive_ceiling(&CloudInfo::default()), None);
+    }
+}
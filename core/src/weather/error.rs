@@ -0,0 +1,70 @@
+use crate::util::Retryable;
+use reqwest::{Response, StatusCode};
+
+/// Why a call to the weather API failed, so callers can tell a configuration
+/// problem from a transient one
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WeatherError {
+    /// The API key was rejected (401/403); retrying won't help
+    #[error("Weather API rejected the API key")]
+    AuthFailed,
+    #[error("Weather API rate limit exceeded")]
+    RateLimited,
+    /// No weather for the requested location or station
+    #[error("Weather API has no data for this location")]
+    NotFound,
+    /// Any other non-success status
+    #[error("Weather API returned status {0}")]
+    Upstream(StatusCode),
+    /// The response (JSON, TAF text) could not be understood
+    #[error("Failed to parse weather response: {0}")]
+    Parse(String),
+    /// The request never got a response: connection failure or timeout
+    #[error("Failed to reach weather API: {0}")]
+    Network(String),
+}
+
+impl WeatherError {
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => WeatherError::AuthFailed,
+            StatusCode::TOO_MANY_REQUESTS => WeatherError::RateLimited,
+            StatusCode::NOT_FOUND => WeatherError::NotFound,
+            status => WeatherError::Upstream(status),
+        }
+    }
+}
+
+impl Retryable for WeatherError {
+    /// Rate limits, server errors and network failures may clear up
+    fn is_retryable(&self) -> bool {
+        match self {
+            WeatherError::RateLimited | WeatherError::Network(_) => true,
+            WeatherError::Upstream(status) => status.is_server_error(),
+            WeatherError::AuthFailed | WeatherError::NotFound | WeatherError::Parse(_) => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for WeatherError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            WeatherError::Parse(err.to_string())
+        } else {
+            WeatherError::Network(err.to_string())
+        }
+    }
+}
+
+/// Pass a successful response through, or map a failed one to its
+/// `WeatherError`, logging the body
+pub(crate) async fn check_weather_status(service: &'static str, response: Response) -> Result<Response, WeatherError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    tracing::debug!("{} API returned status {}: {}", service, status, body);
+    Err(WeatherError::from_status(status))
+}
@@ -0,0 +1,134 @@
+use crate::weather::{location_key, WeatherData};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+
+/// Default age after which a stored forecast is fetched again
+pub const DEFAULT_FORECAST_CACHE_MAX_AGE_MINUTES: i64 = 30;
+
+/// Forecasts stored in the `forecast_cache` table, keyed like the in-memory
+/// current-weather cache by coordinates rounded to 0.01°. Database errors are
+/// logged and treated as cache misses.
+#[derive(Clone)]
+pub struct SqliteForecastCache {
+    db: SqlitePool,
+    max_age: Duration,
+}
+
+impl SqliteForecastCache {
+    /// Cache whose entries go stale after `FORECAST_CACHE_MAX_AGE_MINUTES`
+    pub fn new(db: SqlitePool) -> Self {
+        let max_age_minutes = std::env::var("FORECAST_CACHE_MAX_AGE_MINUTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_FORECAST_CACHE_MAX_AGE_MINUTES);
+
+        Self::with_max_age(db, Duration::minutes(max_age_minutes))
+    }
+
+    pub fn with_max_age(db: SqlitePool, max_age: Duration) -> Self {
+        Self { db, max_age }
+    }
+
+    fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - self.max_age
+    }
+
+    /// The stored forecast for a location, if fetched within the max age
+    pub async fn get(&self, lat: f64, lon: f64) -> Option<Vec<WeatherData>> {
+        let (lat_key, lon_key) = location_key(lat, lon);
+
+        let row: Option<String> = match sqlx::query_scalar(
+            "SELECT forecast FROM forecast_cache WHERE lat_key = ? AND lon_key = ? AND fetched_at > ?"
+        )
+        .bind(lat_key)
+        .bind(lon_key)
+        .bind(self.cutoff())
+        .fetch_optional(&self.db)
+        .await
+        {
+            Ok(row) => row,
+            Err(e) => {
+                tracing::warn!("Failed to read cached forecast for lat={}, lon={}: {}", lat, lon, e);
+                return None;
+            }
+        };
+
+        row.and_then(|json| match serde_json::from_str(&json) {
+            Ok(forecast) => Some(forecast),
+            Err(e) => {
+                tracing::warn!("Discarding unreadable cached forecast for lat={}, lon={}: {}", lat, lon, e);
+                None
+            }
+        })
+    }
+
+    /// Store a freshly fetched forecast, replacing any older one for the location
+    pub async fn set(&self, lat: f64, lon: f64, forecast: &[WeatherData]) {
+        let json = match serde_json::to_string(forecast) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize forecast for lat={}, lon={}: {}", lat, lon, e);
+                return;
+            }
+        };
+        let (lat_key, lon_key) = location_key(lat, lon);
+
+        if let Err(e) = sqlx::query(
+            "INSERT INTO forecast_cache (lat_key, lon_key, forecast, fetched_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(lat_key, lon_key) DO UPDATE SET forecast = excluded.forecast, fetched_at = excluded.fetched_at"
+        )
+        .bind(lat_key)
+        .bind(lon_key)
+        .bind(json)
+        .bind(Utc::now())
+        .execute(&self.db)
+        .await
+        {
+            tracing::warn!("Failed to cache forecast for lat={}, lon={}: {}", lat, lon, e);
+        }
+    }
+
+    /// Delete forecasts older than the max age
+    pub async fn clear_expired(&self) {
+        match sqlx::query("DELETE FROM forecast_cache WHERE fetched_at <= ?")
+            .bind(self.cutoff())
+            .execute(&self.db)
+            .await
+        {
+            Ok(result) => {
+                tracing::debug!("Removed {} expired cached forecasts", result.rows_affected());
+            }
+            Err(e) => tracing::warn!("Failed to clear expired cached forecasts: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    #[tokio::test]
+    async fn test_clear_expired_keeps_fresh_forecasts() {
+        let db = setup_test_db().await;
+        let cache = SqliteForecastCache::with_max_age(db.clone(), Duration::minutes(30));
+
+        cache.set(33.81, -118.15, &[WeatherData::default()]).await;
+        sqlx::query("INSERT INTO forecast_cache (lat_key, lon_key, forecast, fetched_at) VALUES (?, ?, ?, ?)")
+            .bind(4070)
+            .bind(-7400)
+            .bind("[]")
+            .bind(Utc::now() - Duration::minutes(31))
+            .execute(&db)
+            .await
+            .unwrap();
+
+        cache.clear_expired().await;
+
+        let keys: Vec<(i64, i64)> = sqlx::query_as("SELECT lat_key, lon_key FROM forecast_cache")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(keys, vec![location_key(33.81, -118.15)]);
+    }
+}
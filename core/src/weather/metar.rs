@@ -0,0 +1,343 @@
+use crate::util::send_logged;
+use crate::weather::{
+    celsius_to_fahrenheit, derive_ceiling, hourly_taf_windows, CloudInfo, CloudLayer, WeatherData, WeatherSource,
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Half-width in degrees of the box searched for the nearest reporting station
+const STATION_SEARCH_RADIUS_DEG: f64 = 0.5;
+
+/// NOAA Aviation Weather Center METAR client
+pub struct MetarClient {
+    client: reqwest::Client,
+    base_url: String,
+    station: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetarResponse {
+    icao_id: String,
+    obs_time: i64,
+    temp: Option<f64>,
+    dewp: Option<f64>,
+    wdir: Option<serde_json::Value>,
+    wspd: Option<f64>,
+    wgst: Option<f64>,
+    visib: Option<serde_json::Value>,
+    wx_string: Option<String>,
+    #[serde(default)]
+    clouds: Vec<MetarCloudLayer>,
+    lat: f64,
+    lon: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetarCloudLayer {
+    cover: String,
+    base: Option<f64>,
+}
+
+impl MetarClient {
+    pub fn new(station: Option<String>, base_url: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            base_url: base_url.unwrap_or_else(|| "https://aviationweather.gov/api/data".to_string()),
+            station,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let station = std::env::var("METAR_STATION").ok();
+        let base_url = std::env::var("METAR_API_BASE_URL").ok();
+
+        Self::new(station, base_url)
+    }
+
+    /// Fetch the latest METAR for an ICAO station identifier (e.g. "KTOA")
+    pub async fn fetch_station_metar(&self, station: &str) -> Result<WeatherData> {
+        Ok(Self::convert_to_weather_data(&self.station_report(station).await?))
+    }
+
+    /// Fetch the METAR from the reporting station nearest to the given coordinates
+    pub async fn fetch_nearest_metar(&self, lat: f64, lon: f64) -> Result<WeatherData> {
+        Ok(Self::convert_to_weather_data(&self.nearest_report(lat, lon).await?))
+    }
+
+    /// Fetch a station's TAF as hourly windows, or `None` when the station
+    /// doesn't issue one
+    pub async fn fetch_station_taf(&self, station: &str) -> Result<Option<Vec<WeatherData>>> {
+        let url = format!("{}/taf?ids={}&format=raw", self.base_url, station);
+
+        tracing::debug!("Fetching TAF for station {}", station);
+
+        let response = send_logged("AviationWeather", self.client.get(&url))
+            .await
+            .context("Failed to fetch TAF")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("TAF API returned status: {}", response.status());
+        }
+
+        let raw = response.text().await.context("Failed to read TAF response")?;
+        hourly_taf_windows(&raw, Utc::now()).with_context(|| format!("Failed to parse TAF for station {}", station))
+    }
+
+    /// The configured station's METAR, or the nearest station's when none is set
+    async fn report(&self, lat: f64, lon: f64) -> Result<MetarResponse> {
+        match &self.station {
+            Some(station) => self.station_report(station).await,
+            None => self.nearest_report(lat, lon).await,
+        }
+    }
+
+    async fn station_report(&self, station: &str) -> Result<MetarResponse> {
+        let url = format!("{}/metar?ids={}&format=json", self.base_url, station);
+
+        tracing::debug!("Fetching METAR for station {}", station);
+
+        let reports = self.fetch_reports(&url).await?;
+        reports
+            .into_iter()
+            .next()
+            .with_context(|| format!("No METAR available for station {}", station))
+    }
+
+    async fn nearest_report(&self, lat: f64, lon: f64) -> Result<MetarResponse> {
+        let url = format!(
+            "{}/metar?bbox={},{},{},{}&format=json",
+            self.base_url,
+            lat - STATION_SEARCH_RADIUS_DEG,
+            lon - STATION_SEARCH_RADIUS_DEG,
+            lat + STATION_SEARCH_RADIUS_DEG,
+            lon + STATION_SEARCH_RADIUS_DEG
+        );
+
+        tracing::debug!("Fetching nearest METAR for lat={}, lon={}", lat, lon);
+
+        let reports = self.fetch_reports(&url).await?;
+        let nearest = reports
+            .into_iter()
+            .min_by(|a, b| {
+                let da = (a.lat - lat).powi(2) + (a.lon - lon).powi(2);
+                let db = (b.lat - lat).powi(2) + (b.lon - lon).powi(2);
+                da.total_cmp(&db)
+            })
+            .with_context(|| format!("No METAR stations near lat={}, lon={}", lat, lon))?;
+
+        tracing::debug!("Using METAR from station {}", nearest.icao_id);
+
+        Ok(nearest)
+    }
+
+    async fn fetch_reports(&self, url: &str) -> Result<Vec<MetarResponse>> {
+        let response = send_logged("AviationWeather", self.client.get(url))
+            .await
+            .context("Failed to fetch METAR")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("METAR API returned status: {}", response.status());
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse METAR response")
+    }
+
+    fn convert_to_weather_data(data: &MetarResponse) -> WeatherData {
+        let temperature_f = data.temp.map(celsius_to_fahrenheit).unwrap_or(59.0);
+        let wx = data.wx_string.clone().unwrap_or_default();
+        let ceiling_ft = lowest_ceiling(&data.clouds);
+
+        // Visibility is reported as a number or a string such as "10+"
+        let visibility_miles = match &data.visib {
+            Some(serde_json::Value::Number(n)) => n.as_f64().unwrap_or(10.0),
+            Some(serde_json::Value::String(s)) => s.trim_end_matches('+').parse().unwrap_or(10.0),
+            _ => 10.0,
+        };
+
+        // Wind direction is numeric, or "VRB" for variable winds
+        let wind_deg = data.wdir.as_ref().and_then(|d| d.as_f64());
+
+        let has_icing = wx.contains("FZ") || (temperature_f < 32.0 && ceiling_ft.is_some());
+
+        WeatherData {
+            visibility_miles,
+            wind_speed_knots: data.wspd.unwrap_or(0.0),
+            wind_deg,
+            wind_gust_knots: data.wgst,
+            ceiling_ft,
+            temperature_f,
+            conditions: if wx.is_empty() { "Clear".to_string() } else { wx.clone() },
+            has_thunderstorms: wx.contains("TS"),
+            has_icing,
+            date_time: DateTime::from_timestamp(data.obs_time, 0).unwrap_or_else(Utc::now),
+            dew_point_f: data.dewp.map(celsius_to_fahrenheit),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherSource for MetarClient {
+    async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData> {
+        Ok(Self::convert_to_weather_data(&self.report(lat, lon).await?))
+    }
+
+    /// Hourly windows from the reporting station's TAF. METAR is only an
+    /// observation, so the latest report stands in for the forecast when the
+    /// station issues no TAF.
+    async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>> {
+        let report = self.report(lat, lon).await?;
+
+        match self.fetch_station_taf(&report.icao_id).await? {
+            Some(hours) => Ok(hours),
+            None => {
+                tracing::debug!("No TAF for station {}, using its latest METAR", report.icao_id);
+                Ok(vec![Self::convert_to_weather_data(&report)])
+            }
+        }
+    }
+}
+
+/// Ceiling is the base of the lowest broken, overcast or obscured layer
+fn lowest_ceiling(layers: &[MetarCloudLayer]) -> Option<f64> {
+    let layers = layers
+        .iter()
+        .map(|layer| CloudLayer::new(layer.cover.clone(), layer.base))
+        .collect();
+    derive_ceiling(&CloudInfo::from_layers(layers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sample_metar() -> serde_json::Value {
+        serde_json::json!([{
+            "icaoId": "KTOA",
+            "obsTime": 1700000000,
+            "temp": 18.0,
+            "wdir": 290,
+            "wspd": 9,
+            "visib": "10+",
+            "wxString": "-RA",
+            "clouds": [
+                { "cover": "FEW", "base": 1200 },
+                { "cover": "OVC", "base": 4500 },
+                { "cover": "BKN", "base": 2500 }
+            ],
+            "lat": 33.803,
+            "lon": -118.34
+        }])
+    }
+
+    #[test]
+    fn test_ceiling_from_lowest_broken_layer() {
+        let reports: Vec<MetarResponse> = serde_json::from_value(sample_metar()).unwrap();
+        let weather = MetarClient::convert_to_weather_data(&reports[0]);
+
+        assert_eq!(weather.ceiling_ft, Some(2500.0));
+        assert_eq!(weather.visibility_miles, 10.0);
+        assert_eq!(weather.wind_deg, Some(290.0));
+        assert!((weather.temperature_f - 64.4).abs() < 0.1);
+        assert!(!weather.has_thunderstorms);
+    }
+
+    #[test]
+    fn test_no_ceiling_with_scattered_layers() {
+        let layers = vec![
+            MetarCloudLayer { cover: "FEW".to_string(), base: Some(1200.0) },
+            MetarCloudLayer { cover: "SCT".to_string(), base: Some(3000.0) },
+        ];
+        assert_eq!(lowest_ceiling(&layers), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_station_metar() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/metar"))
+            .and(query_param("ids", "KTOA"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(sample_metar()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = MetarClient::new(Some("KTOA".to_string()), Some(server.uri()));
+        let weather = client.fetch_current_weather(33.8, -118.3).await.unwrap();
+
+        assert_eq!(weather.ceiling_ft, Some(2500.0));
+        assert_eq!(weather.conditions, "-RA");
+    }
+
+    /// A 24-hour KTOA TAF issued an hour ago, turning to low cloud six hours in
+    fn current_taf() -> String {
+        let issued = Utc::now() - chrono::Duration::hours(1);
+        let fm = issued + chrono::Duration::hours(7);
+        format!(
+            "TAF KTOA {} {}/{} 27008KT P6SM SCT030\n  FM{} 25015G24KT 4SM BR BKN012\n",
+            issued.format("%d%H%MZ"),
+            issued.format("%d%H"),
+            (issued + chrono::Duration::hours(24)).format("%d%H"),
+            fm.format("%d%H00"),
+        )
+    }
+
+    async fn mount_metar_and_taf(server: &MockServer, taf: String) {
+        Mock::given(method("GET"))
+            .and(path("/metar"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(sample_metar()))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/taf"))
+            .and(query_param("ids", "KTOA"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(taf))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_forecast_built_from_nearest_station_taf() {
+        let server = MockServer::start().await;
+        mount_metar_and_taf(&server, current_taf()).await;
+
+        let client = MetarClient::new(None, Some(server.uri()));
+        let forecast = client.fetch_forecast(33.8, -118.3).await.unwrap();
+
+        assert_eq!(forecast.len(), 24);
+        let in_twelve_hours = Utc::now() + chrono::Duration::hours(12);
+        let slot = forecast
+            .iter()
+            .find(|w| w.date_time <= in_twelve_hours && in_twelve_hours < w.date_time + chrono::Duration::hours(1))
+            .unwrap();
+        assert_eq!(slot.ceiling_ft, Some(1200.0));
+        assert_eq!(slot.wind_gust_knots, Some(24.0));
+    }
+
+    #[tokio::test]
+    async fn test_forecast_falls_back_to_observation_without_taf() {
+        let server = MockServer::start().await;
+        mount_metar_and_taf(&server, String::new()).await;
+
+        let client = MetarClient::new(Some("KTOA".to_string()), Some(server.uri()));
+        let forecast = client.fetch_forecast(33.8, -118.3).await.unwrap();
+
+        assert_eq!(forecast.len(), 1);
+        assert_eq!(forecast[0].conditions, "-RA");
+    }
+}
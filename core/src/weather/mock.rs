@@ -0,0 +1,133 @@
+use crate::weather::{DailyForecast, WeatherData, WeatherSource};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Weather source that replays scripted responses instead of calling an API
+///
+/// Current-weather, forecast and daily-forecast calls each take the next
+/// scripted response in order. The last response is repeated once the script runs out, so a single
+/// scripted value answers every call.
+pub struct MockWeatherClient {
+    current: Mutex<VecDeque<Result<WeatherData, String>>>,
+    forecasts: Mutex<VecDeque<Result<Vec<WeatherData>, String>>>,
+    daily: Mutex<VecDeque<Result<Vec<DailyForecast>, String>>>,
+    requests: Mutex<Vec<(f64, f64)>>,
+}
+
+impl MockWeatherClient {
+    pub fn new() -> Self {
+        Self {
+            current: Mutex::new(VecDeque::new()),
+            forecasts: Mutex::new(VecDeque::new()),
+            daily: Mutex::new(VecDeque::new()),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Script the next `fetch_current_weather` response
+    pub fn with_current(self, weather: WeatherData) -> Self {
+        self.current.lock().unwrap().push_back(Ok(weather));
+        self
+    }
+
+    /// Script the next `fetch_current_weather` call to fail
+    pub fn with_current_error(self, message: impl Into<String>) -> Self {
+        self.current.lock().unwrap().push_back(Err(message.into()));
+        self
+    }
+
+    /// Script the next `fetch_forecast` response
+    pub fn with_forecast(self, forecast: Vec<WeatherData>) -> Self {
+        self.forecasts.lock().unwrap().push_back(Ok(forecast));
+        self
+    }
+
+    /// Script the next `fetch_forecast` call to fail
+    pub fn with_forecast_error(self, message: impl Into<String>) -> Self {
+        self.forecasts.lock().unwrap().push_back(Err(message.into()));
+        self
+    }
+
+    /// Script the next `fetch_daily_forecast` response
+    pub fn with_daily_forecast(self, days: Vec<DailyForecast>) -> Self {
+        self.daily.lock().unwrap().push_back(Ok(days));
+        self
+    }
+
+    /// Coordinates of every call so far, oldest first
+    pub fn requests(&self) -> Vec<(f64, f64)> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn next<T: Clone>(&self, script: &Mutex<VecDeque<Result<T, String>>>, lat: f64, lon: f64) -> Result<T> {
+        self.requests.lock().unwrap().push((lat, lon));
+
+        let mut script = script.lock().unwrap();
+        let response = if script.len() > 1 { script.pop_front() } else { script.front().cloned() };
+
+        match response {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(message)) => Err(anyhow::anyhow!(message)),
+            None => anyhow::bail!("No scripted weather for lat={}, lon={}", lat, lon),
+        }
+    }
+}
+
+impl Default for MockWeatherClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WeatherSource for MockWeatherClient {
+    async fn fetch_current_weather(&self, lat: f64, lon: f64) -> Result<WeatherData> {
+        self.next(&self.current, lat, lon)
+    }
+
+    async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<Vec<WeatherData>> {
+        self.next(&self.forecasts, lat, lon)
+    }
+
+    async fn fetch_daily_forecast(&self, lat: f64, lon: f64) -> Result<Vec<DailyForecast>> {
+        self.next(&self.daily, lat, lon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn weather(conditions: &str) -> WeatherData {
+        WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots: 5.0,
+            ceiling_ft: None,
+            temperature_f: 65.0,
+            conditions: conditions.to_string(),
+            has_thunderstorms: false,
+            has_icing: false,
+            date_time: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replays_script_then_repeats_last() {
+        let client = MockWeatherClient::new()
+            .with_current(weather("Clear"))
+            .with_current_error("API down")
+            .with_current(weather("Rain"));
+
+        assert_eq!(client.fetch_current_weather(1.0, 2.0).await.unwrap().conditions, "Clear");
+        assert!(client.fetch_current_weather(1.0, 2.0).await.is_err());
+        assert_eq!(client.fetch_current_weather(1.0, 2.0).await.unwrap().conditions, "Rain");
+        assert_eq!(client.fetch_current_weather(3.0, 4.0).await.unwrap().conditions, "Rain");
+
+        assert_eq!(client.requests().len(), 4);
+        assert!(client.fetch_forecast(1.0, 2.0).await.is_err());
+    }
+}
@@ -1,5 +1,19 @@
 pub mod api;
+pub mod clouds;
+pub mod error;
+pub mod forecast_cache;
+pub mod metar;
+pub mod mock;
 pub mod safety;
+pub mod taf;
+pub mod units;
 
 pub use api::*;
+pub use clouds::*;
+pub use error::*;
+pub use forecast_cache::*;
+pub use metar::*;
+pub use mock::*;
 pub use safety::*;
+pub use taf::*;
+pub use units::*;
@@ -1,6 +1,12 @@
-use crate::models::{TrainingLevel, WeatherMinimum};
-use crate::weather::WeatherData;
+use crate::models::{Aircraft, TrainingLevel, WeatherMinimum, WeatherMinimumOverride};
+use crate::weather::{celsius_to_fahrenheit, fahrenheit_to_celsius, WeatherData};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::collections::HashMap;
+use std::fmt;
+use utoipa::ToSchema;
 
 // Weather scoring constants
 const PERFECT_SCORE: f32 = 10.0;
@@ -15,51 +21,245 @@ const IDEAL_CEILING_FT: f32 = 5000.0;
 const CEILING_PENALTY_FACTOR: f32 = 2.0;
 const STUDENT_HIGH_WIND_THRESHOLD_KT: f32 = 10.0;
 const STUDENT_HIGH_WIND_PENALTY: f32 = 2.0;
+const GUST_PENALTY_PER_KT: f32 = 0.1;
+const MAX_GUST_PENALTY: f32 = 2.0;
+/// Temperature/dew point spread below which fog becomes likely (about 3°C)
+const FOG_SPREAD_F: f32 = 5.0;
+const FOG_PENALTY: f32 = 2.0;
+/// Ceiling below which student pilots are held regardless of their minimums
+const STUDENT_PILOT_MIN_CEILING_FT: f64 = 3000.0;
+
+/// Default allowance for gusts above the sustained wind limit
+pub const DEFAULT_GUST_MARGIN_KT: f64 = 5.0;
+
+/// A single reason a flight failed the safety check
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum SafetyViolation {
+    Thunderstorms,
+    Icing,
+    Visibility {
+        actual: f64,
+        minimum: f64,
+        training_level: TrainingLevel,
+    },
+    Wind {
+        actual: f64,
+        maximum: f64,
+        training_level: TrainingLevel,
+    },
+    Gusts {
+        actual: f64,
+        maximum: f64,
+        training_level: TrainingLevel,
+    },
+    Crosswind {
+        actual: f64,
+        maximum: f64,
+        training_level: TrainingLevel,
+    },
+    DensityAltitude {
+        actual: f64,
+        maximum: f64,
+        training_level: TrainingLevel,
+    },
+    Ceiling {
+        actual: f64,
+        minimum: f64,
+        training_level: TrainingLevel,
+    },
+    /// Student pilots need at least 3000ft regardless of configured minimums
+    StudentPilotCeiling { actual: f64 },
+    ImcNotAllowed,
+}
+
+impl fmt::Display for SafetyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyViolation::Thunderstorms => write!(f, "Thunderstorms present"),
+            SafetyViolation::Icing => write!(f, "Icing conditions present"),
+            SafetyViolation::Visibility { actual, minimum, training_level } => write!(
+                f,
+                "Visibility {:.1}mi below minimum {:.1}mi for {:?}",
+                actual, minimum, training_level
+            ),
+            SafetyViolation::Wind { actual, maximum, training_level } => write!(
+                f,
+                "Wind speed {:.1}kt exceeds maximum {:.1}kt for {:?}",
+                actual, maximum, training_level
+            ),
+            SafetyViolation::Gusts { actual, maximum, training_level } => write!(
+                f,
+                "Wind gusts {:.1}kt exceed maximum {:.1}kt for {:?}",
+                actual, maximum, training_level
+            ),
+            SafetyViolation::Crosswind { actual, maximum, training_level } => write!(
+                f,
+                "Crosswind {:.1}kt exceeds maximum {:.1}kt for {:?}",
+                actual, maximum, training_level
+            ),
+            SafetyViolation::DensityAltitude { actual, maximum, training_level } => write!(
+                f,
+                "Density altitude {:.0}ft exceeds maximum {:.0}ft for {:?}",
+                actual, maximum, training_level
+            ),
+            SafetyViolation::Ceiling { actual, minimum, training_level } => write!(
+                f,
+                "Ceiling {:.0}ft below minimum {:.0}ft for {:?}",
+                actual, minimum, training_level
+            ),
+            SafetyViolation::StudentPilotCeiling { actual } => write!(
+                f,
+                "Ceiling {:.0}ft too low for student pilot (minimum 3000ft)",
+                actual
+            ),
+            SafetyViolation::ImcNotAllowed => {
+                write!(f, "IMC conditions not allowed for this training level")
+            }
+        }
+    }
+}
+
+/// Crosswind component in knots for a wind blowing from `wind_deg` onto a
+/// runway with magnetic heading `runway_heading_deg`
+pub fn crosswind_component(wind_speed_kt: f64, wind_deg: f64, runway_heading_deg: f64) -> f32 {
+    let angle = (wind_deg - runway_heading_deg).to_radians();
+    (wind_speed_kt * angle.sin()).abs() as f32
+}
+
+/// Density altitude in feet, using the standard rule of thumb of 120ft per
+/// degree Celsius of deviation from ISA temperature at that pressure altitude
+pub fn density_altitude_ft(pressure_altitude_ft: f64, temp_c: f64) -> f64 {
+    let isa_temp_c = 15.0 - 2.0 * pressure_altitude_ft / 1000.0;
+    pressure_altitude_ft + 120.0 * (temp_c - isa_temp_c)
+}
+
+/// Whether `date_time` falls between sunrise and sunset
+pub fn is_daylight(date_time: DateTime<Utc>, sunrise: DateTime<Utc>, sunset: DateTime<Utc>) -> bool {
+    date_time >= sunrise && date_time < sunset
+}
+
+/// Standard aviation flight category, from best to worst conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FlightCategory {
+    Vfr,
+    Mvfr,
+    Ifr,
+    Lifr,
+}
+
+/// Classify weather by the FAA ceiling and visibility thresholds; whichever
+/// of the two is worse decides the category. No reported ceiling counts as
+/// unlimited.
+pub fn flight_category(weather: &WeatherData) -> FlightCategory {
+    let ceiling_ft = weather.ceiling_ft.unwrap_or(f64::INFINITY);
+    let visibility_sm = weather.visibility_miles;
+
+    if ceiling_ft < 500.0 || visibility_sm < 1.0 {
+        FlightCategory::Lifr
+    } else if ceiling_ft < 1000.0 || visibility_sm < 3.0 {
+        FlightCategory::Ifr
+    } else if ceiling_ft < 3000.0 || visibility_sm < 5.0 {
+        FlightCategory::Mvfr
+    } else {
+        FlightCategory::Vfr
+    }
+}
 
 /// Check if flight is safe for the given training level and weather conditions
 ///
-/// Returns (is_safe, reason if unsafe)
+/// When `runway_heading_deg` is given and the minimums define a crosswind
+/// limit, the crosswind component is checked as well. Likewise density
+/// altitude is checked when the field `elevation_ft` and a limit are known.
+///
+/// Returns (is_safe, violations); the list is empty when the flight is safe
 pub fn is_flight_safe(
     training_level: &TrainingLevel,
     weather: &WeatherData,
     minimums: &WeatherMinimum,
-) -> (bool, Option<String>) {
-    let mut reasons = Vec::new();
+    runway_heading_deg: Option<f64>,
+    elevation_ft: Option<f64>,
+) -> (bool, Vec<SafetyViolation>) {
+    let mut violations = Vec::new();
 
     // Check thunderstorms (always unsafe except for specific training)
     if minimums.no_thunderstorms && weather.has_thunderstorms {
-        reasons.push("Thunderstorms present".to_string());
+        violations.push(SafetyViolation::Thunderstorms);
     }
 
     // Check icing conditions
     if minimums.no_icing && weather.has_icing {
-        reasons.push("Icing conditions present".to_string());
+        violations.push(SafetyViolation::Icing);
     }
 
     // Check visibility
     if weather.visibility_miles < minimums.min_visibility_sm {
-        reasons.push(format!(
-            "Visibility {:.1}mi below minimum {:.1}mi for {:?}",
-            weather.visibility_miles, minimums.min_visibility_sm, training_level
-        ));
+        violations.push(SafetyViolation::Visibility {
+            actual: weather.visibility_miles,
+            minimum: minimums.min_visibility_sm,
+            training_level: *training_level,
+        });
     }
 
     // Check wind speed
     if weather.wind_speed_knots > minimums.max_wind_speed_kt {
-        reasons.push(format!(
-            "Wind speed {:.1}kt exceeds maximum {:.1}kt for {:?}",
-            weather.wind_speed_knots, minimums.max_wind_speed_kt, training_level
-        ));
+        violations.push(SafetyViolation::Wind {
+            actual: weather.wind_speed_knots,
+            maximum: minimums.max_wind_speed_kt,
+            training_level: *training_level,
+        });
+    }
+
+    // Check gusts against the wind limit plus the allowed gust margin
+    if let Some(gust) = weather.wind_gust_knots {
+        let max_gust = minimums.max_wind_speed_kt + minimums.gust_margin_kt;
+        if gust > max_gust {
+            violations.push(SafetyViolation::Gusts {
+                actual: gust,
+                maximum: max_gust,
+                training_level: *training_level,
+            });
+        }
+    }
+
+    // Check crosswind component if we know the runway and wind direction
+    if let (Some(max_crosswind), Some(runway_heading), Some(wind_deg)) =
+        (minimums.max_crosswind_kt, runway_heading_deg, weather.wind_deg)
+    {
+        let crosswind = crosswind_component(weather.wind_speed_knots, wind_deg, runway_heading) as f64;
+        if crosswind > max_crosswind {
+            violations.push(SafetyViolation::Crosswind {
+                actual: crosswind,
+                maximum: max_crosswind,
+                training_level: *training_level,
+            });
+        }
+    }
+
+    // Check density altitude; without an altimeter setting, field elevation
+    // stands in for pressure altitude
+    if let (Some(max_density_altitude), Some(elevation)) = (minimums.max_density_altitude_ft, elevation_ft) {
+        let temp_c = fahrenheit_to_celsius(weather.temperature_f);
+        let density_altitude = density_altitude_ft(elevation, temp_c);
+        if density_altitude > max_density_altitude {
+            violations.push(SafetyViolation::DensityAltitude {
+                actual: density_altitude,
+                maximum: max_density_altitude,
+                training_level: *training_level,
+            });
+        }
     }
 
     // Check ceiling if minimum is specified
     if let Some(min_ceiling) = minimums.min_ceiling_ft {
         match weather.ceiling_ft {
             Some(ceiling) if ceiling < min_ceiling => {
-                reasons.push(format!(
-                    "Ceiling {:.0}ft below minimum {:.0}ft for {:?}",
-                    ceiling, min_ceiling, training_level
-                ));
+                violations.push(SafetyViolation::Ceiling {
+                    actual: ceiling,
+                    minimum: min_ceiling,
+                    training_level: *training_level,
+                });
             }
             None if !minimums.allow_imc => {
                 // No ceiling data, but IMC not allowed - treat as potentially unsafe
@@ -72,11 +272,8 @@ pub fn is_flight_safe(
     // Check for low clouds for student pilots (special case)
     if matches!(training_level, TrainingLevel::StudentPilot) {
         if let Some(ceiling) = weather.ceiling_ft {
-            if ceiling < 3000.0 {
-                reasons.push(format!(
-                    "Ceiling {:.0}ft too low for student pilot (minimum 3000ft)",
-                    ceiling
-                ));
+            if ceiling < STUDENT_PILOT_MIN_CEILING_FT {
+                violations.push(SafetyViolation::StudentPilotCeiling { actual: ceiling });
             }
         }
     }
@@ -87,59 +284,320 @@ pub fn is_flight_safe(
         // Check if conditions indicate IMC
         if let Some(ceiling) = weather.ceiling_ft {
             if ceiling < 1000.0 || weather.visibility_miles < 3.0 {
-                reasons.push("IMC conditions not allowed for this training level".to_string());
+                violations.push(SafetyViolation::ImcNotAllowed);
             }
         }
     }
 
-    if reasons.is_empty() {
-        (true, None)
-    } else {
-        (false, Some(reasons.join("; ")))
+    (violations.is_empty(), violations)
+}
+
+/// Minimums tightened to what `aircraft` can handle: the crosswind limit
+/// becomes the lower of the training-level limit and the aircraft's maximum
+/// demonstrated crosswind
+pub fn minimums_for_aircraft(minimums: &WeatherMinimum, aircraft: &Aircraft) -> WeatherMinimum {
+    let max_crosswind_kt = match (minimums.max_crosswind_kt, aircraft.max_demonstrated_crosswind_kt) {
+        (Some(training), Some(demonstrated)) => Some(training.min(demonstrated)),
+        (training, demonstrated) => training.or(demonstrated),
+    };
+
+    WeatherMinimum {
+        max_crosswind_kt,
+        ..minimums.clone()
     }
 }
 
-/// Calculate weather score from 0-10 for AI ranking
+/// Same as `is_flight_safe`, but joins the violations into a single message
 ///
-/// 10 = perfect conditions, 0 = terrible conditions
-pub fn calculate_weather_score(training_level: &TrainingLevel, weather: &WeatherData) -> f32 {
-    let mut score = PERFECT_SCORE;
+/// Returns (is_safe, reason if unsafe)
+pub fn is_flight_safe_message(
+    training_level: &TrainingLevel,
+    weather: &WeatherData,
+    minimums: &WeatherMinimum,
+    runway_heading_deg: Option<f64>,
+    elevation_ft: Option<f64>,
+) -> (bool, Option<String>) {
+    let (is_safe, violations) = is_flight_safe(training_level, weather, minimums, runway_heading_deg, elevation_ft);
+    (is_safe, join_violations(&violations))
+}
 
-    // Deduct for thunderstorms
-    if weather.has_thunderstorms {
-        score -= THUNDERSTORM_PENALTY;
+/// Every forecast entry that passes `is_flight_safe`, in forecast order
+pub fn safe_windows<'a>(
+    training_level: &TrainingLevel,
+    forecast: &'a [WeatherData],
+    minimums: &WeatherMinimum,
+    elevation_ft: Option<f64>,
+) -> Vec<&'a WeatherData> {
+    forecast
+        .iter()
+        .filter(|weather| is_flight_safe(training_level, weather, minimums, None, elevation_ft).0)
+        .collect()
+}
+
+/// How far either side of the flight `weather_trend` looks for comparison
+pub const TREND_WINDOW_HOURS: i64 = 3;
+
+/// Smallest score change (out of 10) that counts as improving or worsening
+const TREND_SCORE_DELTA: f32 = 1.0;
+
+/// Direction conditions are heading as the flight approaches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherTrend {
+    Improving,
+    Steady,
+    Worsening,
+}
+
+impl WeatherTrend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeatherTrend::Improving => "improving",
+            WeatherTrend::Steady => "steady",
+            WeatherTrend::Worsening => "worsening",
+        }
     }
+}
 
-    // Deduct for icing
-    if weather.has_icing {
-        score -= ICING_PENALTY;
+/// Compare the weather score of the forecast entry nearest `at` with the one
+/// nearest `TREND_WINDOW_HOURS` earlier. When the forecast doesn't reach back
+/// that far (flights in the next few hours), the entry at `at` is compared
+/// with the one `TREND_WINDOW_HOURS` later instead.
+///
+/// Returns None when the forecast has no second entry to compare against.
+pub fn weather_trend(
+    training_level: &TrainingLevel,
+    forecast: &[WeatherData],
+    at: DateTime<Utc>,
+) -> Option<WeatherTrend> {
+    let nearest = |target: DateTime<Utc>| {
+        forecast
+            .iter()
+            .min_by_key(|weather| (weather.date_time - target).num_seconds().abs())
+    };
+    let window = chrono::Duration::hours(TREND_WINDOW_HOURS);
+
+    let flight = nearest(at)?;
+    let (before, after) = match nearest(at - window) {
+        Some(earlier) if earlier.date_time < flight.date_time => (earlier, flight),
+        _ => (flight, nearest(at + window).filter(|later| later.date_time > flight.date_time)?),
+    };
+
+    let delta = calculate_weather_score(training_level, after) - calculate_weather_score(training_level, before);
+    Some(if delta >= TREND_SCORE_DELTA {
+        WeatherTrend::Improving
+    } else if delta <= -TREND_SCORE_DELTA {
+        WeatherTrend::Worsening
+    } else {
+        WeatherTrend::Steady
+    })
+}
+
+/// Join violations with "; ", returning None when there are none
+pub fn join_violations(violations: &[SafetyViolation]) -> Option<String> {
+    if violations.is_empty() {
+        None
+    } else {
+        Some(
+            violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+}
+
+/// Tunable penalties and thresholds used by `calculate_weather_score_with_config`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeatherScoringConfig {
+    pub thunderstorm_penalty: f32,
+    pub icing_penalty: f32,
+    pub ideal_visibility_mi: f32,
+    pub visibility_penalty_factor: f32,
+    pub calm_wind_kt: f32,
+    pub max_wind_penalty_kt: f32,
+    pub wind_penalty_factor: f32,
+    pub ideal_ceiling_ft: f32,
+    pub ceiling_penalty_factor: f32,
+    pub student_high_wind_threshold_kt: f32,
+    pub student_high_wind_penalty: f32,
+    /// Penalty per knot of gust spread above the sustained wind
+    pub gust_penalty_per_kt: f32,
+    pub max_gust_penalty: f32,
+    /// Fog penalty grows as the dew point spread shrinks below this
+    pub fog_spread_f: f32,
+    /// Penalty when temperature and dew point meet
+    pub fog_penalty: f32,
+}
+
+impl Default for WeatherScoringConfig {
+    fn default() -> Self {
+        Self {
+            thunderstorm_penalty: THUNDERSTORM_PENALTY,
+            icing_penalty: ICING_PENALTY,
+            ideal_visibility_mi: IDEAL_VISIBILITY_MI,
+            visibility_penalty_factor: VISIBILITY_PENALTY_FACTOR,
+            calm_wind_kt: CALM_WIND_KT,
+            max_wind_penalty_kt: MAX_WIND_PENALTY_KT,
+            wind_penalty_factor: WIND_PENALTY_FACTOR,
+            ideal_ceiling_ft: IDEAL_CEILING_FT,
+            ceiling_penalty_factor: CEILING_PENALTY_FACTOR,
+            student_high_wind_threshold_kt: STUDENT_HIGH_WIND_THRESHOLD_KT,
+            student_high_wind_penalty: STUDENT_HIGH_WIND_PENALTY,
+            gust_penalty_per_kt: GUST_PENALTY_PER_KT,
+            max_gust_penalty: MAX_GUST_PENALTY,
+            fog_spread_f: FOG_SPREAD_F,
+            fog_penalty: FOG_PENALTY,
+        }
     }
+}
+
+/// Calculate weather score from 0-10 for AI ranking using the default weights
+///
+/// 10 = perfect conditions, 0 = terrible conditions
+pub fn calculate_weather_score(training_level: &TrainingLevel, weather: &WeatherData) -> f32 {
+    calculate_weather_score_with_config(training_level, weather, &WeatherScoringConfig::default())
+}
+
+/// Calculate weather score from 0-10 using custom weights
+pub fn calculate_weather_score_with_config(
+    training_level: &TrainingLevel,
+    weather: &WeatherData,
+    config: &WeatherScoringConfig,
+) -> f32 {
+    // Deduct for thunderstorms, icing and fog risk
+    let mut score = PERFECT_SCORE - hazard_penalty(weather, config);
 
     // Deduct for poor visibility
-    if weather.visibility_miles < IDEAL_VISIBILITY_MI as f64 {
-        score -= ((IDEAL_VISIBILITY_MI - weather.visibility_miles as f32) / IDEAL_VISIBILITY_MI) * VISIBILITY_PENALTY_FACTOR;
+    if weather.visibility_miles < config.ideal_visibility_mi as f64 {
+        score -= ((config.ideal_visibility_mi - weather.visibility_miles as f32) / config.ideal_visibility_mi) * config.visibility_penalty_factor;
     }
 
     // Deduct for high winds
-    if weather.wind_speed_knots > CALM_WIND_KT as f64 {
-        score -= ((weather.wind_speed_knots as f32 - CALM_WIND_KT).min(MAX_WIND_PENALTY_KT) / MAX_WIND_PENALTY_KT) * WIND_PENALTY_FACTOR;
+    if weather.wind_speed_knots > config.calm_wind_kt as f64 {
+        score -= ((weather.wind_speed_knots as f32 - config.calm_wind_kt).min(config.max_wind_penalty_kt) / config.max_wind_penalty_kt) * config.wind_penalty_factor;
+    }
+
+    // Deduct for gusty conditions
+    if let Some(gust) = weather.wind_gust_knots {
+        let spread = (gust - weather.wind_speed_knots).max(0.0) as f32;
+        score -= (spread * config.gust_penalty_per_kt).min(config.max_gust_penalty);
     }
 
     // Deduct for low ceiling
     if let Some(ceiling) = weather.ceiling_ft {
-        if ceiling < IDEAL_CEILING_FT as f64 {
-            score -= ((IDEAL_CEILING_FT - ceiling as f32) / IDEAL_CEILING_FT) * CEILING_PENALTY_FACTOR;
+        if ceiling < config.ideal_ceiling_ft as f64 {
+            score -= ((config.ideal_ceiling_ft - ceiling as f32) / config.ideal_ceiling_ft) * config.ceiling_penalty_factor;
         }
     }
 
     // Student pilots need better conditions
     if matches!(training_level, TrainingLevel::StudentPilot) {
-        if weather.wind_speed_knots > STUDENT_HIGH_WIND_THRESHOLD_KT as f64 {
-            score -= STUDENT_HIGH_WIND_PENALTY;
+        if weather.wind_speed_knots > config.student_high_wind_threshold_kt as f64 {
+            score -= config.student_high_wind_penalty;
+        }
+    }
+
+    score.clamp(0.0, PERFECT_SCORE)
+}
+
+/// Calculate weather score from 0-10 relative to the minimums in effect
+///
+/// Wind, gusts, visibility and ceiling are penalized by how much of the
+/// margin between ideal conditions and the configured limit they use up,
+/// squared so that conditions close to a limit cost far more than moderate
+/// ones: 10kt against a 25kt limit scores well, 10kt against an 11kt limit
+/// does not. Anything at or past a limit takes the full penalty; whether the
+/// flight may go at all is still `is_flight_safe`'s call.
+pub fn calculate_weather_score_vs_minimums(
+    training_level: &TrainingLevel,
+    weather: &WeatherData,
+    minimums: &WeatherMinimum,
+) -> f32 {
+    let config = WeatherScoringConfig::default();
+    let mut score = PERFECT_SCORE - hazard_penalty(weather, &config);
+
+    let wind_used = margin_used(weather.wind_speed_knots, minimums.max_wind_speed_kt);
+    score -= wind_used.powi(2) * config.wind_penalty_factor;
+
+    if let Some(gust) = weather.wind_gust_knots {
+        let gust_used = margin_used(gust, minimums.max_wind_speed_kt + minimums.gust_margin_kt);
+        score -= gust_used.powi(2) * config.max_gust_penalty;
+    }
+
+    let ideal_visibility = config.ideal_visibility_mi as f64;
+    let visibility_used = margin_used(
+        ideal_visibility - weather.visibility_miles,
+        ideal_visibility - minimums.min_visibility_sm,
+    );
+    score -= visibility_used.powi(2) * config.visibility_penalty_factor;
+
+    if let Some(ceiling) = weather.ceiling_ft {
+        // Student pilots are held below 3000ft whatever their minimums say
+        let mut min_ceiling = minimums.min_ceiling_ft.unwrap_or(0.0);
+        if matches!(training_level, TrainingLevel::StudentPilot) {
+            min_ceiling = min_ceiling.max(STUDENT_PILOT_MIN_CEILING_FT);
+        }
+        let ideal_ceiling = config.ideal_ceiling_ft as f64;
+        let ceiling_used = margin_used(ideal_ceiling - ceiling, ideal_ceiling - min_ceiling);
+        score -= ceiling_used.powi(2) * config.ceiling_penalty_factor;
+    }
+
+    score.clamp(0.0, PERFECT_SCORE)
+}
+
+/// Penalty both scores take for thunderstorms, icing and fog risk, which
+/// don't depend on the pilot's limits
+fn hazard_penalty(weather: &WeatherData, config: &WeatherScoringConfig) -> f32 {
+    let mut penalty = fog_penalty(weather, config);
+    if weather.has_thunderstorms {
+        penalty += config.thunderstorm_penalty;
+    }
+    if weather.has_icing {
+        penalty += config.icing_penalty;
+    }
+    penalty
+}
+
+/// Fraction (0-1) of the `room` before a limit taken up by `used`; with no
+/// room left any use at all counts as reaching the limit
+fn margin_used(used: f64, room: f64) -> f32 {
+    if used <= 0.0 {
+        0.0
+    } else if room <= 0.0 {
+        1.0
+    } else {
+        (used / room).min(1.0) as f32
+    }
+}
+
+/// Fog risk penalty, scaled by how close the temperature is to the dew point
+fn fog_penalty(weather: &WeatherData, config: &WeatherScoringConfig) -> f32 {
+    match dew_point_spread_f(weather) {
+        Some(spread) if spread < config.fog_spread_f as f64 => {
+            (1.0 - spread.max(0.0) as f32 / config.fog_spread_f) * config.fog_penalty
         }
+        _ => 0.0,
     }
+}
 
-    score.max(0.0).min(PERFECT_SCORE)
+/// Temperature minus dew point in °F. The dew point is estimated from
+/// relative humidity (Magnus formula) when the source doesn't report it.
+fn dew_point_spread_f(weather: &WeatherData) -> Option<f64> {
+    const MAGNUS_A: f64 = 17.62;
+    const MAGNUS_B: f64 = 243.12;
+
+    let dew_point_f = match (weather.dew_point_f, weather.humidity_pct) {
+        (Some(dew_point_f), _) => dew_point_f,
+        (None, Some(humidity)) => {
+            let temp_c = fahrenheit_to_celsius(weather.temperature_f);
+            let gamma = (humidity.clamp(1.0, 100.0) / 100.0).ln() + MAGNUS_A * temp_c / (MAGNUS_B + temp_c);
+            celsius_to_fahrenheit(MAGNUS_B * gamma / (MAGNUS_A - gamma))
+        }
+        (None, None) => return None,
+    };
+    Some(weather.temperature_f - dew_point_f)
 }
 
 /// Default weather minimums for each training level
@@ -153,10 +611,14 @@ pub fn default_weather_minimums() -> HashMap<TrainingLevel, WeatherMinimum> {
             training_level: TrainingLevel::StudentPilot,
             min_visibility_sm: 5.0,
             max_wind_speed_kt: 12.0,
+            max_crosswind_kt: Some(10.0),
+            gust_margin_kt: DEFAULT_GUST_MARGIN_KT,
+            max_density_altitude_ft: Some(6000.0),
             min_ceiling_ft: Some(3000.0),
             allow_imc: false,
             no_thunderstorms: true,
             no_icing: true,
+            allow_night: false,
         },
     );
 
@@ -167,10 +629,14 @@ pub fn default_weather_minimums() -> HashMap<TrainingLevel, WeatherMinimum> {
             training_level: TrainingLevel::PrivatePilot,
             min_visibility_sm: 3.0,
             max_wind_speed_kt: 20.0,
+            max_crosswind_kt: Some(15.0),
+            gust_margin_kt: DEFAULT_GUST_MARGIN_KT,
+            max_density_altitude_ft: Some(8000.0),
             min_ceiling_ft: Some(1000.0),
             allow_imc: false,
             no_thunderstorms: true,
             no_icing: true,
+            allow_night: true,
         },
     );
 
@@ -181,16 +647,84 @@ pub fn default_weather_minimums() -> HashMap<TrainingLevel, WeatherMinimum> {
             training_level: TrainingLevel::InstrumentRated,
             min_visibility_sm: 1.0,
             max_wind_speed_kt: 30.0,
+            max_crosswind_kt: None,
+            gust_margin_kt: DEFAULT_GUST_MARGIN_KT,
+            max_density_altitude_ft: None,
             min_ceiling_ft: None,
             allow_imc: true,
             no_thunderstorms: true,
             no_icing: true,
+            allow_night: true,
         },
     );
 
     minimums
 }
 
+/// Load weather minimums from the `weather_minimums` table
+///
+/// Falls back to `default_weather_minimums()` when the table is empty; a
+/// training level missing from a non-empty table also keeps its default.
+pub async fn load_weather_minimums(db: &SqlitePool) -> Result<HashMap<TrainingLevel, WeatherMinimum>> {
+    let rows = sqlx::query_as::<_, WeatherMinimum>(
+        "SELECT id, training_level, min_visibility_sm, max_wind_speed_kt, max_crosswind_kt, gust_margin_kt,
+                max_density_altitude_ft, min_ceiling_ft, allow_imc, no_thunderstorms, no_icing, allow_night
+         FROM weather_minimums"
+    )
+    .fetch_all(db)
+    .await
+    .context("Failed to load weather minimums")?;
+
+    let mut minimums = default_weather_minimums();
+
+    if rows.is_empty() {
+        tracing::warn!("weather_minimums table is empty, using default minimums");
+        return Ok(minimums);
+    }
+
+    for row in rows {
+        minimums.insert(row.training_level, row);
+    }
+
+    Ok(minimums)
+}
+
+/// Minimums for one flight: the training level's minimums with the booking's
+/// override applied, tightened by the booked aircraft's limits when the
+/// booking references a fleet aircraft
+pub async fn load_flight_minimums(
+    db: &SqlitePool,
+    training_level: TrainingLevel,
+    aircraft_id: Option<&str>,
+    overrides: Option<&WeatherMinimumOverride>,
+) -> Result<WeatherMinimum> {
+    let minimums = load_weather_minimums(db).await?;
+    let level_minimums = minimums
+        .get(&training_level)
+        .ok_or_else(|| anyhow::anyhow!("No minimums for training level"))?;
+    let level_minimums = match overrides {
+        Some(overrides) => level_minimums.with_override(overrides),
+        None => level_minimums.clone(),
+    };
+
+    let Some(aircraft_id) = aircraft_id else {
+        return Ok(level_minimums);
+    };
+
+    let aircraft = sqlx::query_as::<_, Aircraft>(
+        "SELECT id, tail_number, model, max_demonstrated_crosswind_kt, ifr_capable FROM aircraft WHERE id = ?"
+    )
+    .bind(aircraft_id)
+    .fetch_optional(db)
+    .await
+    .context("Failed to load aircraft")?;
+
+    Ok(match aircraft {
+        Some(aircraft) => minimums_for_aircraft(&level_minimums, &aircraft),
+        None => level_minimums,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,29 +746,123 @@ mod tests {
             has_thunderstorms: thunderstorms,
             has_icing: icing,
             date_time: Utc::now(),
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn test_flight_category_ceiling_boundaries() {
+        let category = |ceiling| flight_category(&create_test_weather(10.0, 5.0, ceiling, false, false));
+
+        assert_eq!(category(None), FlightCategory::Vfr);
+        assert_eq!(category(Some(3000.0)), FlightCategory::Vfr);
+        assert_eq!(category(Some(2999.0)), FlightCategory::Mvfr);
+        assert_eq!(category(Some(1000.0)), FlightCategory::Mvfr);
+        assert_eq!(category(Some(999.0)), FlightCategory::Ifr);
+        assert_eq!(category(Some(500.0)), FlightCategory::Ifr);
+        assert_eq!(category(Some(499.0)), FlightCategory::Lifr);
+    }
+
+    #[test]
+    fn test_flight_category_visibility_boundaries() {
+        let category = |visibility| flight_category(&create_test_weather(visibility, 5.0, None, false, false));
+
+        assert_eq!(category(5.0), FlightCategory::Vfr);
+        assert_eq!(category(4.9), FlightCategory::Mvfr);
+        assert_eq!(category(3.0), FlightCategory::Mvfr);
+        assert_eq!(category(2.9), FlightCategory::Ifr);
+        assert_eq!(category(1.0), FlightCategory::Ifr);
+        assert_eq!(category(0.9), FlightCategory::Lifr);
+    }
+
+    #[test]
+    fn test_flight_category_uses_worse_of_ceiling_and_visibility() {
+        let weather = create_test_weather(10.0, 5.0, Some(800.0), false, false);
+        assert_eq!(flight_category(&weather), FlightCategory::Ifr);
+
+        let weather = create_test_weather(0.5, 5.0, Some(4000.0), false, false);
+        assert_eq!(flight_category(&weather), FlightCategory::Lifr);
+        assert_eq!(serde_json::to_value(FlightCategory::Lifr).unwrap(), "LIFR");
+    }
+
     #[test]
     fn test_student_pilot_good_weather() {
         let minimums = default_weather_minimums();
         let weather = create_test_weather(10.0, 8.0, Some(4000.0), false, false);
-        let (is_safe, reason) = is_flight_safe(
+        let (is_safe, reason) = is_flight_safe_message(
             &TrainingLevel::StudentPilot,
             &weather,
             minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            None,
         );
         assert!(is_safe, "Should be safe: {:?}", reason);
     }
 
+    #[test]
+    fn test_only_third_forecast_entry_safe() {
+        let minimums = default_weather_minimums();
+        let student = minimums.get(&TrainingLevel::StudentPilot).unwrap();
+        let mut forecast = vec![
+            create_test_weather(10.0, 8.0, Some(4000.0), true, false),
+            create_test_weather(10.0, 25.0, Some(4000.0), false, false),
+            create_test_weather(10.0, 8.0, Some(4000.0), false, false),
+            create_test_weather(2.0, 8.0, Some(4000.0), false, false),
+        ];
+        for (hour, weather) in forecast.iter_mut().enumerate() {
+            weather.date_time += chrono::Duration::hours(hour as i64);
+        }
+
+        let all = safe_windows(&TrainingLevel::StudentPilot, &forecast, student, None);
+        let times: Vec<_> = all.iter().map(|weather| weather.date_time).collect();
+        assert_eq!(times, vec![forecast[2].date_time]);
+
+        assert!(safe_windows(&TrainingLevel::StudentPilot, &forecast[..2], student, None).is_empty());
+    }
+
+    #[test]
+    fn test_weather_trend_classification() {
+        let level = TrainingLevel::PrivatePilot;
+        let series = |winds: [f64; 4]| -> Vec<WeatherData> {
+            let start = Utc::now();
+            winds
+                .iter()
+                .enumerate()
+                .map(|(i, &wind)| WeatherData {
+                    date_time: start + chrono::Duration::hours(3 * i as i64),
+                    ..create_test_weather(10.0, wind, None, false, false)
+                })
+                .collect()
+        };
+
+        // Flight at the third entry, compared with the one three hours earlier
+        let forecast = series([20.0, 20.0, 5.0, 5.0]);
+        let at = forecast[2].date_time;
+        assert_eq!(weather_trend(&level, &forecast, at), Some(WeatherTrend::Improving));
+
+        let forecast = series([5.0, 5.0, 20.0, 20.0]);
+        assert_eq!(weather_trend(&level, &forecast, at), Some(WeatherTrend::Worsening));
+
+        let forecast = series([8.0, 8.0, 10.0, 10.0]);
+        assert_eq!(weather_trend(&level, &forecast, at), Some(WeatherTrend::Steady));
+
+        // An imminent flight falls back to comparing with the entry after it
+        let forecast = series([5.0, 20.0, 20.0, 20.0]);
+        assert_eq!(weather_trend(&level, &forecast, forecast[0].date_time), Some(WeatherTrend::Worsening));
+
+        assert_eq!(weather_trend(&level, &forecast[..1], at), None);
+    }
+
     #[test]
     fn test_student_pilot_high_winds() {
         let minimums = default_weather_minimums();
         let weather = create_test_weather(10.0, 15.0, Some(4000.0), false, false);
-        let (is_safe, reason) = is_flight_safe(
+        let (is_safe, reason) = is_flight_safe_message(
             &TrainingLevel::StudentPilot,
             &weather,
             minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            None,
         );
         assert!(!is_safe);
         assert!(reason.unwrap().contains("Wind speed"));
@@ -244,10 +872,12 @@ mod tests {
     fn test_student_pilot_low_ceiling() {
         let minimums = default_weather_minimums();
         let weather = create_test_weather(10.0, 8.0, Some(2500.0), false, false);
-        let (is_safe, reason) = is_flight_safe(
+        let (is_safe, reason) = is_flight_safe_message(
             &TrainingLevel::StudentPilot,
             &weather,
             minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            None,
         );
         assert!(!is_safe);
         assert!(reason.unwrap().contains("low for student pilot"));
@@ -261,6 +891,8 @@ mod tests {
             &TrainingLevel::PrivatePilot,
             &weather,
             minimums.get(&TrainingLevel::PrivatePilot).unwrap(),
+            None,
+            None,
         );
         assert!(is_safe);
     }
@@ -273,6 +905,8 @@ mod tests {
             &TrainingLevel::InstrumentRated,
             &weather,
             minimums.get(&TrainingLevel::InstrumentRated).unwrap(),
+            None,
+            None,
         );
         assert!(is_safe);
     }
@@ -287,7 +921,7 @@ mod tests {
             TrainingLevel::PrivatePilot,
             TrainingLevel::InstrumentRated,
         ] {
-            let (is_safe, reason) = is_flight_safe(level, &weather, minimums.get(level).unwrap());
+            let (is_safe, reason) = is_flight_safe_message(level, &weather, minimums.get(level).unwrap(), None, None);
             assert!(!is_safe);
             assert!(reason.unwrap().contains("Thunderstorms"));
         }
@@ -298,15 +932,171 @@ mod tests {
         let minimums = default_weather_minimums();
         let weather = create_test_weather(10.0, 5.0, Some(5000.0), false, true);
 
-        let (is_safe, reason) = is_flight_safe(
+        let (is_safe, reason) = is_flight_safe_message(
             &TrainingLevel::StudentPilot,
             &weather,
             minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            None,
         );
         assert!(!is_safe);
         assert!(reason.unwrap().contains("Icing"));
     }
 
+    #[test]
+    fn test_violations_are_structured() {
+        let minimums = default_weather_minimums();
+        let weather = create_test_weather(2.0, 15.0, Some(4000.0), true, false);
+
+        let (is_safe, violations) = is_flight_safe(
+            &TrainingLevel::StudentPilot,
+            &weather,
+            minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            None,
+        );
+        assert!(!is_safe);
+        assert!(violations.contains(&SafetyViolation::Thunderstorms));
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            SafetyViolation::Wind { actual, maximum, .. } if *actual == 15.0 && *maximum == 12.0
+        )));
+        assert_eq!(
+            SafetyViolation::Visibility {
+                actual: 2.0,
+                minimum: 5.0,
+                training_level: TrainingLevel::StudentPilot,
+            }
+            .to_string(),
+            "Visibility 2.0mi below minimum 5.0mi for StudentPilot"
+        );
+    }
+
+    #[test]
+    fn test_gusts_above_margin_unsafe() {
+        let minimums = default_weather_minimums();
+        let student_mins = minimums.get(&TrainingLevel::StudentPilot).unwrap();
+        let mut weather = create_test_weather(10.0, 10.0, Some(4000.0), false, false);
+
+        weather.wind_gust_knots = Some(16.0);
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, None);
+        assert!(is_safe, "Gusts within the margin should be allowed");
+
+        weather.wind_gust_knots = Some(30.0);
+        let (is_safe, violations) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, None);
+        assert!(!is_safe);
+        assert!(matches!(violations[0], SafetyViolation::Gusts { actual, .. } if actual == 30.0));
+    }
+
+    #[test]
+    fn test_weather_score_gust_penalty() {
+        let steady = create_test_weather(10.0, 10.0, Some(5000.0), false, false);
+        let mut gusty = steady.clone();
+        gusty.wind_gust_knots = Some(25.0);
+
+        let steady_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &steady);
+        let gusty_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &gusty);
+        assert!(gusty_score < steady_score);
+    }
+
+    #[test]
+    fn test_crosswind_component_headwind() {
+        let crosswind = crosswind_component(15.0, 270.0, 270.0);
+        assert!(crosswind.abs() < 0.01, "Pure headwind should have no crosswind: {}", crosswind);
+    }
+
+    #[test]
+    fn test_crosswind_component_full_crosswind() {
+        let crosswind = crosswind_component(15.0, 360.0, 270.0);
+        assert!((crosswind - 15.0).abs() < 0.01, "90° wind should be all crosswind: {}", crosswind);
+    }
+
+    #[test]
+    fn test_crosswind_checked_only_with_runway_heading() {
+        let minimums = default_weather_minimums();
+        let student_mins = minimums.get(&TrainingLevel::StudentPilot).unwrap();
+        let mut weather = create_test_weather(10.0, 11.0, Some(4000.0), false, false);
+        weather.wind_deg = Some(360.0);
+
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, None);
+        assert!(is_safe, "Without a runway heading only total wind is checked");
+
+        let (is_safe, violations) =
+            is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, Some(270.0), None);
+        assert!(!is_safe);
+        assert!(matches!(violations[0], SafetyViolation::Crosswind { .. }));
+
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, Some(360.0), None);
+        assert!(is_safe, "Headwind down the runway should not trip the crosswind limit");
+    }
+
+    #[test]
+    fn test_aircraft_crosswind_limit_tightens_minimums() {
+        let minimums = default_weather_minimums();
+        let mut aircraft = Aircraft {
+            id: "a1".to_string(),
+            tail_number: "N12345".to_string(),
+            model: "Cessna 152".to_string(),
+            max_demonstrated_crosswind_kt: Some(8.0),
+            ifr_capable: false,
+        };
+        let mut weather = create_test_weather(10.0, 9.0, Some(4000.0), false, false);
+        weather.wind_deg = Some(360.0);
+
+        let student_mins = minimums_for_aircraft(minimums.get(&TrainingLevel::StudentPilot).unwrap(), &aircraft);
+        assert_eq!(student_mins.max_crosswind_kt, Some(8.0));
+        let (is_safe, violations) =
+            is_flight_safe(&TrainingLevel::StudentPilot, &weather, &student_mins, Some(270.0), None);
+        assert!(!is_safe, "9kt crosswind exceeds the aircraft's 8kt demonstrated crosswind");
+        assert!(matches!(violations[0], SafetyViolation::Crosswind { maximum, .. } if maximum == 8.0));
+
+        // The aircraft limit also applies when the training level sets none
+        let instrument_mins =
+            minimums_for_aircraft(minimums.get(&TrainingLevel::InstrumentRated).unwrap(), &aircraft);
+        assert_eq!(instrument_mins.max_crosswind_kt, Some(8.0));
+
+        // A more capable aircraft never loosens the training-level limit
+        aircraft.max_demonstrated_crosswind_kt = Some(20.0);
+        let student_mins = minimums_for_aircraft(minimums.get(&TrainingLevel::StudentPilot).unwrap(), &aircraft);
+        assert_eq!(student_mins.max_crosswind_kt, Some(10.0));
+    }
+
+    #[test]
+    fn test_density_altitude_sea_level_cool_day() {
+        let minimums = default_weather_minimums();
+        let mut weather = create_test_weather(10.0, 5.0, Some(5000.0), false, false);
+        weather.temperature_f = 50.0; // 10°C
+
+        assert!(density_altitude_ft(0.0, 10.0) < 0.0);
+        let (is_safe, _) = is_flight_safe(
+            &TrainingLevel::StudentPilot,
+            &weather,
+            minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+            None,
+            Some(0.0),
+        );
+        assert!(is_safe);
+    }
+
+    #[test]
+    fn test_density_altitude_hot_high_field() {
+        let minimums = default_weather_minimums();
+        let student_mins = minimums.get(&TrainingLevel::StudentPilot).unwrap();
+        let mut weather = create_test_weather(10.0, 5.0, Some(5000.0), false, false);
+        weather.temperature_f = 95.0; // 35°C
+
+        // ISA at 5000ft is 5°C, so 30°C above standard adds 3600ft
+        assert!((density_altitude_ft(5000.0, 35.0) - 8600.0).abs() < 0.1);
+
+        let (is_safe, violations) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, Some(5000.0));
+        assert!(!is_safe);
+        assert!(matches!(violations[0], SafetyViolation::DensityAltitude { maximum, .. } if maximum == 6000.0));
+
+        // Unknown elevation keeps the old behavior
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, None);
+        assert!(is_safe);
+    }
+
     #[test]
     fn test_weather_score_perfect_conditions() {
         let weather = create_test_weather(10.0, 5.0, Some(5000.0), false, false);
@@ -314,6 +1104,103 @@ mod tests {
         assert!(score >= 9.0, "Perfect weather should score high: {}", score);
     }
 
+    #[test]
+    fn test_weather_score_custom_wind_weight() {
+        let weather = create_test_weather(10.0, 15.0, Some(5000.0), false, false);
+        let config = WeatherScoringConfig {
+            wind_penalty_factor: 6.0,
+            ..WeatherScoringConfig::default()
+        };
+
+        let default_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &weather);
+        let weighted_score = calculate_weather_score_with_config(&TrainingLevel::PrivatePilot, &weather, &config);
+        assert!(weighted_score < default_score, "{} should be below {}", weighted_score, default_score);
+    }
+
+    #[test]
+    fn test_near_dew_point_lowers_score() {
+        let clear = create_test_weather(10.0, 5.0, Some(5000.0), false, false);
+        let dry = WeatherData {
+            dew_point_f: Some(40.0),
+            humidity_pct: Some(40.0),
+            ..clear.clone()
+        };
+        let foggy = WeatherData {
+            dew_point_f: Some(clear.temperature_f - 1.0),
+            humidity_pct: Some(96.0),
+            ..clear.clone()
+        };
+        let humid_no_dew_point = WeatherData {
+            humidity_pct: Some(97.0),
+            ..clear.clone()
+        };
+
+        let clear_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &clear);
+        assert_eq!(calculate_weather_score(&TrainingLevel::PrivatePilot, &dry), clear_score);
+
+        let foggy_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &foggy);
+        assert!(foggy_score < clear_score - 1.0, "{} should be well below {}", foggy_score, clear_score);
+
+        // Dew point estimated from humidity alone
+        let humid_score = calculate_weather_score(&TrainingLevel::PrivatePilot, &humid_no_dew_point);
+        assert!(humid_score < clear_score, "{} should be below {}", humid_score, clear_score);
+    }
+
+    #[test]
+    fn test_score_vs_minimums_drops_near_wind_limit() {
+        let level = TrainingLevel::PrivatePilot;
+        let generous = WeatherMinimum {
+            max_wind_speed_kt: 25.0,
+            ..default_weather_minimums()[&level].clone()
+        };
+        let strict = WeatherMinimum {
+            max_wind_speed_kt: 11.0,
+            ..generous.clone()
+        };
+        let score = |wind, minimums: &WeatherMinimum| {
+            calculate_weather_score_vs_minimums(&level, &create_test_weather(10.0, wind, Some(5000.0), false, false), minimums)
+        };
+
+        // 10kt is moderate in absolute terms, and the level-only score can't tell the two apart
+        let weather = create_test_weather(10.0, 10.0, Some(5000.0), false, false);
+        assert!(calculate_weather_score(&level, &weather) > 8.0);
+        assert!(score(10.0, &generous) > 9.0, "{}", score(10.0, &generous));
+        assert!(score(10.0, &strict) < score(10.0, &generous) - 1.0);
+
+        // Half the limit scores better than 95% of it
+        assert!(score(5.5, &strict) > score(10.45, &strict) + 1.0);
+        assert!(score(0.0, &strict) > score(5.5, &strict));
+        assert_eq!(score(11.0, &strict), score(20.0, &strict));
+    }
+
+    #[test]
+    fn test_score_vs_minimums_drops_near_visibility_and_ceiling_limits() {
+        let level = TrainingLevel::PrivatePilot;
+        let weather = create_test_weather(6.0, 5.0, Some(3500.0), false, false);
+        let relaxed = default_weather_minimums()[&level].clone();
+        let tight = WeatherMinimum {
+            min_visibility_sm: 5.5,
+            min_ceiling_ft: Some(3300.0),
+            ..relaxed.clone()
+        };
+
+        let relaxed_score = calculate_weather_score_vs_minimums(&level, &weather, &relaxed);
+        let tight_score = calculate_weather_score_vs_minimums(&level, &weather, &tight);
+        assert!(relaxed_score > 8.5, "{}", relaxed_score);
+        assert!(tight_score < relaxed_score - 1.5, "{} vs {}", tight_score, relaxed_score);
+
+        // Student pilots are held below 3000ft even with lower configured minimums
+        let student = WeatherMinimum {
+            min_ceiling_ft: Some(1000.0),
+            ..relaxed.clone()
+        };
+        let low = create_test_weather(10.0, 5.0, Some(3100.0), false, false);
+        assert!(
+            calculate_weather_score_vs_minimums(&TrainingLevel::StudentPilot, &low, &student)
+                < calculate_weather_score_vs_minimums(&level, &low, &student)
+        );
+    }
+
     #[test]
     fn test_weather_score_poor_conditions() {
         let weather = create_test_weather(2.0, 25.0, Some(1000.0), false, true);
@@ -328,14 +1215,18 @@ mod tests {
             training_level: TrainingLevel::PrivatePilot,
             min_visibility_sm: 3.0,
             max_wind_speed_kt: 20.0,
+            max_crosswind_kt: None,
+            gust_margin_kt: DEFAULT_GUST_MARGIN_KT,
+            max_density_altitude_ft: None,
             min_ceiling_ft: Some(1000.0),
             allow_imc: false,
             no_thunderstorms: true,
             no_icing: true,
+            allow_night: true,
         };
 
         let weather = create_test_weather(3.0, 20.0, Some(1000.0), false, false);
-        let (is_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, &minimums);
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, &minimums, None, None);
         assert!(is_safe);
     }
 
@@ -346,20 +1237,55 @@ mod tests {
             training_level: TrainingLevel::PrivatePilot,
             min_visibility_sm: 3.0,
             max_wind_speed_kt: 20.0,
+            max_crosswind_kt: None,
+            gust_margin_kt: DEFAULT_GUST_MARGIN_KT,
+            max_density_altitude_ft: None,
             min_ceiling_ft: Some(1000.0),
             allow_imc: false,
             no_thunderstorms: true,
             no_icing: true,
+            allow_night: true,
         };
 
         let weather = create_test_weather(2.9, 20.1, Some(999.0), false, false);
-        let (is_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, &minimums);
+        let (is_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, &minimums, None, None);
         assert!(!is_safe);
     }
 
     // Property-based tests with proptest
     use proptest::prelude::*;
 
+    fn scoring_config_strategy() -> impl Strategy<Value = WeatherScoringConfig> {
+        (
+            (0.0f32..20.0, 0.0f32..20.0, 0.1f32..20.0, 0.0f32..20.0),
+            (0.0f32..20.0, 0.1f32..40.0, 0.0f32..20.0),
+            (100.0f32..10000.0, 0.0f32..20.0, 0.0f32..30.0, 0.0f32..20.0),
+            (0.0f32..2.0, 0.0f32..20.0, 0.1f32..20.0, 0.0f32..20.0),
+        )
+            .prop_map(|(
+                (thunderstorm_penalty, icing_penalty, ideal_visibility_mi, visibility_penalty_factor),
+                (calm_wind_kt, max_wind_penalty_kt, wind_penalty_factor),
+                (ideal_ceiling_ft, ceiling_penalty_factor, student_high_wind_threshold_kt, student_high_wind_penalty),
+                (gust_penalty_per_kt, max_gust_penalty, fog_spread_f, fog_penalty),
+            )| WeatherScoringConfig {
+                thunderstorm_penalty,
+                icing_penalty,
+                ideal_visibility_mi,
+                visibility_penalty_factor,
+                calm_wind_kt,
+                max_wind_penalty_kt,
+                wind_penalty_factor,
+                ideal_ceiling_ft,
+                ceiling_penalty_factor,
+                student_high_wind_threshold_kt,
+                student_high_wind_penalty,
+                gust_penalty_per_kt,
+                max_gust_penalty,
+                fog_spread_f,
+                fog_penalty,
+            })
+    }
+
     proptest! {
         #[test]
         fn prop_student_pilot_stricter_than_private(
@@ -379,8 +1305,8 @@ mod tests {
                 false, // no icing
             );
 
-            let (student_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins);
-            let (private_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, private_mins);
+            let (student_safe, _) = is_flight_safe(&TrainingLevel::StudentPilot, &weather, student_mins, None, None);
+            let (private_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, private_mins, None, None);
 
             // Property: If it's safe for students, it must be safe for private pilots
             // (Student pilot minimums are stricter)
@@ -410,8 +1336,8 @@ mod tests {
                 false,
             );
 
-            let (private_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, private_mins);
-            let (instrument_safe, _) = is_flight_safe(&TrainingLevel::InstrumentRated, &weather, instrument_mins);
+            let (private_safe, _) = is_flight_safe(&TrainingLevel::PrivatePilot, &weather, private_mins, None, None);
+            let (instrument_safe, _) = is_flight_safe(&TrainingLevel::InstrumentRated, &weather, instrument_mins, None, None);
 
             // If it's safe for private pilots, it should be safe for instrument-rated pilots
             if private_safe {
@@ -429,6 +1355,7 @@ mod tests {
             ceiling in 0.0f64..15000.0,
             has_thunderstorms: bool,
             has_icing: bool,
+            config in scoring_config_strategy(),
         ) {
             let weather = create_test_weather(
                 visibility,
@@ -439,10 +1366,10 @@ mod tests {
             );
 
             for training_level in [TrainingLevel::StudentPilot, TrainingLevel::PrivatePilot, TrainingLevel::InstrumentRated] {
-                let score = calculate_weather_score(&training_level, &weather);
+                let score = calculate_weather_score_with_config(&training_level, &weather, &config);
                 prop_assert!(score >= 0.0 && score <= PERFECT_SCORE,
-                    "Score {} out of bounds [0, {}] for {:?}",
-                    score, PERFECT_SCORE, training_level
+                    "Score {} out of bounds [0, {}] for {:?} with {:?}",
+                    score, PERFECT_SCORE, training_level, config
                 );
             }
         }
@@ -488,7 +1415,7 @@ mod tests {
 
             for training_level in [TrainingLevel::StudentPilot, TrainingLevel::PrivatePilot, TrainingLevel::InstrumentRated] {
                 let mins = minimums_map.get(&training_level).unwrap();
-                let (is_safe, reason) = is_flight_safe(&training_level, &weather, mins);
+                let (is_safe, reason) = is_flight_safe_message(&training_level, &weather, mins, None, None);
 
                 prop_assert!(!is_safe, "Thunderstorms should always be unsafe for {:?}", training_level);
                 prop_assert!(reason.is_some(), "Unsafe weather should have a reason");
@@ -513,7 +1440,7 @@ mod tests {
 
             for training_level in [TrainingLevel::StudentPilot, TrainingLevel::PrivatePilot, TrainingLevel::InstrumentRated] {
                 let mins = minimums_map.get(&training_level).unwrap();
-                let (is_safe, _) = is_flight_safe(&training_level, &weather, mins);
+                let (is_safe, _) = is_flight_safe(&training_level, &weather, mins, None, None);
 
                 prop_assert!(!is_safe, "Zero visibility should always be unsafe for {:?}", training_level);
             }
@@ -0,0 +1,553 @@
+use crate::weather::api::{METERS_TO_MILES, MS_TO_KNOTS};
+use crate::weather::{derive_ceiling, CloudInfo, CloudLayer, WeatherData};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+
+/// Visibility used for P6SM, 9999 and CAVOK, matching the METAR "10+" reading
+const UNLIMITED_VISIBILITY_SM: f64 = 10.0;
+
+/// TAFs carry no hourly temperature, so hours use the standard atmosphere
+const STANDARD_TEMPERATURE_F: f64 = 59.0;
+
+/// Two-letter weather descriptor and phenomenon codes
+const WEATHER_CODES: [&str; 30] = [
+    "MI", "PR", "BC", "DR", "BL", "SH", "TS", "FZ", "DZ", "RA", "SN", "SG", "IC", "PL", "GR", "GS",
+    "UP", "BR", "FG", "FU", "VA", "DU", "SA", "HZ", "PY", "PO", "SQ", "FC", "SS", "DS",
+];
+
+/// How a TAF group changes the forecast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TafChange {
+    /// Conditions from the start of the validity period
+    Base,
+    /// FM: replaces the previous conditions entirely from this time
+    From,
+    /// BECMG: the listed elements change during the period and then persist
+    Becoming,
+    /// TEMPO or PROB: temporary fluctuations within the period
+    Temporary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TafWind {
+    /// `None` for variable (VRB) winds
+    pub direction_deg: Option<f64>,
+    pub speed_knots: f64,
+    pub gust_knots: Option<f64>,
+}
+
+/// Forecast elements of one group; `None` means the group doesn't mention it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TafConditions {
+    pub wind: Option<TafWind>,
+    pub visibility_miles: Option<f64>,
+    /// Forecast layers; empty for SKC, NSC or CAVOK
+    pub clouds: Option<Vec<CloudLayer>>,
+    pub weather: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TafGroup {
+    pub change: TafChange,
+    /// PROB30/PROB40 percentage
+    pub probability: Option<u8>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub conditions: TafConditions,
+}
+
+/// A parsed terminal aerodrome forecast
+#[derive(Debug, Clone, PartialEq)]
+pub struct Taf {
+    pub station: String,
+    pub issued: DateTime<Utc>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: DateTime<Utc>,
+    pub groups: Vec<TafGroup>,
+}
+
+impl Taf {
+    /// Parse a raw TAF. Times only carry the day of month, so they are
+    /// resolved to the month nearest `reference` (normally now).
+    pub fn parse(raw: &str, reference: DateTime<Utc>) -> Result<Self> {
+        let tokens = tokenize(raw);
+        let mut tokens = tokens.iter().map(String::as_str).peekable();
+
+        while let Some(&token) = tokens.peek() {
+            if matches!(token, "TAF" | "AMD" | "COR") {
+                tokens.next();
+            } else {
+                break;
+            }
+        }
+
+        let station = tokens.next().context("TAF is missing a station identifier")?.to_string();
+        let issued = tokens
+            .next()
+            .and_then(|t| parse_issue_time(t, reference))
+            .context("TAF is missing a valid issue time")?;
+        let (valid_from, valid_to) = tokens
+            .next()
+            .and_then(|t| parse_period(t, issued))
+            .context("TAF is missing a valid period")?;
+
+        let mut groups = Vec::new();
+        let mut current = TafGroup {
+            change: TafChange::Base,
+            probability: None,
+            from: valid_from,
+            to: valid_to,
+            conditions: TafConditions::default(),
+        };
+
+        while let Some(token) = tokens.next() {
+            let next_group = if let Some(time) = token.strip_prefix("FM").and_then(|t| parse_day_time(t, issued)) {
+                Some((TafChange::From, None, time, valid_to))
+            } else if token == "TEMPO" || token == "BECMG" {
+                let change = if token == "TEMPO" { TafChange::Temporary } else { TafChange::Becoming };
+                let (from, to) = tokens
+                    .next()
+                    .and_then(|t| parse_period(t, issued))
+                    .with_context(|| format!("{} group is missing its period", token))?;
+                Some((change, None, from, to))
+            } else if let Some(probability) = token.strip_prefix("PROB").and_then(|p| p.parse().ok()) {
+                if tokens.peek() == Some(&"TEMPO") {
+                    tokens.next();
+                }
+                let (from, to) = tokens
+                    .next()
+                    .and_then(|t| parse_period(t, issued))
+                    .context("PROB group is missing its period")?;
+                Some((TafChange::Temporary, Some(probability), from, to))
+            } else {
+                None
+            };
+
+            match next_group {
+                Some((change, probability, from, to)) => {
+                    let finished = std::mem::replace(
+                        &mut current,
+                        TafGroup { change, probability, from, to, conditions: TafConditions::default() },
+                    );
+                    groups.push(finished);
+                }
+                None => current.conditions.apply_token(token),
+            }
+        }
+        groups.push(current);
+
+        // Base and FM conditions last until the next FM group
+        let from_times: Vec<DateTime<Utc>> = groups
+            .iter()
+            .filter(|g| g.change == TafChange::From)
+            .map(|g| g.from)
+            .collect();
+        for group in groups.iter_mut().filter(|g| matches!(g.change, TafChange::Base | TafChange::From)) {
+            group.to = from_times
+                .iter()
+                .copied()
+                .find(|&t| t > group.from)
+                .unwrap_or(valid_to);
+        }
+
+        Ok(Self { station, issued, valid_from, valid_to, groups })
+    }
+
+    /// Expand the forecast into one-hour windows across the validity period;
+    /// each `WeatherData` covers `date_time` to `date_time + 1h`
+    pub fn hourly_weather(&self) -> Vec<WeatherData> {
+        let mut hours = Vec::new();
+        let mut time = self.valid_from;
+        while time < self.valid_to {
+            hours.push(self.weather_at(time));
+            time += Duration::hours(1);
+        }
+        hours
+    }
+
+    /// Conditions at `time`: the prevailing base/FM group plus any BECMG changes
+    /// that have started, with TEMPO/PROB groups applied as the worse case
+    pub fn weather_at(&self, time: DateTime<Utc>) -> WeatherData {
+        let mut conditions = TafConditions::default();
+
+        for group in self.groups.iter().filter(|g| g.from <= time) {
+            match group.change {
+                TafChange::Base | TafChange::From => conditions = group.conditions.clone(),
+                TafChange::Becoming => conditions.update(&group.conditions),
+                TafChange::Temporary => {}
+            }
+        }
+
+        for group in &self.groups {
+            if group.change == TafChange::Temporary && group.from <= time && time < group.to {
+                conditions.worsen(&group.conditions);
+            }
+        }
+
+        conditions.to_weather_data(time)
+    }
+}
+
+/// Hourly windows from the body of an Aviation Weather `/taf?format=raw`
+/// response, or `None` when the station has no TAF and the body is empty
+pub fn hourly_taf_windows(raw: &str, reference: DateTime<Utc>) -> Result<Option<Vec<WeatherData>>> {
+    if raw.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Taf::parse(raw, reference)?.hourly_weather()))
+}
+
+impl TafConditions {
+    fn apply_token(&mut self, token: &str) {
+        if let Some(wind) = parse_wind(token) {
+            self.wind = Some(wind);
+        } else if token == "CAVOK" {
+            self.visibility_miles = Some(UNLIMITED_VISIBILITY_SM);
+            self.clouds = Some(Vec::new());
+            self.weather = Some(Vec::new());
+        } else if let Some(visibility) = parse_visibility(token) {
+            self.visibility_miles = Some(visibility);
+        } else if matches!(token, "SKC" | "CLR" | "NSC" | "NCD") {
+            self.clouds.get_or_insert_with(Vec::new);
+        } else if let Some(layer) = parse_cloud_layer(token) {
+            self.clouds.get_or_insert_with(Vec::new).push(layer);
+        } else if token == "NSW" {
+            self.weather = Some(Vec::new());
+        } else if is_weather_token(token) {
+            self.weather.get_or_insert_with(Vec::new).push(token.to_string());
+        }
+        // Anything else (wind shear, TX/TN, QNH) doesn't affect flight minimums
+    }
+
+    /// BECMG: elements the group mentions replace the current ones
+    fn update(&mut self, changes: &TafConditions) {
+        if changes.wind.is_some() {
+            self.wind = changes.wind;
+        }
+        if changes.visibility_miles.is_some() {
+            self.visibility_miles = changes.visibility_miles;
+        }
+        if changes.clouds.is_some() {
+            self.clouds = changes.clouds.clone();
+        }
+        if changes.weather.is_some() {
+            self.weather = changes.weather.clone();
+        }
+    }
+
+    /// TEMPO/PROB: keep whichever of the current and temporary values is worse
+    fn worsen(&mut self, temporary: &TafConditions) {
+        if let Some(wind) = temporary.wind {
+            let current_peak = self.wind.map_or(0.0, |w| w.gust_knots.unwrap_or(w.speed_knots));
+            if wind.gust_knots.unwrap_or(wind.speed_knots) > current_peak {
+                self.wind = Some(wind);
+            }
+        }
+        if let Some(visibility) = temporary.visibility_miles {
+            self.visibility_miles = Some(self.visibility_miles.map_or(visibility, |v| v.min(visibility)));
+        }
+        // The ceiling is the lowest layer, so adding the temporary layers
+        // keeps whichever is lower
+        if let Some(clouds) = &temporary.clouds {
+            self.clouds.get_or_insert_with(Vec::new).extend(clouds.iter().cloned());
+        }
+        if let Some(weather) = &temporary.weather {
+            self.weather.get_or_insert_with(Vec::new).extend(weather.iter().cloned());
+        }
+    }
+
+    fn to_weather_data(&self, time: DateTime<Utc>) -> WeatherData {
+        let weather = self.weather.clone().unwrap_or_default();
+
+        WeatherData {
+            visibility_miles: self.visibility_miles.unwrap_or(UNLIMITED_VISIBILITY_SM),
+            wind_speed_knots: self.wind.map_or(0.0, |w| w.speed_knots),
+            wind_deg: self.wind.and_then(|w| w.direction_deg),
+            wind_gust_knots: self.wind.and_then(|w| w.gust_knots),
+            ceiling_ft: self
+                .clouds
+                .as_ref()
+                .and_then(|layers| derive_ceiling(&CloudInfo::from_layers(layers.clone()))),
+            temperature_f: STANDARD_TEMPERATURE_F,
+            conditions: if weather.is_empty() { "Clear".to_string() } else { weather.join(" ") },
+            has_thunderstorms: weather.iter().any(|w| w.contains("TS")),
+            has_icing: weather.iter().any(|w| w.contains("FZ") || w.contains("PL")),
+            date_time: time,
+            ..Default::default()
+        }
+    }
+}
+
+/// Split a TAF into tokens, dropping remarks and joining split visibilities
+/// such as "1 1/2SM"
+fn tokenize(raw: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+
+    for token in raw.split_whitespace() {
+        let token = token.trim_end_matches('=');
+        if token == "RMK" {
+            break;
+        }
+
+        let joins_whole_miles = token.contains('/')
+            && token.ends_with("SM")
+            && tokens.last().is_some_and(|t| t.len() == 1 && t.chars().all(|c| c.is_ascii_digit()));
+        if joins_whole_miles {
+            let whole = tokens.pop().unwrap_or_default();
+            tokens.push(format!("{} {}", whole, token));
+        } else if !token.is_empty() {
+            tokens.push(token.to_string());
+        }
+    }
+
+    tokens
+}
+
+/// "121130Z"
+fn parse_issue_time(token: &str, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    parse_day_time(token.strip_suffix('Z')?, reference)
+}
+
+/// "121600" (DDHHMM), as used by FM groups
+fn parse_day_time(token: &str, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if token.len() != 6 || !token.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    resolve_time(
+        token[0..2].parse().ok()?,
+        token[2..4].parse().ok()?,
+        token[4..6].parse().ok()?,
+        reference,
+    )
+}
+
+/// "1212/1318" (DDHH/DDHH)
+fn parse_period(token: &str, reference: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let (from, to) = token.split_once('/')?;
+    let parse = |part: &str, reference| {
+        if part.len() != 4 || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        resolve_time(part[0..2].parse().ok()?, part[2..4].parse().ok()?, 0, reference)
+    };
+
+    let from = parse(from, reference)?;
+    let to = parse(to, from)?;
+    (to > from).then_some((from, to))
+}
+
+/// Resolve a day-of-month time to the month nearest `reference`. Hour 24
+/// means midnight at the end of the day.
+fn resolve_time(day: u32, hour: u32, minute: u32, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (hour, extra_days) = if hour == 24 { (0, 1) } else { (hour, 0) };
+    let (year, month) = (reference.year(), reference.month());
+
+    [-1, 0, 1]
+        .into_iter()
+        .filter_map(|delta| {
+            let index = year * 12 + month as i32 - 1 + delta;
+            NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, day)
+        })
+        .filter_map(|date| date.and_hms_opt(hour, minute, 0))
+        .map(|naive| Utc.from_utc_datetime(&naive) + Duration::days(extra_days))
+        .min_by_key(|time| (*time - reference).num_seconds().abs())
+}
+
+/// "19010KT", "20015G25KT", "VRB03KT", "24008MPS"
+fn parse_wind(token: &str) -> Option<TafWind> {
+    let (body, to_knots) = if let Some(body) = token.strip_suffix("KT") {
+        (body, 1.0)
+    } else if let Some(body) = token.strip_suffix("MPS") {
+        (body, MS_TO_KNOTS)
+    } else {
+        return None;
+    };
+
+    if body.len() < 5 {
+        return None;
+    }
+    let (direction, speeds) = body.split_at(3);
+    let direction_deg = match direction {
+        "VRB" => None,
+        d => Some(d.parse::<f64>().ok()?),
+    };
+    let (speed, gust) = match speeds.split_once('G') {
+        Some((speed, gust)) => (speed, Some(gust)),
+        None => (speeds, None),
+    };
+
+    Some(TafWind {
+        direction_deg,
+        speed_knots: speed.parse::<f64>().ok()? * to_knots,
+        gust_knots: match gust {
+            Some(gust) => Some(gust.parse::<f64>().ok()? * to_knots),
+            None => None,
+        },
+    })
+}
+
+/// "P6SM", "3SM", "1 1/2SM", "M1/4SM", or four-digit metres ("9999", "0800")
+fn parse_visibility(token: &str) -> Option<f64> {
+    if token.len() == 4 && token.chars().all(|c| c.is_ascii_digit()) {
+        let meters: f64 = token.parse().ok()?;
+        return Some(if meters >= 9999.0 { UNLIMITED_VISIBILITY_SM } else { meters * METERS_TO_MILES });
+    }
+
+    let miles = token.strip_suffix("SM")?;
+    if miles.starts_with('P') {
+        return Some(UNLIMITED_VISIBILITY_SM);
+    }
+
+    miles
+        .trim_start_matches('M')
+        .split(' ')
+        .map(|part| match part.split_once('/') {
+            Some((numerator, denominator)) => {
+                Some(numerator.parse::<f64>().ok()? / denominator.parse::<f64>().ok()?)
+            }
+            None => part.parse::<f64>().ok(),
+        })
+        .sum()
+}
+
+/// "BKN025", "OVC008CB", "VV005"
+fn parse_cloud_layer(token: &str) -> Option<CloudLayer> {
+    let cover = ["FEW", "SCT", "BKN", "OVC", "VV"]
+        .into_iter()
+        .find(|cover| token.starts_with(cover))?;
+    let height = token[cover.len()..].get(0..3)?;
+    let hundreds: f64 = height.parse().ok()?;
+    Some(CloudLayer::new(cover, Some(hundreds * 100.0)))
+}
+
+/// "-SHRA", "+TSRA", "FZRA", "VCSH", "BR"
+fn is_weather_token(token: &str) -> bool {
+    let codes = token.trim_start_matches(['+', '-']);
+    let codes = codes.strip_prefix("VC").unwrap_or(codes);
+
+    let mut chunks = codes.as_bytes().chunks_exact(2);
+    let whole_codes = chunks.remainder().is_empty();
+
+    !codes.is_empty()
+        && whole_codes
+        && chunks.all(|code| WEATHER_CODES.iter().any(|known| known.as_bytes() == code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KJFK_TAF: &str = "TAF KJFK 121130Z 1212/1318 19010KT P6SM FEW050 \
+        FM121600 20015G25KT P6SM SCT040 BKN250 \
+        FM130000 22012KT P6SM BKN050 \
+        TEMPO 1302/1306 3SM -SHRA BKN025 \
+        FM131200 30010KT P6SM SCT060";
+
+    const EGLL_TAF: &str = "TAF EGLL 121059Z 1212/1318 24012KT 9999 SCT035 \
+        BECMG 1214/1217 28015G25KT \
+        PROB30 TEMPO 1218/1222 4000 TSRA BKN012CB \
+        BECMG 1302/1305 VRB03KT 6000 BR=";
+
+    const KORD_TAF: &str = "TAF AMD KORD 121520Z 1215/1318 03012KT 1 1/2SM -FZRA BR OVC008 \
+        FM122000 36015G22KT 3/4SM -SN VV005 RMK NXT FCST BY 18Z";
+
+    fn reference() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 12, 12, 0, 0).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_fm_and_tempo_groups() {
+        let taf = Taf::parse(KJFK_TAF, reference()).unwrap();
+
+        assert_eq!(taf.station, "KJFK");
+        assert_eq!(taf.issued, Utc.with_ymd_and_hms(2024, 1, 12, 11, 30, 0).unwrap());
+        assert_eq!((taf.valid_from, taf.valid_to), (at(12, 12), at(13, 18)));
+
+        let changes: Vec<TafChange> = taf.groups.iter().map(|g| g.change).collect();
+        assert_eq!(
+            changes,
+            vec![TafChange::Base, TafChange::From, TafChange::From, TafChange::Temporary, TafChange::From]
+        );
+        // FM groups run until the next FM group
+        assert_eq!((taf.groups[1].from, taf.groups[1].to), (at(12, 16), at(13, 0)));
+        assert_eq!(taf.groups[2].to, at(13, 12));
+        assert_eq!((taf.groups[3].from, taf.groups[3].to), (at(13, 2), at(13, 6)));
+
+        let afternoon = taf.weather_at(at(12, 18));
+        assert_eq!(afternoon.wind_speed_knots, 15.0);
+        assert_eq!(afternoon.wind_gust_knots, Some(25.0));
+        assert_eq!(afternoon.ceiling_ft, Some(25000.0));
+        assert_eq!(afternoon.visibility_miles, 10.0);
+
+        // TEMPO showers lower visibility and ceiling within their window only
+        let tempo = taf.weather_at(at(13, 3));
+        assert_eq!(tempo.visibility_miles, 3.0);
+        assert_eq!(tempo.ceiling_ft, Some(2500.0));
+        assert_eq!(tempo.conditions, "-SHRA");
+        let after_tempo = taf.weather_at(at(13, 7));
+        assert_eq!(after_tempo.ceiling_ft, Some(5000.0));
+        assert_eq!(after_tempo.conditions, "Clear");
+
+        let hours = taf.hourly_weather();
+        assert_eq!(hours.len(), 30);
+        assert_eq!(hours[0].date_time, at(12, 12));
+        assert_eq!(hours[0].ceiling_ft, None);
+    }
+
+    #[test]
+    fn test_parse_becmg_and_prob_groups() {
+        let taf = Taf::parse(EGLL_TAF, reference()).unwrap();
+
+        assert_eq!(taf.groups[2].probability, Some(30));
+        assert_eq!(taf.groups[2].change, TafChange::Temporary);
+
+        // BECMG changes the wind but keeps the base visibility and clouds
+        let becoming = taf.weather_at(at(12, 17));
+        assert_eq!(becoming.wind_deg, Some(280.0));
+        assert_eq!(becoming.wind_gust_knots, Some(25.0));
+        assert_eq!(becoming.visibility_miles, 10.0);
+        assert_eq!(becoming.ceiling_ft, None);
+
+        let storm = taf.weather_at(at(12, 20));
+        assert!(storm.has_thunderstorms);
+        assert_eq!(storm.ceiling_ft, Some(1200.0));
+        assert!((storm.visibility_miles - 4000.0 * METERS_TO_MILES).abs() < 1e-9);
+
+        let overnight = taf.weather_at(at(13, 6));
+        assert_eq!(overnight.wind_deg, None);
+        assert_eq!(overnight.wind_speed_knots, 3.0);
+        assert_eq!(overnight.conditions, "BR");
+        assert!(!overnight.has_thunderstorms);
+    }
+
+    #[test]
+    fn test_parse_fractional_visibility_and_freezing_rain() {
+        let taf = Taf::parse(KORD_TAF, reference()).unwrap();
+
+        assert_eq!(taf.station, "KORD");
+        let now = taf.weather_at(at(12, 15));
+        assert_eq!(now.visibility_miles, 1.5);
+        assert_eq!(now.ceiling_ft, Some(800.0));
+        assert!(now.has_icing);
+        assert_eq!(now.conditions, "-FZRA BR");
+
+        let snow = taf.weather_at(at(12, 22));
+        assert_eq!(snow.visibility_miles, 0.75);
+        assert_eq!(snow.ceiling_ft, Some(500.0));
+        assert_eq!(snow.wind_gust_knots, Some(22.0));
+        assert!(!snow.has_icing);
+    }
+
+    #[test]
+    fn test_times_resolve_across_month_end() {
+        let reference = Utc.with_ymd_and_hms(2024, 1, 31, 20, 0, 0).unwrap();
+        let (from, to) = parse_period("3118/0124", reference).unwrap();
+
+        assert_eq!(from, Utc.with_ymd_and_hms(2024, 1, 31, 18, 0, 0).unwrap());
+        assert_eq!(to, Utc.with_ymd_and_hms(2024, 2, 2, 0, 0, 0).unwrap());
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Unit system for weather values shown to users. Internally weather is
+/// always stored in aviation (US) units: °F, statute miles, knots and feet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    #[default]
+    Imperial,
+    Metric,
+}
+
+pub fn fahrenheit_to_celsius(fahrenheit: f64) -> f64 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Statute miles to kilometres
+pub fn miles_to_km(miles: f64) -> f64 {
+    miles * 1.609_344
+}
+
+pub fn knots_to_meters_per_second(knots: f64) -> f64 {
+    knots * 1852.0 / 3600.0
+}
+
+pub fn knots_to_kmh(knots: f64) -> f64 {
+    knots * 1.852
+}
+
+pub fn feet_to_meters(feet: f64) -> f64 {
+    feet * 0.3048
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_conversions() {
+        assert!((fahrenheit_to_celsius(68.0) - 20.0).abs() < 1e-9);
+        assert!((fahrenheit_to_celsius(32.0)).abs() < 1e-9);
+        assert!((celsius_to_fahrenheit(fahrenheit_to_celsius(59.0)) - 59.0).abs() < 1e-9);
+        assert!((miles_to_km(10.0) - 16.09344).abs() < 1e-9);
+        assert!((knots_to_meters_per_second(10.0) - 5.14444).abs() < 1e-4);
+        assert!((knots_to_kmh(10.0) - 18.52).abs() < 1e-9);
+        assert!((feet_to_meters(1000.0) - 304.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_units_default_to_imperial() {
+        assert_eq!(Units::default(), Units::Imperial);
+        assert_eq!(serde_json::from_str::<Units>("\"metric\"").unwrap(), Units::Metric);
+    }
+}
@@ -57,15 +57,21 @@ export class ApiMocks {
         await route.fulfill({
           status: 200,
           contentType: 'application/json',
-          body: JSON.stringify([
-            {
-              id: '1',
-              name: 'John Doe',
-              email: 'john.doe@example.com',
-              training_level: 'PRIVATE_PILOT',
-              phone: '+1-555-0123'
-            }
-          ])
+          body: JSON.stringify({
+            items: [
+              {
+                id: '1',
+                name: 'John Doe',
+                email: 'john.doe@example.com',
+                training_level: 'PRIVATE_PILOT',
+                phone: '+1-555-0123'
+              }
+            ],
+            page: 1,
+            limit: 50,
+            total: 1,
+            total_pages: 1
+          })
         });
       } else if (route.request().method() === 'POST') {
         await route.fulfill({
@@ -87,20 +93,26 @@ export class ApiMocks {
         await route.fulfill({
           status: 200,
           contentType: 'application/json',
-          body: JSON.stringify([
-            {
-              id: '1',
-              student_id: '1',
-              aircraft_type: 'Cessna 172',
-              scheduled_date: '2025-11-08T10:00:00Z',
-              departure_location: {
-                lat: 41.9786,
-                lon: -87.9048,
-                name: 'KORD'
-              },
-              status: 'Confirmed'
-            }
-          ])
+          body: JSON.stringify({
+            items: [
+              {
+                id: '1',
+                student_id: '1',
+                aircraft_type: 'Cessna 172',
+                scheduled_date: '2025-11-08T10:00:00Z',
+                departure_location: {
+                  lat: 41.9786,
+                  lon: -87.9048,
+                  name: 'KORD'
+                },
+                status: 'Confirmed'
+              }
+            ],
+            page: 1,
+            limit: 50,
+            total: 1,
+            total_pages: 1
+          })
         });
       } else if (route.request().method() === 'POST') {
         await route.fulfill({
@@ -90,6 +90,13 @@ csrfTokenDecoder =
     Decode.field "token" Decode.string
 
 
+{-| List endpoints wrap their results in a page envelope
+-}
+pageDecoder : Decoder a -> Decoder (List a)
+pageDecoder itemDecoder =
+    Decode.field "items" (Decode.list itemDecoder)
+
+
 getCsrfToken : (Result String String -> msg) -> Cmd msg
 getCsrfToken toMsg =
     Http.get
@@ -102,7 +109,7 @@ getBookings : (Result String (List Booking) -> msg) -> Cmd msg
 getBookings toMsg =
     Http.get
         { url = apiUrl "/bookings"
-        , expect = expectJson toMsg (Decode.list bookingDecoder)
+        , expect = expectJson toMsg (pageDecoder bookingDecoder)
         }
 
 
@@ -110,7 +117,7 @@ getStudents : (Result String (List Student) -> msg) -> Cmd msg
 getStudents toMsg =
     Http.get
         { url = apiUrl "/students"
-        , expect = expectJson toMsg (Decode.list studentDecoder)
+        , expect = expectJson toMsg (pageDecoder studentDecoder)
         }
 
 
@@ -0,0 +1,5 @@
+-- Add crosswind limit to weather minimums
+ALTER TABLE weather_minimums ADD COLUMN max_crosswind_kt REAL;
+
+UPDATE weather_minimums SET max_crosswind_kt = 10.0 WHERE training_level = 'STUDENT_PILOT';
+UPDATE weather_minimums SET max_crosswind_kt = 15.0 WHERE training_level = 'PRIVATE_PILOT';
@@ -0,0 +1,2 @@
+-- Allow gusts to exceed the sustained wind limit by a configurable margin
+ALTER TABLE weather_minimums ADD COLUMN gust_margin_kt REAL NOT NULL DEFAULT 5.0;
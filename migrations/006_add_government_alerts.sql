@@ -0,0 +1,5 @@
+-- Track the source event of government-issued alerts so they are stored once
+ALTER TABLE weather_alerts ADD COLUMN event TEXT;
+ALTER TABLE weather_alerts ADD COLUMN event_start TIMESTAMP;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_weather_alerts_event ON weather_alerts(event, event_start);
@@ -0,0 +1,8 @@
+-- Persist AI reschedule suggestions across restarts
+CREATE TABLE IF NOT EXISTS ai_reschedule_cache (
+    key TEXT PRIMARY KEY NOT NULL,
+    response TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_reschedule_cache_created_at ON ai_reschedule_cache(created_at);
@@ -0,0 +1,5 @@
+-- Density altitude limit, checked when the departure field elevation is known
+ALTER TABLE weather_minimums ADD COLUMN max_density_altitude_ft REAL;
+
+UPDATE weather_minimums SET max_density_altitude_ft = 6000.0 WHERE training_level = 'STUDENT_PILOT';
+UPDATE weather_minimums SET max_density_altitude_ft = 8000.0 WHERE training_level = 'PRIVATE_PILOT';
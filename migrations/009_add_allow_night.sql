@@ -0,0 +1,4 @@
+-- Whether flights may be scheduled between sunset and sunrise
+ALTER TABLE weather_minimums ADD COLUMN allow_night BOOLEAN NOT NULL DEFAULT 1;
+
+UPDATE weather_minimums SET allow_night = 0 WHERE training_level = 'STUDENT_PILOT';
@@ -0,0 +1,3 @@
+-- Free-text reason for each reschedule event, so scheduler cancellations and
+-- user reschedules record the same fields
+ALTER TABLE reschedule_events ADD COLUMN reason TEXT;
@@ -0,0 +1,18 @@
+-- Backfill reasons for events written before every path recorded one.
+-- Scheduler cancellations used suggested_by = 'SYSTEM'; user cancellations
+-- used 'USER' with an unchanged date.
+UPDATE reschedule_events
+SET reason = 'Weather conflict'
+WHERE reason IS NULL AND suggested_by = 'SYSTEM';
+
+UPDATE reschedule_events
+SET reason = 'User cancelled booking'
+WHERE reason IS NULL AND suggested_by = 'USER' AND original_date = new_date;
+
+UPDATE reschedule_events
+SET reason = 'User requested reschedule'
+WHERE reason IS NULL AND suggested_by = 'USER';
+
+UPDATE reschedule_events
+SET created_at = CURRENT_TIMESTAMP
+WHERE created_at IS NULL;
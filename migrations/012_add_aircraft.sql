@@ -0,0 +1,29 @@
+-- Fleet aircraft, so bookings can carry tail numbers and per-aircraft limits
+CREATE TABLE IF NOT EXISTS aircraft (
+    id TEXT PRIMARY KEY NOT NULL,
+    tail_number TEXT NOT NULL UNIQUE,
+    model TEXT NOT NULL,
+    max_demonstrated_crosswind_kt REAL,
+    ifr_capable BOOLEAN NOT NULL DEFAULT 0,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_aircraft_model ON aircraft(model);
+
+ALTER TABLE bookings ADD COLUMN aircraft_id TEXT REFERENCES aircraft(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_aircraft_id ON bookings(aircraft_id);
+
+-- Backfill one placeholder aircraft per free-text aircraft_type already booked.
+-- Tail numbers are unknown, so they are marked UNASSIGNED until edited.
+INSERT INTO aircraft (id, tail_number, model, max_demonstrated_crosswind_kt, ifr_capable)
+SELECT lower(hex(randomblob(16))), 'UNASSIGNED-' || aircraft_type, aircraft_type, NULL, 0
+FROM (SELECT DISTINCT aircraft_type FROM bookings);
+
+UPDATE bookings
+SET aircraft_id = (
+    SELECT id FROM aircraft
+    WHERE aircraft.tail_number = 'UNASSIGNED-' || bookings.aircraft_type
+)
+WHERE aircraft_id IS NULL;
@@ -0,0 +1,16 @@
+-- Instructors, so availability checks know whose schedule a booking is on
+CREATE TABLE IF NOT EXISTS instructors (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    certifications TEXT NOT NULL DEFAULT '[]', -- JSON array, e.g. ["CFI", "CFII"]
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_instructors_email ON instructors(email);
+
+-- Existing bookings have no instructor assigned
+ALTER TABLE bookings ADD COLUMN instructor_id TEXT REFERENCES instructors(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_instructor_date ON bookings(instructor_id, scheduled_date);
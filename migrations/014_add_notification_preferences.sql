@@ -0,0 +1,3 @@
+-- Per-student opt-out of cancellation emails and texts; everyone starts opted in
+ALTER TABLE students ADD COLUMN notify_email BOOLEAN NOT NULL DEFAULT 1;
+ALTER TABLE students ADD COLUMN notify_sms BOOLEAN NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Per-booking replacement for some of the training level's weather minimums,
+-- stored as JSON; NULL means the level's minimums apply unchanged
+ALTER TABLE bookings ADD COLUMN weather_minimum_override TEXT;
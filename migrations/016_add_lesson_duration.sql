@@ -0,0 +1,2 @@
+-- Length of each lesson, so overlap checks compare time intervals rather than start times
+ALTER TABLE bookings ADD COLUMN lesson_duration_minutes INTEGER NOT NULL DEFAULT 120;
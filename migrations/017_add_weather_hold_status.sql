@@ -0,0 +1,61 @@
+-- Allow the WEATHER_HOLD booking status. SQLite can't alter a CHECK
+-- constraint, so the bookings table is rebuilt.
+--
+-- Migrations run in a transaction, where foreign keys can't be switched off,
+-- so dropping the old table cascades to weather checks and reschedule events
+-- and unlinks weather alerts. Those rows are copied aside first and put back
+-- once the new table has taken the old one's name.
+CREATE TEMP TABLE weather_checks_backup AS SELECT * FROM weather_checks;
+CREATE TEMP TABLE reschedule_events_backup AS SELECT * FROM reschedule_events;
+CREATE TEMP TABLE weather_alert_bookings AS
+    SELECT id, booking_id FROM weather_alerts WHERE booking_id IS NOT NULL;
+
+CREATE TABLE bookings_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    student_id TEXT NOT NULL,
+    scheduled_date TIMESTAMP NOT NULL,
+    departure_location TEXT NOT NULL, -- JSON: {"lat": float, "lon": float, "name": string}
+    status TEXT NOT NULL CHECK (
+        status IN ('SCHEDULED', 'WEATHER_HOLD', 'CANCELLED', 'RESCHEDULED', 'COMPLETED')
+    ),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    aircraft_type TEXT NOT NULL DEFAULT 'Cessna 172',
+    aircraft_id TEXT REFERENCES aircraft(id) ON DELETE SET NULL,
+    instructor_id TEXT REFERENCES instructors(id) ON DELETE SET NULL,
+    weather_minimum_override TEXT,
+    lesson_duration_minutes INTEGER NOT NULL DEFAULT 120,
+    FOREIGN KEY (student_id) REFERENCES students(id) ON DELETE CASCADE
+);
+
+INSERT INTO bookings_new (
+    id, student_id, scheduled_date, departure_location, status, created_at, updated_at,
+    aircraft_type, aircraft_id, instructor_id, weather_minimum_override, lesson_duration_minutes
+)
+SELECT
+    id, student_id, scheduled_date, departure_location, status, created_at, updated_at,
+    aircraft_type, aircraft_id, instructor_id, weather_minimum_override, lesson_duration_minutes
+FROM bookings;
+
+DROP TABLE bookings;
+ALTER TABLE bookings_new RENAME TO bookings;
+
+CREATE INDEX idx_bookings_student_id ON bookings(student_id);
+CREATE INDEX idx_bookings_scheduled_date ON bookings(scheduled_date);
+CREATE INDEX idx_bookings_status ON bookings(status);
+CREATE INDEX idx_bookings_status_date ON bookings(status, scheduled_date);
+CREATE INDEX idx_bookings_aircraft_id ON bookings(aircraft_id);
+CREATE INDEX idx_bookings_instructor_date ON bookings(instructor_id, scheduled_date);
+
+INSERT OR IGNORE INTO weather_checks SELECT * FROM temp.weather_checks_backup;
+INSERT OR IGNORE INTO reschedule_events SELECT * FROM temp.reschedule_events_backup;
+UPDATE weather_alerts
+SET booking_id = (
+    SELECT booking_id FROM temp.weather_alert_bookings
+    WHERE temp.weather_alert_bookings.id = weather_alerts.id
+)
+WHERE id IN (SELECT id FROM temp.weather_alert_bookings);
+
+DROP TABLE temp.weather_checks_backup;
+DROP TABLE temp.reschedule_events_backup;
+DROP TABLE temp.weather_alert_bookings;
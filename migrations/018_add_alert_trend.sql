@@ -0,0 +1,2 @@
+-- Whether conditions were improving, steady or worsening toward the flight when the alert was raised
+ALTER TABLE weather_alerts ADD COLUMN trend TEXT;
@@ -0,0 +1,4 @@
+-- Deleting a student (and with it their bookings) only marks the rows, so
+-- booking history is retained for audit. created_at/updated_at already exist.
+ALTER TABLE students ADD COLUMN deleted_at TIMESTAMP;
+ALTER TABLE bookings ADD COLUMN deleted_at TIMESTAMP;
@@ -0,0 +1,11 @@
+-- Idempotency-Key values seen on booking creation, so a retried request
+-- returns the original booking instead of creating a duplicate
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key TEXT PRIMARY KEY NOT NULL,
+    request_hash TEXT NOT NULL, -- SHA-256 of the request body
+    booking_id TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (booking_id) REFERENCES bookings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
@@ -0,0 +1,9 @@
+-- Forecasts fetched from the weather API, reused across restarts and bursts
+-- of reschedule requests. Coordinates are rounded to 0.01 degrees (x100).
+CREATE TABLE IF NOT EXISTS forecast_cache (
+    lat_key INTEGER NOT NULL,
+    lon_key INTEGER NOT NULL,
+    forecast TEXT NOT NULL, -- JSON array of WeatherData
+    fetched_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (lat_key, lon_key)
+);
@@ -0,0 +1,15 @@
+-- Status changes that don't move a booking, such as a weather hold being
+-- placed or released, and a student's decision to keep a held booking.
+-- Reschedules stay in reschedule_events.
+CREATE TABLE IF NOT EXISTS booking_status_events (
+    id TEXT PRIMARY KEY NOT NULL,
+    booking_id TEXT NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    changed_by TEXT NOT NULL, -- SYSTEM or USER
+    reason TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (booking_id) REFERENCES bookings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_status_events_booking_id ON booking_status_events(booking_id);
@@ -0,0 +1,85 @@
+use axum::{
+    extract::{OriginalUri, Request},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+/// Current version of the REST API, served under `/api/v1`
+pub const API_VERSION: &str = "v1";
+
+/// Response header naming the API version that handled the request
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// Response header marking a request made through a deprecated path
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// `path` relative to the API prefix, whether it came in under `/api/v1` or
+/// the deprecated `/api` alias; other paths are returned unchanged
+pub fn unversioned_path(path: &str) -> &str {
+    let versioned = path
+        .strip_prefix("/api/")
+        .and_then(|rest| rest.strip_prefix(API_VERSION))
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'));
+    versioned
+        .or_else(|| path.strip_prefix("/api").filter(|rest| rest.is_empty() || rest.starts_with('/')))
+        .unwrap_or(path)
+}
+
+/// Middleware for the versioned routes: tags every response with the version
+pub async fn tag_version(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert(API_VERSION_HEADER, HeaderValue::from_static(API_VERSION));
+    response
+}
+
+/// Middleware for the unversioned `/api/*` alias
+///
+/// The request is served exactly as under `/api/v1`, but a warning is logged
+/// and the response carries `Deprecation: true` and a `Link` to the
+/// versioned path so clients can find their way over before the alias goes.
+pub async fn deprecated_alias(request: Request, next: Next) -> Response {
+    // Nesting strips the prefix from the request URI; log the path as sent
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    tracing::warn!(
+        "Deprecated unversioned API path {} {}; use /api/{} instead",
+        request.method(),
+        path,
+        API_VERSION
+    );
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION_HEADER, HeaderValue::from_static(API_VERSION));
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+    let successor = format!(
+        "</api/{}{}>; rel=\"successor-version\"",
+        API_VERSION,
+        unversioned_path(&path)
+    );
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(header::LINK, link);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_path_strips_either_prefix() {
+        assert_eq!(unversioned_path("/api/v1/weather/check"), "/weather/check");
+        assert_eq!(unversioned_path("/api/weather/check"), "/weather/check");
+        assert_eq!(unversioned_path("/api/v1"), "");
+        assert_eq!(unversioned_path("/api/v1x/students"), "/v1x/students");
+        assert_eq!(unversioned_path("/apiary"), "/apiary");
+        assert_eq!(unversioned_path("/health"), "/health");
+    }
+}
@@ -1,14 +1,105 @@
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    extract::{Request, State},
+    http::{Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
 
-/// Simple authentication middleware
-/// In production, replace with JWT validation or proper session management
+use crate::api_version::unversioned_path;
+
+/// Access level granted by an API key, ordered from least to most privileged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiRole {
+    ReadOnly,
+    Instructor,
+    Admin,
+}
+
+impl ApiRole {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "admin" => Some(ApiRole::Admin),
+            "instructor" => Some(ApiRole::Instructor),
+            "readonly" | "read_only" | "read-only" => Some(ApiRole::ReadOnly),
+            _ => None,
+        }
+    }
+
+    /// Whether this role grants at least the access of `required`
+    pub fn satisfies(self, required: ApiRole) -> bool {
+        self >= required
+    }
+
+    /// Read-only keys may only use safe methods (GET, HEAD, OPTIONS), plus
+    /// POSTs that compute a result without changing anything. The path may
+    /// carry the `/api/v1` prefix or the deprecated `/api` one.
+    pub fn permits(self, method: &Method, path: &str) -> bool {
+        method.is_safe()
+            || self != ApiRole::ReadOnly
+            || (method == Method::POST && READ_ONLY_POSTS.contains(&unversioned_path(path)))
+    }
+}
+
+/// POST endpoints that only read, open to read-only keys, relative to the
+/// API prefix
+const READ_ONLY_POSTS: [&str; 1] = ["/weather/check"];
+
+/// The API keys the server accepts, each with the role it grants
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys(Arc<Vec<(String, ApiRole)>>);
+
+impl ApiKeys {
+    pub fn new(keys: Vec<(String, ApiRole)>) -> Self {
+        Self(Arc::new(keys))
+    }
+
+    /// Keys from `API_KEYS` ("key:role,key:role"), plus the legacy single
+    /// `API_KEY`, which is treated as an admin key
+    pub fn from_env() -> Self {
+        let mut keys = std::env::var("API_KEYS")
+            .map(|spec| parse_api_keys(&spec))
+            .unwrap_or_default();
+
+        if let Ok(key) = std::env::var("API_KEY") {
+            if !key.is_empty() {
+                keys.push((key, ApiRole::Admin));
+            }
+        }
+
+        Self::new(keys)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Resolve a `Bearer <key>` header value to the role of the matching key
+    fn resolve(&self, token: &str) -> Option<ApiRole> {
+        let Some(bearer_token) = token.strip_prefix("Bearer ") else {
+            tracing::debug!("Token does not start with 'Bearer '");
+            return None;
+        };
+
+        // Compare against every configured key so the time taken doesn't reveal
+        // which key, if any, matched
+        self.0.iter().fold(None, |matched, (key, role)| {
+            if keys_match(bearer_token, key) {
+                Some(*role)
+            } else {
+                matched
+            }
+        })
+    }
+}
+
+/// Authentication middleware
+/// Resolves the bearer token to an `ApiRole` and stores it in the request
+/// extensions so handlers can extract it with `Extension<ApiRole>`
 pub async fn auth_middleware(
-    request: Request,
+    State(api_keys): State<ApiKeys>,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     tracing::debug!("Auth middleware: checking request to {}", request.uri());
@@ -21,40 +112,53 @@ pub async fn auth_middleware(
 
     tracing::debug!("Auth header present: {}", auth_header.is_some());
 
-    if let Some(token) = auth_header {
-        tracing::debug!("Auth header value: '{}'", token);
-        // Validate API key from environment
-        if validate_api_key(token) {
-            tracing::debug!("Auth successful");
-            return Ok(next.run(request).await);
-        } else {
-            tracing::debug!("Invalid API key provided: '{}'", token);
-        }
-    } else {
+    let Some(token) = auth_header else {
         tracing::debug!("No authorization header provided");
+        tracing::warn!("Unauthorized access attempt to {}", request.uri());
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let Some(role) = api_keys.resolve(token) else {
+        tracing::debug!("Invalid API key provided");
+        tracing::warn!("Unauthorized access attempt to {}", request.uri());
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    if !role.permits(request.method(), request.uri().path()) {
+        tracing::warn!(
+            "{:?} key attempted {} {}",
+            role,
+            request.method(),
+            request.uri()
+        );
+        return Err(StatusCode::FORBIDDEN);
     }
 
-    tracing::warn!("Unauthorized access attempt to {}", request.uri());
-    Err(StatusCode::UNAUTHORIZED)
+    tracing::debug!("Auth successful with role {:?}", role);
+    request.extensions_mut().insert(role);
+    Ok(next.run(request).await)
 }
 
-/// Validate API key from environment
-fn validate_api_key(token: &str) -> bool {
-    tracing::debug!("Validating token: '{}'", token);
-
-    if let Some(bearer_token) = token.strip_prefix("Bearer ") {
-        tracing::debug!("Extracted bearer token: '{}'", bearer_token);
-
-        // For development, accept the test key
-        let expected_key = "test-secure-api-key-12345";
-        tracing::debug!("Expected API key: '{}'", expected_key);
-        let valid = bearer_token == expected_key;
-        tracing::debug!("Token validation result: {}", valid);
-        return valid;
-    } else {
-        tracing::debug!("Token does not start with 'Bearer '");
-    }
-    false
+/// Constant-time key comparison that examines every byte rather than
+/// stopping at the first mismatch
+fn keys_match(provided: &str, expected: &str) -> bool {
+    provided.as_bytes().ct_eq(expected.as_bytes()).into()
+}
+
+fn parse_api_keys(spec: &str) -> Vec<(String, ApiRole)> {
+    spec.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .filter_map(|entry| {
+            let (key, role) = entry.trim().rsplit_once(':')?;
+            match ApiRole::parse(role) {
+                Some(role) if !key.is_empty() => Some((key.to_string(), role)),
+                _ => {
+                    tracing::warn!("Ignoring malformed API_KEYS entry");
+                    None
+                }
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -62,10 +166,50 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_validate_api_key() {
-        std::env::set_var("API_KEY", "test-secret-key");
-        assert!(validate_api_key("Bearer test-secret-key"));
-        assert!(!validate_api_key("Bearer wrong-key"));
-        assert!(!validate_api_key("test-secret-key")); // Missing Bearer prefix
+    fn test_resolve_api_key() {
+        let api_keys = ApiKeys::new(vec![
+            ("test-secret-key".to_string(), ApiRole::Admin),
+            ("dash-key".to_string(), ApiRole::ReadOnly),
+        ]);
+        assert_eq!(api_keys.resolve("Bearer test-secret-key"), Some(ApiRole::Admin));
+        assert_eq!(api_keys.resolve("Bearer dash-key"), Some(ApiRole::ReadOnly));
+        assert_eq!(api_keys.resolve("Bearer wrong-key"), None);
+        assert_eq!(api_keys.resolve("test-secret-key"), None); // Missing Bearer prefix
+    }
+
+    #[test]
+    fn test_keys_match() {
+        assert!(keys_match("test-secret-key", "test-secret-key"));
+        assert!(!keys_match("test-secret-kez", "test-secret-key"));
+        assert!(!keys_match("test-secret", "test-secret-key"));
+        assert!(!keys_match("", "test-secret-key"));
+    }
+
+    #[test]
+    fn test_parse_api_keys() {
+        let keys = parse_api_keys("ops-key:admin, cfi-key:instructor,dash-key:readonly,bad-entry,x:pilot");
+
+        assert_eq!(
+            keys,
+            vec![
+                ("ops-key".to_string(), ApiRole::Admin),
+                ("cfi-key".to_string(), ApiRole::Instructor),
+                ("dash-key".to_string(), ApiRole::ReadOnly),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_only_rejected_for_state_changes() {
+        assert!(ApiRole::ReadOnly.permits(&Method::GET, "/api/bookings"));
+        assert!(!ApiRole::ReadOnly.permits(&Method::POST, "/api/bookings"));
+        assert!(!ApiRole::ReadOnly.permits(&Method::PATCH, "/api/students/1"));
+        assert!(!ApiRole::ReadOnly.permits(&Method::DELETE, "/api/students/1"));
+        assert!(ApiRole::ReadOnly.permits(&Method::POST, "/api/weather/check"));
+        assert!(ApiRole::ReadOnly.permits(&Method::POST, "/api/v1/weather/check"));
+        assert!(!ApiRole::ReadOnly.permits(&Method::POST, "/api/v1/bookings"));
+        assert!(ApiRole::Instructor.permits(&Method::PATCH, "/api/students/1"));
+        assert!(ApiRole::Admin.satisfies(ApiRole::Instructor));
+        assert!(!ApiRole::Instructor.satisfies(ApiRole::Admin));
     }
 }
@@ -6,11 +6,77 @@ use axum::{
     Json,
 };
 use axum::http::header::{COOKIE, SET_COOKIE};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::OnceLock;
 use uuid::Uuid;
 
 const CSRF_COOKIE_NAME: &str = "csrf_token";
-const CSRF_HEADER_NAME: &str = "x-csrf-token";
+pub(crate) const CSRF_HEADER_NAME: &str = "x-csrf-token";
+
+/// Default token lifetime, overridable with CSRF_MAX_AGE_SECS
+const DEFAULT_CSRF_MAX_AGE_SECS: i64 = 3600;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Secret used to sign tokens. Taken from CSRF_SECRET, or generated once per
+/// process if unset (tokens then don't survive a restart)
+fn csrf_secret() -> &'static [u8] {
+    static SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+    SECRET.get_or_init(|| match std::env::var("CSRF_SECRET") {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => {
+            tracing::warn!("CSRF_SECRET not set, using a random per-process secret");
+            format!("{}{}", Uuid::new_v4(), Uuid::new_v4()).into_bytes()
+        }
+    })
+}
+
+fn csrf_max_age_secs() -> i64 {
+    std::env::var("CSRF_MAX_AGE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CSRF_MAX_AGE_SECS)
+}
+
+fn token_signature(secret: &[u8], nonce: &str, issued_at: i64) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", nonce, issued_at).as_bytes());
+    mac
+}
+
+/// Build a token of the form `nonce.issued_at.signature`
+fn sign_token(secret: &[u8], nonce: &str, issued_at: i64) -> String {
+    let signature = token_signature(secret, nonce, issued_at).finalize().into_bytes();
+    format!("{}.{}.{}", nonce, issued_at, hex::encode(signature))
+}
+
+/// Check a token's signature and that it was issued within `max_age_secs` of `now`
+fn verify_token(secret: &[u8], token: &str, now: i64, max_age_secs: i64) -> bool {
+    let mut parts = token.splitn(3, '.');
+    let (Some(nonce), Some(issued_at), Some(signature)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    let (Ok(issued_at), Ok(signature)) = (issued_at.parse::<i64>(), hex::decode(signature)) else {
+        return false;
+    };
+
+    // verify_slice compares in constant time
+    if token_signature(secret, nonce, issued_at).verify_slice(&signature).is_err() {
+        tracing::warn!("CSRF validation failed: bad signature");
+        return false;
+    }
+
+    let age = now - issued_at;
+    if !(0..=max_age_secs).contains(&age) {
+        tracing::warn!("CSRF validation failed: token expired");
+        return false;
+    }
+
+    true
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct CsrfToken {
@@ -19,13 +85,13 @@ pub struct CsrfToken {
 
 /// Generate a new CSRF token and return it with a Set-Cookie header
 pub async fn generate_csrf_token() -> impl IntoResponse {
-    let token = Uuid::new_v4().to_string();
+    let token = sign_token(csrf_secret(), &Uuid::new_v4().to_string(), Utc::now().timestamp());
 
     // Create secure cookie with SameSite=Strict
     // Note: HttpOnly is NOT set so JavaScript can read it for the header
     let cookie = format!(
-        "{}={}; Path=/; SameSite=Strict; Secure",
-        CSRF_COOKIE_NAME, token
+        "{}={}; Path=/; Max-Age={}; SameSite=Strict; Secure",
+        CSRF_COOKIE_NAME, token, csrf_max_age_secs()
     );
 
     let mut headers = HeaderMap::new();
@@ -42,7 +108,8 @@ pub async fn generate_csrf_token() -> impl IntoResponse {
 
 /// CSRF validation middleware
 /// Validates CSRF tokens for state-changing requests (POST, PATCH, PUT, DELETE)
-/// Extracts token from cookie and X-CSRF-Token header and compares them
+/// Extracts token from cookie and X-CSRF-Token header, requires them to match,
+/// and rejects tokens with a bad signature or older than CSRF_MAX_AGE_SECS
 pub async fn csrf_middleware(
     headers: HeaderMap,
     request: Request,
@@ -64,6 +131,9 @@ pub async fn csrf_middleware(
     // Both must be present and match
     match (cookie_token, header_token) {
         (Some(cookie), Some(header)) if cookie == header && !cookie.is_empty() => {
+            if !verify_token(csrf_secret(), &cookie, Utc::now().timestamp(), csrf_max_age_secs()) {
+                return Err(StatusCode::FORBIDDEN);
+            }
             tracing::debug!("CSRF token validated successfully");
             Ok(next.run(request).await)
         }
@@ -113,7 +183,48 @@ fn extract_csrf_from_header(headers: &HeaderMap) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::http::HeaderValue;
+    use axum::{body::Body, http::HeaderValue, middleware, routing::post, Router};
+    use tower::ServiceExt;
+
+    async fn post_with_token(token: &str) -> StatusCode {
+        let app = Router::new()
+            .route("/", post(|| async { "ok" }))
+            .route_layer(middleware::from_fn(csrf_middleware));
+
+        let request = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(COOKIE, format!("{}={}", CSRF_COOKIE_NAME, token))
+            .header(CSRF_HEADER_NAME, token)
+            .body(Body::empty())
+            .unwrap();
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_fresh_signed_token_accepted() {
+        let token = sign_token(csrf_secret(), "nonce", Utc::now().timestamp());
+        assert_eq!(post_with_token(&token).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_rejected() {
+        let issued_at = Utc::now().timestamp() - DEFAULT_CSRF_MAX_AGE_SECS - 60;
+        let token = sign_token(csrf_secret(), "nonce", issued_at);
+        assert_eq!(post_with_token(&token).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_token_rejected() {
+        let token = sign_token(csrf_secret(), "nonce", Utc::now().timestamp());
+        let tampered = token.replacen("nonce", "other", 1);
+        assert_eq!(post_with_token(&tampered).await, StatusCode::FORBIDDEN);
+
+        // An unsigned token like the old random UUIDs is also rejected
+        let unsigned = Uuid::new_v4().to_string();
+        assert_eq!(post_with_token(&unsigned).await, StatusCode::FORBIDDEN);
+    }
 
     #[test]
     fn test_extract_csrf_from_cookie() {
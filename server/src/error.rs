@@ -1,22 +1,31 @@
 use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use crate::telemetry::current_request_id;
+use weather_core::weather::WeatherError;
 use serde::Serialize;
+use utoipa::ToSchema;
 
 /// Standardized API error response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiError {
     pub error: ErrorDetails,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorDetails {
+    /// Machine-readable code, e.g. NOT_FOUND or VALIDATION_ERROR
     pub code: String,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Matches the response's `X-Request-Id`, filled in when the error is sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ApiError {
@@ -26,6 +35,7 @@ impl ApiError {
                 code: code.into(),
                 message: message.into(),
                 details: None,
+                request_id: None,
             },
         }
     }
@@ -40,6 +50,7 @@ impl ApiError {
                 code: code.into(),
                 message: message.into(),
                 details: Some(details.into()),
+                request_id: None,
             },
         }
     }
@@ -83,20 +94,39 @@ impl ApiError {
     pub fn conflict(message: impl Into<String>) -> Self {
         Self::new("CONFLICT", message)
     }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new("FORBIDDEN", message)
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new("PAYLOAD_TOO_LARGE", message)
+    }
+
+    pub fn service_unavailable(message: impl Into<String>) -> Self {
+        Self::new("SERVICE_UNAVAILABLE", message)
+    }
 }
 
 /// Convert ApiError to HTTP response
 impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+    fn into_response(mut self) -> Response {
         let status = match self.error.code.as_str() {
             "NOT_FOUND" => StatusCode::NOT_FOUND,
             "BAD_REQUEST" => StatusCode::BAD_REQUEST,
             "VALIDATION_ERROR" => StatusCode::BAD_REQUEST,
             "CONFLICT" => StatusCode::CONFLICT,
+            "FORBIDDEN" => StatusCode::FORBIDDEN,
+            "PAYLOAD_TOO_LARGE" => StatusCode::PAYLOAD_TOO_LARGE,
             "EXTERNAL_API_ERROR" => StatusCode::BAD_GATEWAY,
+            "SERVICE_UNAVAILABLE" => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
+        if self.error.request_id.is_none() {
+            self.error.request_id = current_request_id();
+        }
+
         (status, Json(self)).into_response()
     }
 }
@@ -127,6 +157,39 @@ impl From<serde_json::Error> for ApiError {
     }
 }
 
+/// Malformed or oversized request bodies: syntax errors and a missing JSON
+/// content type are bad requests, well-formed JSON of the wrong shape is a
+/// validation error
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(e) => ApiError::validation_error(e.body_text()),
+            JsonRejection::JsonSyntaxError(e) => ApiError::bad_request(e.body_text()),
+            rejection if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                ApiError::payload_too_large(rejection.body_text())
+            }
+            rejection => ApiError::bad_request(rejection.body_text()),
+        }
+    }
+}
+
+/// `Json` extractor whose rejections use the standard error envelope
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(ApiJson(value))
+    }
+}
+
 impl From<chrono::ParseError> for ApiError {
     fn from(err: chrono::ParseError) -> Self {
         ApiError::validation_error(format!("Invalid date format: {}", err))
@@ -139,9 +202,28 @@ impl From<uuid::Error> for ApiError {
     }
 }
 
+/// A rejected API key is our misconfiguration, not the client's problem, so it
+/// surfaces as an internal error; a rate limit is temporary (503)
+impl From<WeatherError> for ApiError {
+    fn from(err: WeatherError) -> Self {
+        match err {
+            WeatherError::AuthFailed => {
+                tracing::error!("Weather API rejected the API key; check WEATHER_API_KEY");
+                ApiError::internal_error("Weather service is not configured correctly")
+            }
+            WeatherError::RateLimited => {
+                ApiError::service_unavailable("Weather provider rate limit reached, try again shortly")
+            }
+            WeatherError::NotFound => ApiError::not_found("Weather for this location"),
+            err => ApiError::external_api_error("OpenWeatherMap", err.to_string()),
+        }
+    }
+}
+
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
-        // Log detailed error for server-side debugging
+        // Log detailed error for server-side debugging; inside a request this
+        // is within its span, so the line carries the request ID
         tracing::error!("Internal error occurred: {:?}", err);
         // Return generic message to client to avoid information disclosure
         ApiError::internal_error("An unexpected internal error occurred")
@@ -160,6 +242,66 @@ mod tests {
         assert!(json.contains("Booking not found"));
     }
 
+    async fn post_json(body: impl Into<String>) -> (StatusCode, serde_json::Value) {
+        use axum::{extract::DefaultBodyLimit, routing::post, Router};
+        use tower::ServiceExt;
+
+        #[derive(serde::Deserialize)]
+        struct Payload {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        let app = Router::new()
+            .route("/", post(|ApiJson(_): ApiJson<Payload>| async { StatusCode::OK }))
+            .layer(DefaultBodyLimit::max(64));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::post("/")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(body.into()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_uses_error_envelope() {
+        let (status, json) = post_json("{\"name\": ").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "BAD_REQUEST");
+
+        let (status, json) = post_json("{\"name\": 42}").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "VALIDATION_ERROR");
+        assert!(json["error"]["message"].as_str().unwrap().contains("name"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_uses_error_envelope() {
+        let (status, json) = post_json(format!("{{\"name\": \"{}\"}}", "x".repeat(100))).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(json["error"]["code"], "PAYLOAD_TOO_LARGE");
+    }
+
+    #[test]
+    fn test_weather_auth_and_rate_limit_mapping() {
+        let auth = ApiError::from(WeatherError::AuthFailed).into_response();
+        assert_eq!(auth.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let rate_limited = ApiError::from(WeatherError::RateLimited).into_response();
+        assert_eq!(rate_limited.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let unparseable = ApiError::from(WeatherError::Parse("missing field `main`".to_string()));
+        assert_eq!(unparseable.error.code, "EXTERNAL_API_ERROR");
+    }
+
     #[test]
     fn test_error_with_details() {
         let error = ApiError::database_error("Connection timeout");
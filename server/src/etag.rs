@@ -0,0 +1,163 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+/// Hex digits of the body hash kept in the tag
+const ETAG_HASH_LEN: usize = 16;
+
+/// Headers describing the body, dropped from a 304
+const BODY_HEADERS: [header::HeaderName; 3] = [header::CONTENT_TYPE, header::CONTENT_LENGTH, header::CONTENT_ENCODING];
+
+/// Middleware for conditional GETs on polled read endpoints
+///
+/// Successful responses get a weak `ETag` computed from the serialized body.
+/// When the request's `If-None-Match` already names that tag, the body is
+/// dropped and a 304 is returned instead. The handler still runs, so this
+/// saves bandwidth rather than database work.
+pub async fn conditional_get(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to buffer response body for ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = weak_etag(&bytes);
+    if if_none_match.as_ref().is_some_and(|value| matches_etag(value, &etag)) {
+        // Keep headers such as Cache-Control and Vary, but none describing
+        // the body that is no longer sent
+        for name in BODY_HEADERS {
+            parts.headers.remove(name);
+        }
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.insert(header::ETAG, etag);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    parts.headers.insert(header::ETAG, etag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+fn weak_etag(body: &[u8]) -> HeaderValue {
+    let hash = hex::encode(Sha256::digest(body));
+    HeaderValue::from_str(&format!("W/\"{}\"", &hash[..ETAG_HASH_LEN]))
+        .expect("hex digest is a valid header value")
+}
+
+/// Weak comparison against a comma-separated `If-None-Match` list
+fn matches_etag(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(candidates) = if_none_match.to_str() else {
+        return false;
+    };
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+
+    candidates
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == opaque(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ApiRole;
+    use crate::test_support::{setup_test_db, test_state};
+    use axum::{middleware, routing::get, Json, Router};
+    use tower::ServiceExt;
+    use weather_core::weather::MockWeatherClient;
+
+    async fn get_with(app: Router, if_none_match: Option<&HeaderValue>) -> Response {
+        let mut request = axum::http::Request::get("/");
+        if let Some(value) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repeat_request_with_etag_gets_not_modified() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    (
+                        [(header::CACHE_CONTROL, "no-cache")],
+                        Json(serde_json::json!({ "alerts": [1, 2, 3] })),
+                    )
+                }),
+            )
+            .layer(middleware::from_fn(conditional_get));
+
+        let first = get_with(app.clone(), None).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(header::ETAG).cloned().unwrap();
+        assert!(etag.to_str().unwrap().starts_with("W/\""));
+
+        let repeat = get_with(app.clone(), Some(&etag)).await;
+        assert_eq!(repeat.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(repeat.headers().get(header::ETAG), Some(&etag));
+        assert_eq!(repeat.headers()[header::CACHE_CONTROL], "no-cache");
+        assert!(repeat.headers().get(header::CONTENT_TYPE).is_none());
+        let body = axum::body::to_bytes(repeat.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        let stale = HeaderValue::from_static("W/\"0000000000000000\"");
+        let changed = get_with(app, Some(&stale)).await;
+        assert_eq!(changed.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(changed.into_body(), usize::MAX).await.unwrap();
+        assert!(!body.is_empty());
+    }
+    #[tokio::test]
+    async fn test_polled_api_routes_answer_not_modified() {
+        let weather = weather_core::weather::WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots: 5.0,
+            ceiling_ft: Some(5000.0),
+            temperature_f: 70.0,
+            conditions: "Clear".to_string(),
+            date_time: chrono::Utc::now(),
+            ..Default::default()
+        };
+        let state = test_state(setup_test_db().await, MockWeatherClient::new().with_current(weather));
+        let app = crate::api_routes()
+            .layer(axum::Extension(ApiRole::ReadOnly))
+            .with_state(state);
+        let get = |uri: &str, if_none_match: Option<&HeaderValue>| {
+            let mut request = axum::http::Request::get(uri);
+            if let Some(value) = if_none_match {
+                request = request.header(header::IF_NONE_MATCH, value);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        for uri in ["/api/v1/alerts", "/api/v1/bookings", "/api/v1/weather?lat=33.81&lon=-118.15"] {
+            let first = get(uri, None).await.unwrap();
+            assert_eq!(first.status(), StatusCode::OK, "{}", uri);
+            let etag = first.headers().get(header::ETAG).cloned().unwrap();
+
+            let repeat = get(uri, Some(&etag)).await.unwrap();
+            assert_eq!(repeat.status(), StatusCode::NOT_MODIFIED, "{}", uri);
+            assert_eq!(repeat.headers().get(header::ETAG), Some(&etag));
+            assert!(repeat.headers().get(header::CONTENT_TYPE).is_none(), "{}", uri);
+        }
+    }
+}
@@ -1,18 +1,22 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
-    routing::{get, patch, post},
+    routing::{delete, get, patch, post},
     Router,
 };
-use core::ai::{AiCache, AiRescheduleClient};
-use core::weather::api::WeatherClient;
+use weather_core::ai::{ai_cache_ttl_hours_from_env, AiCacheStore, AiRescheduleClient, SqliteAiCache};
+use weather_core::notifications::{create_email_provider, create_sms_provider};
+use weather_core::weather::{MetarClient, SqliteForecastCache, WeatherClient, WeatherSource};
 use dotenv::dotenv;
+use metrics_exporter_prometheus::PrometheusHandle;
 use sqlx::sqlite::SqlitePool;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
-use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::services::ServeDir;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use tower_governor::{
@@ -20,12 +24,21 @@ use tower_governor::{
     GovernorLayer,
 };
 
+mod api_version;
 mod auth;
 mod csrf;
 mod error;
+mod etag;
+mod openapi;
+mod rate_limit;
 mod routes;
 mod scheduler;
+mod seed;
+mod telemetry;
+#[cfg(test)]
+pub(crate) mod test_support;
 
+use rate_limit::{ClientIpKeyExtractor, RateLimitSettings};
 use routes::websocket;
 
 pub type NotificationChannel = broadcast::Sender<String>;
@@ -35,7 +48,7 @@ pub struct AppState {
     pub db: SqlitePool,
     pub notification_tx: NotificationChannel,
     pub ai_client: Arc<AiRescheduleClient>,
-    pub weather_client: Arc<WeatherClient>,
+    pub weather_client: Arc<dyn WeatherSource>,
 }
 
 #[tokio::main]
@@ -47,13 +60,6 @@ async fn main() -> anyhow::Result<()> {
         dotenv().ok();
     }
 
-    // Debug: Check API_KEY loading
-    if let Ok(api_key) = std::env::var("API_KEY") {
-        tracing::debug!("API_KEY loaded from environment: '{}'", api_key);
-    } else {
-        tracing::error!("API_KEY not found in environment variables!");
-    }
-
     // Initialize tracing
     tracing_subscriber::registry()
         .with(
@@ -65,6 +71,15 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting Weather Event Server...");
 
+    // Only whether keys are configured is logged, never the keys themselves
+    let api_keys = auth::ApiKeys::from_env();
+    if api_keys.is_empty() {
+        tracing::error!("Neither API_KEY nor API_KEYS is set; authenticated routes will reject every request");
+    }
+
+    // Prometheus recorder backing the /metrics endpoint
+    let metrics_handle = telemetry::install_recorder()?;
+
     // Database connection
     let database_url = std::env::var("DATABASE_URL")
         .unwrap_or_else(|_| {
@@ -99,11 +114,20 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Database migrations completed");
 
+    // `--seed` fills an empty database with demo data and exits
+    if std::env::args().any(|arg| arg == "--seed") {
+        seed::seed(&db).await?;
+        return Ok(());
+    }
+
     // Create notification channel
     let (notification_tx, _) = broadcast::channel::<String>(100);
 
     // Initialize AI client
-    let ai_cache = Arc::new(AiCache::new());
+    // AI suggestions are cached in the database so they survive restarts
+    let ai_cache_ttl_hours = ai_cache_ttl_hours_from_env();
+    tracing::info!("AI suggestions are cached for {} hours", ai_cache_ttl_hours);
+    let ai_cache: Arc<dyn AiCacheStore> = Arc::new(SqliteAiCache::with_ttl(db.clone(), ai_cache_ttl_hours));
     let ai_client = Arc::new(
         AiRescheduleClient::from_env(ai_cache.clone())
             .map_err(|e| {
@@ -112,166 +136,368 @@ async fn main() -> anyhow::Result<()> {
             })
             .unwrap_or_else(|_| {
                 // Fallback: create client with dummy key (will always use fallback logic)
-                AiRescheduleClient::new("dummy_key".to_string(), Arc::new(AiCache::new()))
+                AiRescheduleClient::new("dummy_key".to_string(), ai_cache.clone()).with_options_from_env()
             })
     );
+    if !ai_client.is_configured() {
+        tracing::warn!("No AI API key configured; reschedule suggestions will be rule-based");
+    }
 
-    // Initialize weather client
-    let weather_client = Arc::new(
-        WeatherClient::from_env()
-            .map_err(|e| {
-                tracing::error!("Failed to initialize weather client: {}. Using fallback.", e);
-                e
-            })
-            .unwrap_or_else(|_| {
-                tracing::warn!("Using fallback WeatherClient with empty key");
-                // Fallback: create client with empty key
-                WeatherClient::new(String::new(), None)
-            })
-    );
+    // Initialize weather client (WEATHER_SOURCE=metar selects NOAA Aviation Weather Center)
+    let weather_client: Arc<dyn WeatherSource> = match std::env::var("WEATHER_SOURCE").as_deref() {
+        Ok("metar") => {
+            tracing::info!("Using NOAA METAR weather source");
+            Arc::new(MetarClient::from_env())
+        }
+        _ => Arc::new(
+            WeatherClient::from_env()
+                .map_err(|e| {
+                    tracing::error!("Failed to initialize weather client: {}. Using fallback.", e);
+                    e
+                })
+                .unwrap_or_else(|_| {
+                    tracing::warn!("Using fallback WeatherClient with empty key");
+                    // Fallback: create client with empty key
+                    WeatherClient::new(String::new(), None)
+                })
+                .with_forecast_cache(SqliteForecastCache::new(db.clone()))
+        ),
+    };
+
+    // Cancelled on SIGTERM/ctrl-c to stop background tasks
+    let shutdown = CancellationToken::new();
 
     // Spawn cache cleanup task
     let cache_clone = ai_cache.clone();
-    tokio::spawn(async move {
+    let weather_cache_client = weather_client.clone();
+    let cache_shutdown = shutdown.clone();
+    let cache_task = tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(3600)); // Every hour
         loop {
-            interval.tick().await;
-            cache_clone.clear_expired().await;
-            tracing::info!("Cleared expired AI cache entries");
+            tokio::select! {
+                _ = interval.tick() => {
+                    cache_clone.clear_expired().await;
+                    weather_cache_client.clear_expired().await;
+                    tracing::info!("Cleared expired AI and weather cache entries");
+                }
+                _ = cache_shutdown.cancelled() => break,
+            }
         }
+        tracing::info!("Cache cleanup task stopped");
     });
 
     // Create app state
     let state = AppState {
         db: db.clone(),
         notification_tx: notification_tx.clone(),
-        ai_client,
+        ai_client: ai_client.clone(),
         weather_client,
     };
 
     // Configure CORS - SECURITY: No wildcard origins allowed
-    let cors = if let Ok(origins_str) = std::env::var("ALLOWED_ORIGINS") {
-        let origins: Vec<_> = origins_str
-            .split(',')
-            .filter_map(|s| {
-                let trimmed = s.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    trimmed.parse().ok()
-                }
-            })
-            .collect();
-
-        if origins.is_empty() {
-            tracing::error!("ALLOWED_ORIGINS is set but contains no valid origins");
-            panic!("FATAL: ALLOWED_ORIGINS environment variable contains no valid origins");
+    let origins = match std::env::var("ALLOWED_ORIGINS") {
+        Ok(origins_str) => {
+            let origins = parse_allowed_origins(&origins_str)?;
+            tracing::info!("CORS configured with {} allowed origin(s): {:?}", origins.len(), origins);
+            origins
+        }
+        Err(_) => {
+            // Development fallback: restrictive default
+            tracing::warn!("ALLOWED_ORIGINS not set, using default (http://localhost:8000)");
+            vec![axum::http::HeaderValue::from_static("http://localhost:8000")]
         }
-
-        tracing::info!("CORS configured with {} allowed origin(s): {:?}", origins.len(), origins);
-
-        CorsLayer::new()
-            .allow_origin(origins)
-            .allow_methods([axum::http::Method::GET, axum::http::Method::POST, axum::http::Method::PATCH])
-            .allow_headers([axum::http::header::CONTENT_TYPE, axum::http::HeaderName::from_static("x-csrf-token")])
-            .allow_credentials(true)
-    } else {
-        // Development fallback: restrictive default
-        tracing::warn!("ALLOWED_ORIGINS not set, using default (http://localhost:8000)");
-        let origins = vec!["http://localhost:8000".parse().unwrap()];
-        CorsLayer::new()
-            .allow_origin(origins)
-            .allow_methods([axum::http::Method::GET, axum::http::Method::POST, axum::http::Method::PATCH])
-            .allow_headers([axum::http::header::CONTENT_TYPE, axum::http::HeaderName::from_static("x-csrf-token")])
-            .allow_credentials(true)
     };
+    let cors = cors_layer(origins);
+
+    // Configure per-IP rate limiting
+    let rate_limit = RateLimitSettings::from_env();
+    tracing::info!(
+        "Rate limiting: burst {}, {} requests/second sustained, trust proxy: {}",
+        rate_limit.burst_size,
+        rate_limit.per_second,
+        rate_limit.trust_proxy
+    );
+    let app = build_app(state.clone(), api_keys, cors, &rate_limit, metrics_handle);
+
+    // Validate job schedules and the listen address before starting anything
+    // in the background
+    let schedule = scheduler::ScheduleConfig::from_env()?;
+    let addr = bind_addr_from_env()?;
+
+    // Start background scheduler with the same weather source as the API
+    let scheduler_shutdown = shutdown.clone();
+    let scheduler_task = tokio::spawn(async move {
+        if let Err(e) = scheduler::start_weather_monitor(
+            state,
+            Arc::from(create_email_provider()),
+            Arc::from(create_sms_provider()),
+            schedule,
+            scheduler_shutdown,
+        )
+        .await
+        {
+            tracing::error!("Scheduler error: {}", e);
+        }
+    });
+
+    // Start server
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind {}: {}", addr, e))?;
+    tracing::info!("Server listening on {}", addr);
+    // Connect info supplies the peer address used as the rate limit key
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal(shutdown.clone()))
+        .await?;
+    tracing::info!("HTTP server stopped, waiting for background tasks...");
+
+    shutdown.cancel();
+    if let Err(e) = scheduler_task.await {
+        tracing::error!("Scheduler task failed during shutdown: {}", e);
+    }
+    if let Err(e) = cache_task.await {
+        tracing::error!("Cache cleanup task failed during shutdown: {}", e);
+    }
 
-    // Configure rate limiting
+    tracing::info!("Closing database pool...");
+    db.close().await;
+    tracing::info!("Shutdown complete");
+
+    Ok(())
+}
+
+/// The whole HTTP app: public probes and metrics, the authenticated API and
+/// WebSocket, and the layers every request passes through
+fn build_app(
+    state: AppState,
+    api_keys: auth::ApiKeys,
+    cors: CorsLayer,
+    rate_limit: &RateLimitSettings,
+    metrics_handle: PrometheusHandle,
+) -> Router {
     let governor_conf = Box::new(
         GovernorConfigBuilder::default()
-            .per_second(10)
-            .burst_size(50)
+            .per_millisecond(rate_limit.replenish_interval_ms())
+            .burst_size(rate_limit.burst_size)
+            .key_extractor(ClientIpKeyExtractor::new(rate_limit.trust_proxy))
             .finish()
             .unwrap(),
     );
-
-    // Build protected API routes with authentication, CSRF protection, and rate limiting
-    let api_routes = Router::new()
-        .route("/test", get(|| async { "test response" }))
-        .route("/alerts", get(routes::alerts::list_alerts))
-        .route("/bookings", get(routes::bookings::list_bookings))
-        .route("/bookings", post(routes::bookings::create_booking))
-        .route("/bookings/:id", get(routes::bookings::get_booking))
-        .route("/bookings/:id/reschedule-suggestions", get(routes::bookings::get_reschedule_suggestions))
-        .route("/bookings/:id/reschedule", patch(routes::bookings::reschedule_booking))
-        .route("/students", get(routes::students::list_students))
-        .route("/students", post(routes::students::create_student))
-        .route("/weather", get(routes::weather::get_weather))
-        // .route_layer(middleware::from_fn(csrf::csrf_middleware))
-        // .route_layer(middleware::from_fn(auth::auth_middleware))
-        .layer(GovernorLayer {
-            config: Box::leak(governor_conf),
-        });
+    let governor_layer = GovernorLayer {
+        config: Box::leak(governor_conf),
+    };
 
     // Build protected WebSocket route
     let ws_route = Router::new()
         .route("/ws", get(websocket::ws_handler))
-        .route_layer(middleware::from_fn(auth::auth_middleware));
+        .route_layer(middleware::from_fn_with_state(api_keys.clone(), auth::auth_middleware));
 
-    // Build main router
-    let app = Router::new()
+    Router::new()
         // Health check (public)
         .route("/health", get(health_check))
-        // CSRF token endpoint (public)
-        .route("/api/csrf-token", get(csrf::generate_csrf_token))
+        // Readiness probe checking the database and weather API (public)
+        .route("/health/ready", get(routes::health::readiness_check))
+        // Prometheus metrics (public, like /health)
+        .route("/metrics", get(move || std::future::ready(metrics_handle.render())))
         // Test route
         .route("/api/test", get(test_handler))
         .layer(axum::middleware::from_fn(|req: axum::http::Request<axum::body::Body>, next: axum::middleware::Next| async {
             tracing::debug!("Request: {} {}", req.method(), req.uri());
             next.run(req).await
         }))
-        // API routes (not nested for now)
-        .route("/api/alerts", get(routes::alerts::list_alerts))
-        .route("/api/bookings", get(routes::bookings::list_bookings))
-        .route("/api/bookings", post(routes::bookings::create_booking))
-        .route("/api/bookings/:id", get(routes::bookings::get_booking))
-        .route("/api/bookings/:id/reschedule-suggestions", get(routes::bookings::get_reschedule_suggestions))
-        .route("/api/bookings/:id/reschedule", patch(routes::bookings::reschedule_booking))
-        .route("/api/students", get(routes::students::list_students))
-        .route("/api/students", post(routes::students::create_student))
-        .route("/api/weather", get(routes::weather::get_weather))
-        .route_layer(middleware::from_fn(auth::auth_middleware))
-        .layer(GovernorLayer {
-            config: Box::leak(governor_conf),
-        })
+        // API routes under /api/v1, plus the deprecated unversioned alias
+        .merge(api_routes())
+        .route_layer(middleware::from_fn_with_state(api_keys, auth::auth_middleware))
+        .layer(governor_layer)
+        // gzip/deflate per Accept-Encoding; added before the WebSocket route
+        // is merged so the upgrade is left alone
+        .layer(CompressionLayer::new())
         // Protected WebSocket
         .merge(ws_route)
         // Static files (for Elm frontend)
         // .fallback_service(ServeDir::new("dist").not_found_service(get(routes::serve_spa)))
         // CORS
         .layer(cors)
-        // Request body size limit (1MB)
-        .layer(RequestBodyLimitLayer::new(1024 * 1024))
-        // State
-        .with_state(state);
-
-    // Start background scheduler
-    let scheduler_db = db.clone();
-    let scheduler_tx = notification_tx.clone();
-    tokio::spawn(async move {
-        if let Err(e) = scheduler::start_weather_monitor(scheduler_db, scheduler_tx).await {
-            tracing::error!("Scheduler error: {}", e);
+        // Request body size limit (1MB); oversized JSON bodies get a 413 error envelope
+        .layer(DefaultBodyLimit::max(1024 * 1024))
+        // Request count and latency per route
+        .layer(middleware::from_fn(telemetry::track_metrics))
+        // Correlation ID for logs, error bodies and the X-Request-Id header
+        .layer(middleware::from_fn(telemetry::propagate_request_id))
+        .with_state(state)
+}
+
+/// The REST API, served under `/api/v1` and, for clients that predate
+/// versioning, under `/api` with deprecation headers
+fn api_routes() -> Router<AppState> {
+    let routes = || {
+        Router::new()
+            // CSRF token endpoint (public)
+            .route("/csrf-token", get(csrf::generate_csrf_token))
+            .route("/openapi.json", get(openapi::openapi_json))
+            .route("/alerts", get(routes::alerts::list_alerts).layer(middleware::from_fn(etag::conditional_get)))
+            .route("/alerts/:id/dismiss", post(routes::alerts::dismiss_alert))
+            .route("/admin/cleanup", post(routes::admin::cleanup))
+            .route("/aircraft", get(routes::aircraft::list_aircraft))
+            .route("/aircraft", post(routes::aircraft::create_aircraft))
+            .route("/aircraft/:id", get(routes::aircraft::get_aircraft))
+            .route("/aircraft/:id", patch(routes::aircraft::update_aircraft))
+            .route("/aircraft/:id", delete(routes::aircraft::delete_aircraft))
+            .route("/bookings", get(routes::bookings::list_bookings).layer(middleware::from_fn(etag::conditional_get)))
+            .route("/bookings", post(routes::bookings::create_booking))
+            .route("/bookings/bulk", post(routes::bookings::bulk_create_bookings))
+            .route("/bookings/:id", get(routes::bookings::get_booking))
+            .route("/bookings/:id/reschedule-suggestions", get(routes::bookings::get_reschedule_suggestions))
+            .route("/bookings/:id/reschedule-history", get(routes::bookings::get_reschedule_history))
+            .route("/bookings/:id/reschedule", patch(routes::bookings::reschedule_booking))
+            .route("/bookings/:id/cancel", post(routes::bookings::cancel_booking))
+            .route("/bookings/:id/acknowledge-hold", post(routes::bookings::acknowledge_hold))
+            .route("/bookings/:id/weather", get(routes::weather::get_booking_weather))
+            .route("/reschedule-suggestions/batch", post(routes::bookings::batch_reschedule_suggestions))
+            .route("/instructors", get(routes::instructors::list_instructors))
+            .route("/instructors", post(routes::instructors::create_instructor))
+            .route("/instructors/:id", get(routes::instructors::get_instructor))
+            .route("/instructors/:id", patch(routes::instructors::update_instructor))
+            .route("/instructors/:id", delete(routes::instructors::delete_instructor))
+            .route("/stats", get(routes::stats::get_stats))
+            .route("/students", get(routes::students::list_students))
+            .route("/students", post(routes::students::create_student))
+            .route("/students/:id", get(routes::students::get_student))
+            .route("/students/:id", patch(routes::students::update_student))
+            .route("/students/:id", delete(routes::students::delete_student))
+            .route("/weather", get(routes::weather::get_weather).layer(middleware::from_fn(etag::conditional_get)))
+            .route("/weather/check", post(routes::weather::check_weather_safety))
+            .route("/weather/daily", get(routes::weather::get_daily_forecast))
+            .route("/weather-minimums", get(routes::weather_minimums::list_weather_minimums))
+            .route("/weather-minimums/:training_level", patch(routes::weather_minimums::update_weather_minimum))
+    };
+
+    Router::new()
+        .nest(
+            &format!("/api/{}", api_version::API_VERSION),
+            routes().layer(middleware::from_fn(api_version::tag_version)),
+        )
+        .nest("/api", routes().layer(middleware::from_fn(api_version::deprecated_alias)))
+}
+
+/// Comma-separated `ALLOWED_ORIGINS`. Credentials are allowed, so a wildcard
+/// is rejected rather than letting any site make authenticated requests.
+fn parse_allowed_origins(origins_str: &str) -> anyhow::Result<Vec<axum::http::HeaderValue>> {
+    let mut origins = Vec::new();
+    for origin in origins_str.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if origin == "*" {
+            anyhow::bail!("ALLOWED_ORIGINS must list origins explicitly; '*' is not allowed with credentials");
         }
-    });
+        match origin.parse() {
+            Ok(origin) => origins.push(origin),
+            Err(_) => tracing::warn!("Ignoring invalid origin in ALLOWED_ORIGINS: {}", origin),
+        }
+    }
 
-    // Start server
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3003));
-    tracing::info!("Server listening on {}", addr);
+    if origins.is_empty() {
+        anyhow::bail!("ALLOWED_ORIGINS is set but contains no valid origins");
+    }
+    Ok(origins)
+}
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+/// CORS for the listed origins, with cookies and the `Authorization` header
+/// (credentials) allowed
+fn cors_layer(origins: Vec<axum::http::HeaderValue>) -> CorsLayer {
+    use axum::http::{header, HeaderName, Method};
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            HeaderName::from_static(csrf::CSRF_HEADER_NAME),
+            HeaderName::from_static(telemetry::REQUEST_ID_HEADER),
+            HeaderName::from_static(routes::bookings::IDEMPOTENCY_KEY_HEADER),
+            header::IF_NONE_MATCH,
+        ])
+        .expose_headers([
+            HeaderName::from_static(telemetry::REQUEST_ID_HEADER),
+            header::ETAG,
+            HeaderName::from_static(api_version::API_VERSION_HEADER),
+            HeaderName::from_static(api_version::DEPRECATION_HEADER),
+            header::LINK,
+        ])
+        .allow_credentials(true)
+}
 
-    Ok(())
+/// Listen address used when none of `BIND_ADDR`, `HOST` or `PORT` is set
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 3000;
+
+/// Address to listen on: `BIND_ADDR` (host:port) if set, otherwise `HOST`
+/// and `PORT`, each falling back to the default
+fn bind_addr_from_env() -> anyhow::Result<SocketAddr> {
+    let var = |name| std::env::var(name).ok().filter(|value: &String| !value.trim().is_empty());
+    resolve_bind_addr(var("BIND_ADDR").as_deref(), var("HOST").as_deref(), var("PORT").as_deref())
+}
+
+fn resolve_bind_addr(bind_addr: Option<&str>, host: Option<&str>, port: Option<&str>) -> anyhow::Result<SocketAddr> {
+    use std::net::ToSocketAddrs;
+
+    if let Some(bind_addr) = bind_addr {
+        return bind_addr
+            .trim()
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Invalid BIND_ADDR '{}': expected host:port, e.g. 127.0.0.1:3000", bind_addr)
+            });
+    }
+
+    let port = match port {
+        Some(port) => match port.trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => anyhow::bail!("Invalid PORT '{}': must be a number between 1 and 65535", port),
+        },
+        None => DEFAULT_PORT,
+    };
+    let host = host.map(str::trim).unwrap_or(DEFAULT_HOST);
+
+    (host, port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| anyhow::anyhow!("Invalid HOST '{}': not an IP address or resolvable hostname", host))
+}
+
+/// Resolves on ctrl-c or SIGTERM, cancelling `shutdown` so background tasks stop
+async fn shutdown_signal(shutdown: CancellationToken) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for ctrl-c: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received ctrl-c, shutting down..."),
+        _ = terminate => tracing::info!("Received SIGTERM, shutting down..."),
+    }
+
+    shutdown.cancel();
 }
 
 async fn health_check() -> axum::Json<serde_json::Value> {
@@ -283,3 +509,139 @@ async fn test_handler() -> &'static str {
     tracing::debug!("Test route called");
     "test response"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::students::{insert_student, CreateStudentRequest};
+    use weather_core::weather::MockWeatherClient;
+    use crate::test_support::{setup_test_db, test_state};
+    use axum::extract::ConnectInfo;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_app_gzips_api_responses_but_not_websocket() {
+        let db = setup_test_db().await;
+        for i in 0..50 {
+            insert_student(
+                &db,
+                &CreateStudentRequest {
+                    name: format!("Student {}", i),
+                    email: format!("student{}@example.com", i),
+                    phone: "+15625550100".to_string(),
+                    training_level: "STUDENT_PILOT".to_string(),
+                    notify_email: None,
+                    notify_sms: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let rate_limit = RateLimitSettings {
+            per_second: 100,
+            burst_size: 100,
+            trust_proxy: false,
+        };
+        let metrics_handle = telemetry::prometheus_builder().unwrap().build_recorder().handle();
+        let app = build_app(
+            test_state(db, MockWeatherClient::new()),
+            auth::ApiKeys::new(vec![("test-secret-key".to_string(), auth::ApiRole::Admin)]),
+            cors_layer(vec![axum::http::HeaderValue::from_static("http://localhost:8000")]),
+            &rate_limit,
+            metrics_handle,
+        )
+        // The peer address axum::serve would supply, for the rate limit key
+        .layer(axum::Extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40000)))));
+
+        let request = |uri: &str, encoding: Option<&str>| {
+            let mut request = axum::http::Request::get(uri).header("authorization", "Bearer test-secret-key");
+            if let Some(encoding) = encoding {
+                request = request.header("accept-encoding", encoding);
+            }
+            request.body(axum::body::Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request("/api/v1/students?limit=50", Some("gzip"))).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(response.headers()["content-encoding"], "gzip");
+
+        let response = app.clone().oneshot(request("/api/v1/students?limit=50", None)).await.unwrap();
+        assert!(response.headers().get("content-encoding").is_none());
+
+        // The WebSocket route sits outside the compression layer, so even its
+        // plain-text rejection of a request without an upgrade isn't encoded
+        let response = app.oneshot(request("/ws", Some("gzip"))).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        assert!(response.headers().get("content-encoding").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_alias_is_deprecated() {
+        use axum::http::{header, StatusCode};
+
+        let state = test_state(setup_test_db().await, MockWeatherClient::new());
+        let app = api_routes().layer(axum::Extension(auth::ApiRole::ReadOnly)).with_state(state);
+        let get = |uri: &str| axum::http::Request::get(uri).body(axum::body::Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/api/v1/students")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[api_version::API_VERSION_HEADER], "v1");
+        assert!(response.headers().get(api_version::DEPRECATION_HEADER).is_none());
+
+        let response = app.oneshot(get("/api/students")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[api_version::API_VERSION_HEADER], "v1");
+        assert_eq!(response.headers()[api_version::DEPRECATION_HEADER], "true");
+        assert_eq!(response.headers()[header::LINK], "</api/v1/students>; rel=\"successor-version\"");
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_allows_credentialed_delete() {
+        use axum::http::{header, Method, StatusCode};
+
+        let origins = parse_allowed_origins("https://app.example.com, https://ops.example.com").unwrap();
+        let app = Router::new()
+            .route("/api/students/:id", delete(|| async { StatusCode::NO_CONTENT }))
+            .layer(cors_layer(origins));
+
+        let request = axum::http::Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/students/1")
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "DELETE")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization,x-csrf-token")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://app.example.com");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        let methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS].to_str().unwrap();
+        assert!(methods.contains("DELETE") && methods.contains("PUT"));
+        let allowed = headers[header::ACCESS_CONTROL_ALLOW_HEADERS].to_str().unwrap();
+        assert!(allowed.contains("authorization") && allowed.contains("x-csrf-token"));
+
+        assert!(parse_allowed_origins("https://app.example.com,*").is_err());
+        assert!(parse_allowed_origins(" , ").is_err());
+    }
+
+    #[test]
+    fn test_bind_addr_from_host_port_or_bind_addr() {
+        let addr = |bind_addr, host, port| resolve_bind_addr(bind_addr, host, port).map(|a| a.to_string());
+
+        assert_eq!(addr(None, None, None).unwrap(), "0.0.0.0:3000");
+        assert_eq!(addr(None, Some("127.0.0.1"), Some("8080")).unwrap(), "127.0.0.1:8080");
+        assert_eq!(addr(None, Some("::1"), None).unwrap(), "[::1]:3000");
+        // BIND_ADDR wins over HOST and PORT
+        assert_eq!(addr(Some("127.0.0.1:4000"), Some("0.0.0.0"), Some("8080")).unwrap(), "127.0.0.1:4000");
+
+        let err = addr(None, None, Some("http")).unwrap_err();
+        assert!(err.to_string().contains("Invalid PORT 'http'"));
+        assert!(addr(None, None, Some("0")).is_err());
+        assert!(addr(None, None, Some("70000")).is_err());
+        let err = addr(Some("127.0.0.1"), None, None).unwrap_err();
+        assert!(err.to_string().contains("Invalid BIND_ADDR"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    error::{ApiError, ErrorDetails},
+    routes::{
+        admin,
+        admin::CleanupResponse,
+        aircraft, alerts,
+        alerts::WeatherAlert,
+        bookings,
+        bookings::{BatchSuggestionsRequest, BatchSuggestionsResponse, BookingResponse, CreateBookingRequest},
+        instructors,
+        pagination::{PaginatedAlerts, PaginatedBookings, PaginatedStudents},
+        stats,
+        stats::StatsResponse,
+        students,
+        students::StudentResponse,
+        weather,
+        weather::{
+            BookingWeatherResponse, DailyForecastResponse, DailyOutlook, SafetyCheckResponse, TemperatureRange,
+            ViolationResponse, WeatherEvaluation, WeatherReadings, WeatherResponse,
+        },
+        weather_minimums,
+    },
+};
+use axum::Json;
+use weather_core::ai::RescheduleOption;
+use weather_core::models::{BookingStatus, Location, TrainingLevel, WeatherMinimumOverride};
+use weather_core::weather::{FlightCategory, SafetyViolation, Units, WeatherData};
+use utoipa::OpenApi;
+
+/// Machine-readable contract for the REST API. Every handler under
+/// `routes` carries a `#[utoipa::path]`; add new ones to `paths` here.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Flight Schedule Pro Weather API"),
+    paths(
+        admin::cleanup,
+        aircraft::list_aircraft,
+        aircraft::create_aircraft,
+        aircraft::get_aircraft,
+        aircraft::update_aircraft,
+        aircraft::delete_aircraft,
+        alerts::list_alerts,
+        alerts::dismiss_alert,
+        bookings::list_bookings,
+        bookings::create_booking,
+        bookings::bulk_create_bookings,
+        bookings::get_booking,
+        bookings::get_reschedule_suggestions,
+        bookings::get_reschedule_history,
+        bookings::reschedule_booking,
+        bookings::cancel_booking,
+        bookings::acknowledge_hold,
+        bookings::batch_reschedule_suggestions,
+        instructors::list_instructors,
+        instructors::create_instructor,
+        instructors::get_instructor,
+        instructors::update_instructor,
+        instructors::delete_instructor,
+        stats::get_stats,
+        students::list_students,
+        students::create_student,
+        students::get_student,
+        students::update_student,
+        students::delete_student,
+        weather::get_weather,
+        weather::get_daily_forecast,
+        weather::check_weather_safety,
+        weather::get_booking_weather,
+        weather_minimums::list_weather_minimums,
+        weather_minimums::update_weather_minimum,
+    ),
+    components(schemas(
+        ApiError,
+        BatchSuggestionsRequest,
+        BatchSuggestionsResponse,
+        ErrorDetails,
+        BookingResponse,
+        BookingStatus,
+        BookingWeatherResponse,
+        CleanupResponse,
+        CreateBookingRequest,
+        DailyForecastResponse,
+        DailyOutlook,
+        FlightCategory,
+        Location,
+        PaginatedAlerts,
+        PaginatedBookings,
+        PaginatedStudents,
+        RescheduleOption,
+        SafetyCheckResponse,
+        SafetyViolation,
+        StatsResponse,
+        StudentResponse,
+        TemperatureRange,
+        TrainingLevel,
+        Units,
+        ViolationResponse,
+        WeatherAlert,
+        WeatherData,
+        WeatherEvaluation,
+        WeatherMinimumOverride,
+        WeatherReadings,
+        WeatherResponse,
+    ))
+)]
+pub struct ApiDoc;
+
+/// GET /api/openapi.json - The OpenAPI document for this API
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_version::API_VERSION;
+
+    /// Served under the API prefix but deliberately left out of the document
+    const UNDOCUMENTED_ROUTES: [(&str, &str); 2] = [("get", "/csrf-token"), ("get", "/openapi.json")];
+
+    /// `(method, path)` for every route in `api_routes()`, in OpenAPI's
+    /// `{param}` form. axum can't list a router's routes, so they're read
+    /// from the `.route(...)` calls in main.rs.
+    fn routed_paths() -> Vec<(String, String)> {
+        let source = include_str!("main.rs");
+        let start = source.find("fn api_routes()").unwrap();
+        let end = start + source[start..].find("\n}\n").unwrap();
+
+        let mut routes: Vec<(String, String)> = source[start..end]
+            .lines()
+            .filter_map(|line| {
+                let (path, rest) = line.trim().strip_prefix(".route(\"")?.split_once('"')?;
+                let method = rest.trim_start_matches(',').trim_start().split('(').next()?;
+                Some((method.to_string(), path.to_string()))
+            })
+            .filter(|(method, path)| !UNDOCUMENTED_ROUTES.contains(&(method.as_str(), path.as_str())))
+            .map(|(method, path)| {
+                let segments: Vec<String> = path
+                    .split('/')
+                    .map(|segment| match segment.strip_prefix(':') {
+                        Some(param) => format!("{{{}}}", param),
+                        None => segment.to_string(),
+                    })
+                    .collect();
+                (method, format!("/api/{}{}", API_VERSION, segments.join("/")))
+            })
+            .collect();
+        routes.sort();
+        routes
+    }
+
+    #[test]
+    fn test_document_matches_router() {
+        let json = serde_json::to_string(&ApiDoc::openapi()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let mut documented: Vec<(String, String)> = document["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .filter(|key| ["get", "post", "put", "patch", "delete"].contains(&key.as_str()))
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect();
+        documented.sort();
+
+        let routed = routed_paths();
+        assert!(!routed.is_empty());
+        assert_eq!(documented, routed);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(
+            schemas["BookingStatus"]["enum"],
+            serde_json::json!(["SCHEDULED", "WEATHER_HOLD", "CANCELLED", "RESCHEDULED", "COMPLETED"])
+        );
+        assert_eq!(
+            schemas["TrainingLevel"]["enum"],
+            serde_json::json!(["STUDENT_PILOT", "PRIVATE_PILOT", "INSTRUMENT_RATED"])
+        );
+        assert!(schemas["ApiError"]["properties"]["error"].is_object());
+    }
+}
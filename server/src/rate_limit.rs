@@ -0,0 +1,166 @@
+use axum::{extract::ConnectInfo, http::Request};
+use std::net::{IpAddr, SocketAddr};
+use tower_governor::{key_extractor::KeyExtractor, GovernorError};
+
+const DEFAULT_RATE_LIMIT_PER_SECOND: u64 = 10;
+const DEFAULT_RATE_LIMIT_BURST: u32 = 50;
+
+/// Rate limit configuration read from the environment
+/// - RATE_LIMIT_PER_SECOND: sustained requests per second (default 10)
+/// - RATE_LIMIT_BURST: requests allowed before limiting kicks in (default 50)
+/// - TRUST_PROXY: honor X-Forwarded-For / X-Real-IP (only set behind a proxy)
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitSettings {
+    pub per_second: u64,
+    pub burst_size: u32,
+    pub trust_proxy: bool,
+}
+
+impl RateLimitSettings {
+    /// Milliseconds to replenish one request; governor takes the period
+    /// rather than the rate, and rates above 1000/s round to 1ms
+    pub fn replenish_interval_ms(&self) -> u64 {
+        (1000 / self.per_second.max(1)).max(1)
+    }
+}
+
+impl RateLimitSettings {
+    pub fn from_env() -> Self {
+        Self {
+            per_second: std::env::var("RATE_LIMIT_PER_SECOND")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_RATE_LIMIT_PER_SECOND),
+            burst_size: std::env::var("RATE_LIMIT_BURST")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_RATE_LIMIT_BURST),
+            trust_proxy: std::env::var("TRUST_PROXY")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Keys rate limiting on the client IP
+///
+/// Without `trust_proxy` only the socket peer address is used, so clients
+/// can't dodge the limit by sending their own forwarding headers. The server
+/// must be served with `into_make_service_with_connect_info::<SocketAddr>()`.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIpKeyExtractor {
+    trust_proxy: bool,
+}
+
+impl ClientIpKeyExtractor {
+    pub fn new(trust_proxy: bool) -> Self {
+        Self { trust_proxy }
+    }
+}
+
+impl KeyExtractor for ClientIpKeyExtractor {
+    type Key = IpAddr;
+
+    fn extract<T>(&self, req: &Request<T>) -> Result<Self::Key, GovernorError> {
+        let forwarded = if self.trust_proxy {
+            forwarded_client_ip(req)
+        } else {
+            None
+        };
+
+        forwarded
+            .or_else(|| {
+                req.extensions()
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip())
+            })
+            .ok_or(GovernorError::UnableToExtractKey)
+    }
+}
+
+/// The original client from X-Forwarded-For (first entry), else X-Real-IP
+fn forwarded_client_ip<T>(req: &Request<T>) -> Option<IpAddr> {
+    let headers = req.headers();
+
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .and_then(|ip| ip.trim().parse().ok())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|ip| ip.trim().parse().ok())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+    use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
+
+    fn request_from(forwarded_for: &str) -> Request<Body> {
+        Request::builder()
+            .uri("/api/test")
+            .header("x-forwarded-for", forwarded_for)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_forwarded_headers_ignored_without_trust_proxy() {
+        let mut req = request_from("203.0.113.7");
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+
+        let untrusted = ClientIpKeyExtractor::new(false).extract(&req).unwrap();
+        let trusted = ClientIpKeyExtractor::new(true).extract(&req).unwrap();
+
+        assert_eq!(untrusted, IpAddr::from([10, 0, 0, 1]));
+        assert_eq!(trusted, IpAddr::from([203, 0, 113, 7]));
+    }
+
+    #[test]
+    fn test_rate_converted_to_replenish_interval() {
+        let settings = |per_second| RateLimitSettings { per_second, burst_size: 50, trust_proxy: false };
+
+        assert_eq!(settings(10).replenish_interval_ms(), 100);
+        assert_eq!(settings(1).replenish_interval_ms(), 1000);
+        assert_eq!(settings(5000).replenish_interval_ms(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_past_burst_get_429() {
+        let config = Box::new(
+            GovernorConfigBuilder::default()
+                .per_second(60)
+                .burst_size(3)
+                .key_extractor(ClientIpKeyExtractor::new(true))
+                .finish()
+                .unwrap(),
+        );
+        let app = Router::new()
+            .route("/api/test", get(|| async { "ok" }))
+            .layer(GovernorLayer {
+                config: Box::leak(config),
+            });
+
+        let mut statuses = Vec::new();
+        for _ in 0..5 {
+            let response = app.clone().oneshot(request_from("203.0.113.7")).await.unwrap();
+            statuses.push(response.status());
+        }
+
+        assert_eq!(&statuses[..3], &[StatusCode::OK; 3]);
+        assert_eq!(&statuses[3..], &[StatusCode::TOO_MANY_REQUESTS; 2]);
+
+        // A different client still has its own budget
+        let other = app.oneshot(request_from("198.51.100.4")).await.unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+    }
+}
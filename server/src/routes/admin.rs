@@ -0,0 +1,204 @@
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::ApiRole,
+    error::{ApiError, ApiResult},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CleanupParams {
+    #[serde(default = "default_older_than_days")]
+    pub older_than_days: i64,
+}
+
+fn default_older_than_days() -> i64 {
+    90
+}
+
+/// Rows removed by a cleanup; events and checks go with their bookings
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct CleanupResponse {
+    pub alerts_deleted: u64,
+    pub bookings_deleted: u64,
+    pub reschedule_events_deleted: u64,
+    pub weather_checks_deleted: u64,
+}
+
+/// Completed and cancelled bookings whose flight was before the cutoff
+const PURGEABLE_BOOKINGS: &str = "SELECT id FROM bookings
+     WHERE status IN ('COMPLETED', 'CANCELLED') AND datetime(scheduled_date) < datetime(?)";
+
+/// POST /api/admin/cleanup - Permanently delete dismissed alerts and
+/// completed/cancelled bookings older than `older_than_days` (default 90)
+/// Requires an admin API key
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/cleanup",
+    tag = "admin",
+    params(("older_than_days" = Option<i64>, Query, description = "Age cutoff in days (default 90)")),
+    responses(
+        (status = 200, description = "Counts of rows removed", body = CleanupResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 403, description = "Admin API key required", body = ApiError),
+    )
+)]
+pub async fn cleanup(
+    Query(params): Query<CleanupParams>,
+    State(state): State<AppState>,
+    Extension(role): Extension<ApiRole>,
+) -> ApiResult<Json<CleanupResponse>> {
+    if !role.satisfies(ApiRole::Admin) {
+        return Err(ApiError::forbidden("Cleanup requires an admin API key"));
+    }
+    if params.older_than_days < 1 {
+        return Err(ApiError::validation_error("older_than_days must be at least 1"));
+    }
+
+    let cutoff = Utc::now() - Duration::days(params.older_than_days);
+    let removed = purge_before(&state.db, cutoff).await?;
+
+    tracing::info!(
+        "Cleanup before {} removed {} alerts, {} bookings, {} reschedule events and {} weather checks",
+        cutoff,
+        removed.alerts_deleted,
+        removed.bookings_deleted,
+        removed.reschedule_events_deleted,
+        removed.weather_checks_deleted
+    );
+    Ok(Json(removed))
+}
+
+/// Delete alerts dismissed before `cutoff` and finished bookings flown
+/// before it, in one transaction. Bookings' reschedule events and weather
+/// checks are removed by the foreign key cascade and counted first.
+async fn purge_before(db: &SqlitePool, cutoff: DateTime<Utc>) -> Result<CleanupResponse, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let alerts_deleted = sqlx::query(
+        "DELETE FROM weather_alerts WHERE dismissed_at IS NOT NULL AND datetime(dismissed_at) < datetime(?)"
+    )
+    .bind(cutoff)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let reschedule_events_deleted: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM reschedule_events WHERE booking_id IN ({})",
+        PURGEABLE_BOOKINGS
+    ))
+    .bind(cutoff)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let weather_checks_deleted: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM weather_checks WHERE booking_id IN ({})",
+        PURGEABLE_BOOKINGS
+    ))
+    .bind(cutoff)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let bookings_deleted = sqlx::query(&format!("DELETE FROM bookings WHERE id IN ({})", PURGEABLE_BOOKINGS))
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+
+    Ok(CleanupResponse {
+        alerts_deleted,
+        bookings_deleted,
+        reschedule_events_deleted: reschedule_events_deleted as u64,
+        weather_checks_deleted: weather_checks_deleted as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weather_core::models::{RescheduleEvent, SUGGESTED_BY_SYSTEM};
+    use crate::test_support::{self, insert_test_student};
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = test_support::setup_test_db().await;
+        insert_test_student(&pool).await;
+        pool
+    }
+
+    async fn insert_booking(db: &SqlitePool, id: &str, status: &str, scheduled_date: DateTime<Utc>) {
+        sqlx::query(
+            "INSERT INTO bookings (id, student_id, scheduled_date, departure_location, status)
+             VALUES (?, 'student1', ?, '{\"lat\": 33.8, \"lon\": -118.1, \"name\": \"KTOA\"}', ?)"
+        )
+        .bind(id)
+        .bind(scheduled_date)
+        .bind(status)
+        .execute(db)
+        .await
+        .unwrap();
+
+        RescheduleEvent::record(db, id, scheduled_date, scheduled_date, SUGGESTED_BY_SYSTEM, Some("Weather conflict"))
+            .await
+            .unwrap();
+    }
+
+    async fn insert_alert(db: &SqlitePool, id: &str, dismissed_at: Option<DateTime<Utc>>) {
+        sqlx::query(
+            "INSERT INTO weather_alerts (id, severity, message, location, created_at, dismissed_at)
+             VALUES (?, 'high', 'Test alert', 'KTOA', ?, ?)"
+        )
+        .bind(id)
+        .bind(Utc::now() - Duration::days(200))
+        .bind(dismissed_at)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    async fn ids(db: &SqlitePool, table: &str) -> Vec<String> {
+        sqlx::query_scalar(&format!("SELECT id FROM {} ORDER BY id", table))
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_purges_only_old_rows() {
+        let db = setup_test_db().await;
+        let old = Utc::now() - Duration::days(120);
+        let recent = Utc::now() - Duration::days(10);
+
+        insert_booking(&db, "old_completed", "COMPLETED", old).await;
+        insert_booking(&db, "old_cancelled", "CANCELLED", old).await;
+        insert_booking(&db, "old_scheduled", "SCHEDULED", old).await;
+        insert_booking(&db, "recent_completed", "COMPLETED", recent).await;
+
+        insert_alert(&db, "old_dismissed", Some(old)).await;
+        insert_alert(&db, "recent_dismissed", Some(recent)).await;
+        insert_alert(&db, "active", None).await;
+
+        let removed = purge_before(&db, Utc::now() - Duration::days(90)).await.unwrap();
+
+        assert_eq!(removed.alerts_deleted, 1);
+        assert_eq!(removed.bookings_deleted, 2);
+        assert_eq!(removed.reschedule_events_deleted, 2);
+        assert_eq!(removed.weather_checks_deleted, 0);
+
+        assert_eq!(ids(&db, "bookings").await, vec!["old_scheduled", "recent_completed"]);
+        assert_eq!(ids(&db, "weather_alerts").await, vec!["active", "recent_dismissed"]);
+        let events: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reschedule_events")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(events, 2);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{
+    error::{ApiError, ApiJson, ApiResult},
+    routes::{
+        nullable,
+        pagination::{Paginated, PaginationParams},
+    },
+    AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use weather_core::models::Aircraft;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite, SqlitePool};
+use validator::Validate;
+
+const AIRCRAFT_COLUMNS: &str = "id, tail_number, model, max_demonstrated_crosswind_kt, ifr_capable";
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAircraftRequest {
+    #[validate(length(min = 1, message = "Tail number cannot be empty"))]
+    pub tail_number: String,
+    #[validate(length(min = 1, message = "Model cannot be empty"))]
+    pub model: String,
+    #[validate(range(min = 0.0, message = "Max demonstrated crosswind must be non-negative"))]
+    pub max_demonstrated_crosswind_kt: Option<f64>,
+    #[serde(default)]
+    pub ifr_capable: bool,
+}
+
+/// Partial update; omitted fields keep their current value and a `null`
+/// crosswind limit removes it
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateAircraftRequest {
+    #[validate(length(min = 1, message = "Tail number cannot be empty"))]
+    pub tail_number: Option<String>,
+    #[validate(length(min = 1, message = "Model cannot be empty"))]
+    pub model: Option<String>,
+    #[validate(range(min = 0.0, message = "Max demonstrated crosswind must be non-negative"))]
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_demonstrated_crosswind_kt: Option<Option<f64>>,
+    pub ifr_capable: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AircraftResponse {
+    pub id: String,
+    pub tail_number: String,
+    pub model: String,
+    pub max_demonstrated_crosswind_kt: Option<f64>,
+    pub ifr_capable: bool,
+}
+
+impl From<Aircraft> for AircraftResponse {
+    fn from(aircraft: Aircraft) -> Self {
+        Self {
+            id: aircraft.id,
+            tail_number: aircraft.tail_number,
+            model: aircraft.model,
+            max_demonstrated_crosswind_kt: aircraft.max_demonstrated_crosswind_kt,
+            ifr_capable: aircraft.ifr_capable,
+        }
+    }
+}
+
+/// GET /api/aircraft - Fleet aircraft ordered by tail number
+#[utoipa::path(
+    get,
+    path = "/api/v1/aircraft",
+    tag = "aircraft",
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Page of aircraft ordered by tail number"),
+    )
+)]
+pub async fn list_aircraft(
+    Query(params): Query<PaginationParams>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<Paginated<AircraftResponse>>> {
+    let (page, limit, offset) = params.clamped();
+
+    let aircraft = sqlx::query_as::<_, Aircraft>(&format!(
+        "SELECT {} FROM aircraft ORDER BY tail_number LIMIT ? OFFSET ?",
+        AIRCRAFT_COLUMNS
+    ))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM aircraft")
+        .fetch_one(&state.db)
+        .await?;
+
+    tracing::debug!("Retrieved {} of {} aircraft", aircraft.len(), total);
+    Ok(Json(Paginated::new(aircraft, page, limit, total).map(AircraftResponse::from)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/aircraft",
+    tag = "aircraft",
+    responses(
+        (status = 201, description = "Aircraft created"),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 409, description = "Tail number already registered", body = ApiError),
+    )
+)]
+pub async fn create_aircraft(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<CreateAircraftRequest>,
+) -> ApiResult<(StatusCode, Json<AircraftResponse>)> {
+    let aircraft = insert_aircraft(&state.db, &req).await?;
+
+    tracing::info!("Created aircraft {} ({})", aircraft.tail_number, aircraft.id);
+    Ok((StatusCode::CREATED, Json(aircraft.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/aircraft/{id}",
+    tag = "aircraft",
+    params(("id" = String, Path, description = "Aircraft id")),
+    responses(
+        (status = 200, description = "The aircraft"),
+        (status = 404, description = "Aircraft not found", body = ApiError),
+    )
+)]
+pub async fn get_aircraft(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<AircraftResponse>> {
+    let aircraft = fetch_aircraft(&state.db, &id).await?;
+    Ok(Json(aircraft.into()))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/aircraft/{id}",
+    tag = "aircraft",
+    params(("id" = String, Path, description = "Aircraft id")),
+    responses(
+        (status = 200, description = "Updated aircraft"),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Aircraft not found", body = ApiError),
+        (status = 409, description = "Tail number already registered", body = ApiError),
+    )
+)]
+pub async fn update_aircraft(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<UpdateAircraftRequest>,
+) -> ApiResult<Json<AircraftResponse>> {
+    req.validate()
+        .map_err(|e| ApiError::validation_error(e.to_string()))?;
+
+    let current = fetch_aircraft(&state.db, &id).await?;
+
+    if let Some(tail_number) = &req.tail_number {
+        ensure_tail_number_free(&state.db, tail_number, Some(&id)).await?;
+    }
+
+    sqlx::query(
+        "UPDATE aircraft SET tail_number = ?, model = ?, max_demonstrated_crosswind_kt = ?, ifr_capable = ?,
+         updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(req.tail_number.as_ref().unwrap_or(&current.tail_number))
+    .bind(req.model.as_ref().unwrap_or(&current.model))
+    .bind(req.max_demonstrated_crosswind_kt.unwrap_or(current.max_demonstrated_crosswind_kt))
+    .bind(req.ifr_capable.unwrap_or(current.ifr_capable))
+    .bind(&id)
+    .execute(&state.db)
+    .await?;
+
+    let aircraft = fetch_aircraft(&state.db, &id).await?;
+
+    tracing::info!("Updated aircraft {} ({})", aircraft.tail_number, aircraft.id);
+    Ok(Json(aircraft.into()))
+}
+
+/// DELETE /api/aircraft/:id
+/// Bookings that referenced the aircraft keep their aircraft type but lose
+/// the link, and with it any per-aircraft limits
+#[utoipa::path(
+    delete,
+    path = "/api/v1/aircraft/{id}",
+    tag = "aircraft",
+    params(("id" = String, Path, description = "Aircraft id")),
+    responses(
+        (status = 204, description = "Aircraft deleted"),
+        (status = 404, description = "Aircraft not found", body = ApiError),
+    )
+)]
+pub async fn delete_aircraft(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<StatusCode> {
+    let aircraft = fetch_aircraft(&state.db, &id).await?;
+
+    sqlx::query("DELETE FROM aircraft WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await?;
+
+    tracing::info!("Deleted aircraft {} ({})", aircraft.tail_number, aircraft.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn insert_aircraft(db: &SqlitePool, req: &CreateAircraftRequest) -> ApiResult<Aircraft> {
+    req.validate()
+        .map_err(|e| ApiError::validation_error(e.to_string()))?;
+
+    ensure_tail_number_free(db, &req.tail_number, None).await?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO aircraft (id, tail_number, model, max_demonstrated_crosswind_kt, ifr_capable) VALUES (?, ?, ?, ?, ?)"
+    )
+    .bind(&id)
+    .bind(&req.tail_number)
+    .bind(&req.model)
+    .bind(req.max_demonstrated_crosswind_kt)
+    .bind(req.ifr_capable)
+    .execute(db)
+    .await?;
+
+    fetch_aircraft(db, &id).await
+}
+
+/// Reject a tail number already registered to another aircraft
+async fn ensure_tail_number_free(db: &SqlitePool, tail_number: &str, exclude_id: Option<&str>) -> ApiResult<()> {
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM aircraft WHERE tail_number = ? AND id != ?)"
+    )
+    .bind(tail_number)
+    .bind(exclude_id.unwrap_or(""))
+    .fetch_one(db)
+    .await?;
+
+    if taken {
+        return Err(ApiError::conflict(format!("Tail number {} is already registered", tail_number)));
+    }
+    Ok(())
+}
+
+pub(crate) async fn fetch_aircraft<'e, E>(db: E, id: &str) -> ApiResult<Aircraft>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as::<_, Aircraft>(&format!("SELECT {} FROM aircraft WHERE id = ?", AIRCRAFT_COLUMNS))
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Aircraft"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    fn create_request(tail_number: &str) -> CreateAircraftRequest {
+        CreateAircraftRequest {
+            tail_number: tail_number.to_string(),
+            model: "Cessna 172".to_string(),
+            max_demonstrated_crosswind_kt: Some(15.0),
+            ifr_capable: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_tail_number_conflicts() {
+        let db = setup_test_db().await;
+
+        let aircraft = insert_aircraft(&db, &create_request("N12345")).await.unwrap();
+        assert_eq!(aircraft.tail_number, "N12345");
+        assert_eq!(aircraft.max_demonstrated_crosswind_kt, Some(15.0));
+        assert!(aircraft.ifr_capable);
+
+        let err = insert_aircraft(&db, &create_request("N12345")).await.unwrap_err();
+        assert_eq!(err.error.code, "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_negative_crosswind_rejected() {
+        let db = setup_test_db().await;
+        let req = CreateAircraftRequest {
+            max_demonstrated_crosswind_kt: Some(-5.0),
+            ..create_request("N54321")
+        };
+
+        let err = insert_aircraft(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+}
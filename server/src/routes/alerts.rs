@@ -1,14 +1,23 @@
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     Json,
 };
 use chrono::{DateTime, Utc};
+use weather_core::models::Notification;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool};
+use utoipa::{IntoParams, ToSchema};
 
-use crate::{error::ApiResult, AppState};
+use crate::{
+    error::{ApiError, ApiResult},
+    routes::pagination::{clamp_page, Paginated},
+    AppState, NotificationChannel,
+};
+
+/// Alert severities, most severe first
+pub(crate) const SEVERITIES: [&str; 5] = ["severe", "high", "moderate", "low", "clear"];
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct WeatherAlert {
     pub id: String,
     pub booking_id: Option<String>,
@@ -17,26 +26,258 @@ pub struct WeatherAlert {
     pub location: String,
     pub student_name: Option<String>,
     pub original_date: Option<DateTime<Utc>>,
+    /// improving, steady or worsening toward the flight, when known
+    pub trend: Option<String>,
     pub created_at: DateTime<Utc>,
     pub dismissed_at: Option<DateTime<Utc>>,
 }
 
-/// GET /api/alerts - Retrieve all weather alerts
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AlertQueryParams {
+    pub severity: Option<String>,
+    pub booking_id: Option<String>,
+    #[serde(default)]
+    pub include_dismissed: bool,
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// GET /api/alerts - Retrieve weather alerts, newest first
 /// Query params:
-/// - dismissed: bool (optional) - include dismissed alerts
+/// - severity: string (optional) - only alerts of this severity
+/// - booking_id: string (optional) - only alerts for this booking
+/// - include_dismissed: bool (optional) - include dismissed alerts
+/// - page, limit: pagination (limit clamped to 1..=100)
+#[utoipa::path(
+    get,
+    path = "/api/v1/alerts",
+    tag = "alerts",
+    params(AlertQueryParams),
+    responses(
+        (status = 200, description = "Page of alerts, newest first", body = PaginatedAlerts),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
 pub async fn list_alerts(
+    Query(params): Query<AlertQueryParams>,
     State(state): State<AppState>,
-) -> ApiResult<Json<Vec<WeatherAlert>>> {
-    let alerts = sqlx::query_as::<_, WeatherAlert>(
-        "SELECT id, booking_id, severity, message, location, student_name, original_date, created_at, dismissed_at
-         FROM weather_alerts
-         WHERE dismissed_at IS NULL
-         ORDER BY created_at DESC
-         LIMIT 100"
+) -> ApiResult<Json<Paginated<WeatherAlert>>> {
+    if let Some(severity) = &params.severity {
+        if !SEVERITIES.contains(&severity.as_str()) {
+            return Err(ApiError::validation_error(format!(
+                "Invalid severity: {}. Must be one of: {}",
+                severity,
+                SEVERITIES.join(", ")
+            )));
+        }
+    }
+
+    let alerts = fetch_alerts(&state.db, &params).await?;
+    let total = count_alerts(&state.db, &params).await?;
+    let (page, limit, _) = clamp_page(params.page, params.limit);
+
+    tracing::debug!("Retrieved {} of {} weather alerts", alerts.len(), total);
+    Ok(Json(Paginated::new(alerts, page, limit, total)))
+}
+
+/// POST /api/alerts/:id/dismiss - Dismiss an alert and tell WebSocket clients
+/// to remove it. Dismissing an already dismissed alert is a no-op.
+#[utoipa::path(
+    post,
+    path = "/api/v1/alerts/{id}/dismiss",
+    tag = "alerts",
+    params(("id" = String, Path, description = "Alert id")),
+    responses(
+        (status = 200, description = "The dismissed alert", body = WeatherAlert),
+        (status = 404, description = "Alert not found", body = ApiError),
+    )
+)]
+pub async fn dismiss_alert(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<WeatherAlert>> {
+    let alert = dismiss(&state.db, &state.notification_tx, &id).await?;
+    Ok(Json(alert))
+}
+
+async fn dismiss(db: &SqlitePool, tx: &NotificationChannel, id: &str) -> ApiResult<WeatherAlert> {
+    let alert = fetch_alert(db, id).await?;
+    if alert.dismissed_at.is_some() {
+        return Ok(alert);
+    }
+
+    let dismissed_at = Utc::now();
+    sqlx::query("UPDATE weather_alerts SET dismissed_at = ? WHERE id = ?")
+        .bind(dismissed_at)
+        .bind(id)
+        .execute(db)
+        .await?;
+
+    let notification = Notification::AlertDismissed {
+        id: id.to_string(),
+        dismissed_at,
+    };
+    let _ = tx.send(serde_json::to_string(&notification)?);
+
+    tracing::info!("Dismissed weather alert {}", id);
+    fetch_alert(db, id).await
+}
+
+async fn fetch_alert(db: &SqlitePool, id: &str) -> ApiResult<WeatherAlert> {
+    sqlx::query_as::<_, WeatherAlert>(
+        "SELECT id, booking_id, severity, message, location, student_name, original_date, trend, created_at, dismissed_at
+         FROM weather_alerts WHERE id = ?",
     )
-    .fetch_all(&state.db)
-    .await?;
+    .bind(id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Alert"))
+}
+
+/// Undismissed alerts, newest first (capped at one page of 100)
+pub(crate) async fn active_alerts(db: &SqlitePool) -> Result<Vec<WeatherAlert>, sqlx::Error> {
+    let params = AlertQueryParams {
+        page: default_page(),
+        limit: default_limit(),
+        ..Default::default()
+    };
+    fetch_alerts(db, &params).await
+}
+
+async fn fetch_alerts(
+    db: &SqlitePool,
+    params: &AlertQueryParams,
+) -> Result<Vec<WeatherAlert>, sqlx::Error> {
+    // Validate and sanitize pagination parameters
+    let (_, limit, offset) = clamp_page(params.page, params.limit);
+
+    let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT id, booking_id, severity, message, location, student_name, original_date, trend, created_at, dismissed_at
+         FROM weather_alerts",
+    );
+    push_alert_filters(&mut query, params);
+
+    query
+        .push(" ORDER BY created_at DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    query.build_query_as::<WeatherAlert>().fetch_all(db).await
+}
+
+/// Number of alerts matching the filters, ignoring pagination
+async fn count_alerts(db: &SqlitePool, params: &AlertQueryParams) -> Result<i64, sqlx::Error> {
+    let mut query: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT COUNT(*) FROM weather_alerts");
+    push_alert_filters(&mut query, params);
+
+    query.build_query_scalar().fetch_one(db).await
+}
+
+fn push_alert_filters<'a>(query: &mut QueryBuilder<'a, Sqlite>, params: &'a AlertQueryParams) {
+    query.push(" WHERE 1 = 1");
+
+    if !params.include_dismissed {
+        query.push(" AND dismissed_at IS NULL");
+    }
+    if let Some(severity) = &params.severity {
+        query.push(" AND severity = ").push_bind(severity);
+    }
+    if let Some(booking_id) = &params.booking_id {
+        query.push(" AND booking_id = ").push_bind(booking_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    async fn insert_alert(db: &SqlitePool, id: &str, severity: &str, dismissed: bool) {
+        sqlx::query(
+            "INSERT INTO weather_alerts (id, severity, message, location, created_at, dismissed_at)
+             VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind(id)
+        .bind(severity)
+        .bind("Test alert")
+        .bind("(33.8113, -118.1515)")
+        .bind(Utc::now())
+        .bind(if dismissed { Some(Utc::now()) } else { None })
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_alerts_by_severity() {
+        let db = setup_test_db().await;
+        insert_alert(&db, "a1", "severe", false).await;
+        insert_alert(&db, "a2", "low", false).await;
+        insert_alert(&db, "a3", "severe", false).await;
+
+        let params = AlertQueryParams {
+            severity: Some("severe".to_string()),
+            page: 1,
+            limit: 100,
+            ..Default::default()
+        };
+        let alerts = fetch_alerts(&db, &params).await.unwrap();
+
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts.iter().all(|a| a.severity == "severe"));
+        assert_eq!(count_alerts(&db, &params).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dismiss_broadcasts_alert_dismissed() {
+        let db = setup_test_db().await;
+        insert_alert(&db, "a1", "high", false).await;
+        let (tx, mut rx) = tokio::sync::broadcast::channel(10);
+
+        let alert = dismiss(&db, &tx, "a1").await.unwrap();
+        let dismissed_at = alert.dismissed_at.expect("alert should be dismissed");
+
+        let frame: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(frame["type"], "alert_dismissed");
+        assert_eq!(frame["id"], "a1");
+        assert_eq!(frame["dismissed_at"], serde_json::to_value(dismissed_at).unwrap());
+
+        // Dismissing again changes nothing and broadcasts nothing
+        dismiss(&db, &tx, "a1").await.unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let err = dismiss(&db, &tx, "missing").await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_alerts_include_dismissed() {
+        let db = setup_test_db().await;
+        insert_alert(&db, "a1", "high", false).await;
+        insert_alert(&db, "a2", "high", true).await;
+
+        let mut params = AlertQueryParams {
+            page: 1,
+            limit: 100,
+            ..Default::default()
+        };
+        assert_eq!(fetch_alerts(&db, &params).await.unwrap().len(), 1);
 
-    tracing::debug!("Retrieved {} weather alerts", alerts.len());
-    Ok(Json(alerts))
+        params.include_dismissed = true;
+        let alerts = fetch_alerts(&db, &params).await.unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts.iter().any(|a| a.dismissed_at.is_some()));
+    }
 }
@@ -1,46 +1,140 @@
-use crate::{error::ApiResult, AppState};
+use crate::{
+    auth::ApiRole,
+    error::{ApiError, ApiJson, ApiResult},
+    routes::{
+        aircraft::fetch_aircraft,
+        instructors::fetch_instructor,
+        nullable,
+        pagination::{Paginated, PaginationParams},
+        students::{authorize_include_deleted, fetch_student, IncludeDeletedParams, STUDENT_COLUMNS},
+    },
+    scheduler::{release_weather_hold, select_forecast_slot},
+    AppState, NotificationChannel,
+};
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
 };
 use chrono::{DateTime, Utc};
-use core::ai::RescheduleOption;
-use core::models::{Booking, BookingStatus, Location, Student};
+use weather_core::ai::{lesson_duration_from_env, RescheduleOption, DEFAULT_RESCHEDULE_OPTIONS};
+use weather_core::models::{
+    Booking, BookingStatus, BookingStatusEvent, Location, BOOKING_COLUMNS, Notification, RescheduleEvent, Student, WeatherMinimumOverride, SUGGESTED_BY_AI,
+    SUGGESTED_BY_USER,
+};
+use weather_core::weather::{is_flight_safe, join_violations, load_flight_minimums, WeatherData};
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::types::Json as SqlJson;
+use sqlx::{Executor, QueryBuilder, Sqlite, SqliteConnection, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Deserialize)]
-pub struct PaginationParams {
-    #[serde(default = "default_page")]
-    pub page: i64,
-    #[serde(default = "default_limit")]
-    pub limit: i64,
+/// Optional filters for listing bookings
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BookingFilter {
+    pub student_id: Option<String>,
+    /// SCHEDULED, WEATHER_HOLD, CANCELLED, RESCHEDULED or COMPLETED
+    pub status: Option<String>,
+    /// Only bookings scheduled at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only bookings scheduled at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Also list soft-deleted bookings (admin keys only)
+    #[serde(default)]
+    pub include_deleted: bool,
 }
 
-fn default_page() -> i64 {
-    1
-}
+impl BookingFilter {
+    /// Reject an inverted date range or unknown status, returning the parsed status
+    fn validate(&self) -> ApiResult<Option<BookingStatus>> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(ApiError::validation_error("'from' must not be after 'to'"));
+            }
+        }
 
-fn default_limit() -> i64 {
-    50
+        self.status
+            .as_ref()
+            .map(|status| {
+                BookingStatus::try_from(status.to_uppercase()).map_err(|e| {
+                    ApiError::validation_error(format!(
+                        "{}. Must be one of: SCHEDULED, WEATHER_HOLD, CANCELLED, RESCHEDULED, COMPLETED",
+                        e
+                    ))
+                })
+            })
+            .transpose()
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateBookingRequest {
     pub student_id: String,
+    /// Instructor teaching the lesson, if already assigned
+    #[serde(default)]
+    pub instructor_id: Option<String>,
+    /// Fleet aircraft to fly; its model becomes the booking's aircraft type
+    #[serde(default)]
+    pub aircraft_id: Option<String>,
+    /// Free-text aircraft type, used only when no `aircraft_id` is given
+    #[serde(default)]
     pub aircraft_type: String,
     pub scheduled_date: DateTime<Utc>,
     pub departure_location: Location,
+    /// Defaults to `LESSON_DURATION_MINUTES`
+    #[serde(default)]
+    pub lesson_duration_minutes: Option<i64>,
+    /// Minimums for this flight in place of the student's training level defaults
+    #[serde(default)]
+    pub weather_minimum_override: Option<WeatherMinimumOverride>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BookingResponse {
     pub id: String,
     pub student_id: String,
+    pub instructor_id: Option<String>,
+    pub aircraft_id: Option<String>,
     pub aircraft_type: String,
     pub scheduled_date: DateTime<Utc>,
     pub departure_location: Location,
+    #[schema(value_type = BookingStatus)]
     pub status: String,
+    pub lesson_duration_minutes: i64,
+    pub weather_minimum_override: Option<WeatherMinimumOverride>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RescheduleEventResponse {
+    pub id: String,
+    pub booking_id: String,
+    pub original_date: DateTime<Utc>,
+    pub new_date: DateTime<Utc>,
+    pub suggested_by: String,
+    pub reason: Option<String>,
+    pub ai_suggestions: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RescheduleEvent> for RescheduleEventResponse {
+    fn from(event: RescheduleEvent) -> Self {
+        Self {
+            id: event.id,
+            booking_id: event.booking_id,
+            original_date: event.original_date,
+            new_date: event.new_date,
+            suggested_by: event.suggested_by,
+            reason: event.reason,
+            ai_suggestions: event.ai_suggestions.and_then(|json| serde_json::from_str(&json).ok()),
+            created_at: event.created_at,
+        }
+    }
 }
 
 impl From<Booking> for BookingResponse {
@@ -48,48 +142,138 @@ impl From<Booking> for BookingResponse {
         Self {
             id: booking.id,
             student_id: booking.student_id,
+            instructor_id: booking.instructor_id,
+            aircraft_id: booking.aircraft_id,
             aircraft_type: booking.aircraft_type,
             scheduled_date: booking.scheduled_date,
             departure_location: booking.departure_location,
             status: booking.status.as_str().to_string(),
+            lesson_duration_minutes: booking.lesson_duration_minutes,
+            weather_minimum_override: booking.weather_minimum_override.map(|overrides| overrides.0),
+            created_at: booking.created_at,
+            updated_at: booking.updated_at,
+            deleted_at: booking.deleted_at,
         }
     }
 }
 
+/// GET /api/bookings - Bookings, newest first
+/// Query params:
+/// - student_id, status: only bookings for this student / in this status
+/// - from, to: RFC 3339 bounds on the scheduled date (inclusive)
+/// - include_deleted: bool (optional) - include soft-deleted bookings; admin keys only
+/// - page, limit: pagination (limit clamped to 1..=100)
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings",
+    tag = "bookings",
+    params(BookingFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of bookings, newest first", body = PaginatedBookings),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 403, description = "include_deleted requires an admin API key", body = ApiError),
+    )
+)]
 pub async fn list_bookings(
+    Query(filter): Query<BookingFilter>,
     Query(params): Query<PaginationParams>,
     State(state): State<AppState>,
-) -> ApiResult<Json<Vec<BookingResponse>>> {
+    Extension(role): Extension<ApiRole>,
+) -> ApiResult<Json<Paginated<BookingResponse>>> {
     tracing::debug!("Starting list_bookings");
+    authorize_include_deleted(filter.include_deleted, role)?;
 
-    // Validate and sanitize pagination parameters
-    let page = params.page.max(1);
-    let limit = params.limit.clamp(1, 100); // Max 100 items per page
-    let offset = (page - 1) * limit;
+    let bookings = fetch_bookings_page(&state.db, &filter, &params).await?;
 
-    let bookings = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status
-         FROM bookings
-         ORDER BY scheduled_date DESC
-         LIMIT ? OFFSET ?"
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&state.db)
-    .await?;
+    tracing::debug!(
+        "Retrieved {} of {} bookings (page={}, limit={})",
+        bookings.items.len(),
+        bookings.total,
+        bookings.page,
+        bookings.limit
+    );
+    Ok(Json(bookings.map(BookingResponse::from)))
+}
+
+async fn fetch_bookings_page(
+    db: &SqlitePool,
+    filter: &BookingFilter,
+    params: &PaginationParams,
+) -> ApiResult<Paginated<Booking>> {
+    let status = filter.validate()?;
 
-    tracing::debug!("Retrieved {} bookings (page={}, limit={})", bookings.len(), page, limit);
-    Ok(Json(bookings.into_iter().map(BookingResponse::from).collect()))
+    // Validate and sanitize pagination parameters (max 100 items per page)
+    let (page, limit, offset) = params.clamped();
+
+    let mut query: QueryBuilder<Sqlite> =
+        QueryBuilder::new(format!("SELECT {} FROM bookings", BOOKING_COLUMNS));
+    push_booking_filters(&mut query, filter, status);
+    query
+        .push(" ORDER BY scheduled_date DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let bookings = query.build_query_as::<Booking>().fetch_all(db).await?;
+
+    let mut count: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT COUNT(*) FROM bookings");
+    push_booking_filters(&mut count, filter, status);
+    let total: i64 = count.build_query_scalar().fetch_one(db).await?;
+
+    Ok(Paginated::new(bookings, page, limit, total))
 }
 
+fn push_booking_filters<'a>(
+    query: &mut QueryBuilder<'a, Sqlite>,
+    filter: &'a BookingFilter,
+    status: Option<BookingStatus>,
+) {
+    query.push(" WHERE 1 = 1");
+
+    if !filter.include_deleted {
+        query.push(" AND deleted_at IS NULL");
+    }
+
+    if let Some(student_id) = &filter.student_id {
+        query.push(" AND student_id = ").push_bind(student_id);
+    }
+    if let Some(status) = status {
+        query.push(" AND status = ").push_bind(status.as_str());
+    }
+    if let Some(from) = filter.from {
+        query.push(" AND scheduled_date >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(" AND scheduled_date <= ").push_bind(to);
+    }
+}
+
+/// GET /api/bookings/:id
+/// Soft-deleted bookings are only returned with `?include_deleted=true` from
+/// an admin key
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings/{id}",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id"), IncludeDeletedParams),
+    responses(
+        (status = 200, description = "The booking", body = BookingResponse),
+        (status = 403, description = "include_deleted requires an admin API key", body = ApiError),
+        (status = 404, description = "Booking not found", body = ApiError),
+    )
+)]
 pub async fn get_booking(
     Path(id): Path<String>,
+    Query(params): Query<IncludeDeletedParams>,
     State(state): State<AppState>,
+    Extension(role): Extension<ApiRole>,
 ) -> ApiResult<Json<BookingResponse>> {
-    let booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
-    )
+    authorize_include_deleted(params.include_deleted, role)?;
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND (deleted_at IS NULL OR ?)",
+        BOOKING_COLUMNS
+    ))
     .bind(&id)
+    .bind(params.include_deleted)
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| crate::error::ApiError::not_found("Booking"))?;
@@ -97,63 +281,431 @@ pub async fn get_booking(
     Ok(Json(booking.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/bookings",
+    tag = "bookings",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key return the original booking")),
+    request_body = CreateBookingRequest,
+    responses(
+        (status = 201, description = "Booking created", body = BookingResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Student, instructor or aircraft not found", body = ApiError),
+        (status = 409, description = "Overlaps another booking, or Idempotency-Key reused with a different body", body = ApiError),
+    )
+)]
 pub async fn create_booking(
     State(state): State<AppState>,
-    Json(req): Json<CreateBookingRequest>,
+    headers: HeaderMap,
+    ApiJson(req): ApiJson<CreateBookingRequest>,
 ) -> ApiResult<(StatusCode, Json<BookingResponse>)> {
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| ApiError::bad_request("Idempotency-Key must be visible ASCII"))
+        })
+        .transpose()?;
+
+    let booking = match idempotency_key {
+        Some(key) => insert_booking_idempotent(&state.db, key, &req).await?,
+        None => insert_booking(&state.db, &req).await?,
+    };
+
+    tracing::info!("Created booking {} for student {}", booking.id, booking.student_id);
+    Ok((StatusCode::CREATED, Json(booking.into())))
+}
+
+/// Header a client sets to make retrying `POST /api/bookings` safe
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// How long a key is remembered; a reuse after this creates a new booking
+const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// Create a booking once per idempotency key. A repeat of the same request
+/// within `IDEMPOTENCY_KEY_TTL_HOURS` returns the booking the first call
+/// created; the same key with a different body is a conflict.
+async fn insert_booking_idempotent(db: &SqlitePool, key: &str, req: &CreateBookingRequest) -> ApiResult<Booking> {
+    let request_hash = hex::encode(Sha256::digest(serde_json::to_vec(req)?));
+    let mut tx = db.begin().await?;
+
+    let previous: Option<(String, String)> = sqlx::query_as(
+        "SELECT request_hash, booking_id FROM idempotency_keys WHERE key = ? AND created_at > ?"
+    )
+    .bind(key)
+    .bind(Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS))
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some((previous_hash, booking_id)) = previous {
+        if previous_hash != request_hash {
+            return Err(ApiError::conflict(
+                "Idempotency-Key was already used with a different request body",
+            ));
+        }
+
+        tracing::info!("Replaying booking {} for idempotency key {}", booking_id, key);
+        let booking = sqlx::query_as::<_, Booking>(&format!(
+            "SELECT {} FROM bookings WHERE id = ?",
+            BOOKING_COLUMNS
+        ))
+        .bind(&booking_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        return Ok(booking);
+    }
+
+    let booking = insert_booking_on(&mut tx, req).await?;
+
+    // Replaces an expired entry for the same key, if any
+    sqlx::query(
+        "INSERT OR REPLACE INTO idempotency_keys (key, request_hash, booking_id, created_at) VALUES (?, ?, ?, ?)"
+    )
+    .bind(key)
+    .bind(&request_hash)
+    .bind(&booking.id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(booking)
+}
+
+/// Largest batch accepted by the bulk import endpoint
+const MAX_BULK_BOOKINGS: usize = 500;
+
+/// Longest lesson a booking may hold. Also bounds how far back the overlap
+/// check has to look for a booking that could still be running.
+const MAX_LESSON_DURATION_MINUTES: i64 = 24 * 60;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BulkImportParams {
+    /// Keep the valid rows when some fail instead of rolling back the batch
+    #[serde(default)]
+    pub partial: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkImportResponse {
+    pub created: usize,
+    pub errors: Vec<BulkImportError>,
+}
+
+/// Why the row at `index` of the submitted array was rejected
+#[derive(Debug, Serialize)]
+pub struct BulkImportError {
+    pub index: usize,
+    pub message: String,
+}
+
+/// POST /api/bookings/bulk - Import an array of bookings in one transaction
+/// By default any invalid row rolls back the whole batch (422); with
+/// `?partial=true` the valid rows are kept. Every invalid row is reported
+/// either way.
+#[utoipa::path(
+    post,
+    path = "/api/v1/bookings/bulk",
+    tag = "bookings",
+    params(("partial" = Option<bool>, Query, description = "Keep valid rows when some fail")),
+    request_body = Vec<CreateBookingRequest>,
+    responses(
+        (status = 201, description = "Bookings imported, with per-row errors"),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn bulk_create_bookings(
+    Query(params): Query<BulkImportParams>,
+    State(state): State<AppState>,
+    ApiJson(requests): ApiJson<Vec<CreateBookingRequest>>,
+) -> ApiResult<(StatusCode, Json<BulkImportResponse>)> {
+    if requests.len() > MAX_BULK_BOOKINGS {
+        return Err(ApiError::validation_error(format!(
+            "Too many bookings: {}. At most {} may be imported at once",
+            requests.len(),
+            MAX_BULK_BOOKINGS
+        )));
+    }
+
+    let summary = import_bookings(&state.db, &requests, params.partial).await?;
+    let status = match (summary.errors.is_empty(), params.partial) {
+        (true, _) => StatusCode::CREATED,
+        (false, true) => StatusCode::OK,
+        (false, false) => StatusCode::UNPROCESSABLE_ENTITY,
+    };
+
+    tracing::info!(
+        "Bulk import: {} of {} bookings created, {} rejected",
+        summary.created,
+        requests.len(),
+        summary.errors.len()
+    );
+    Ok((status, Json(summary)))
+}
+
+async fn import_bookings(
+    db: &SqlitePool,
+    requests: &[CreateBookingRequest],
+    partial: bool,
+) -> ApiResult<BulkImportResponse> {
+    let mut tx = db.begin().await?;
+    let mut created = 0;
+    let mut errors = Vec::new();
+
+    // Rows share the transaction, so later rows are overlap-checked against
+    // earlier ones in the same batch
+    for (index, req) in requests.iter().enumerate() {
+        match insert_booking_on(&mut tx, req).await {
+            Ok(_) => created += 1,
+            Err(e) => errors.push(BulkImportError {
+                index,
+                message: e.error.message,
+            }),
+        }
+    }
+
+    if errors.is_empty() || partial {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+        created = 0;
+    }
+
+    Ok(BulkImportResponse { created, errors })
+}
+
+pub(crate) async fn insert_booking(db: &SqlitePool, req: &CreateBookingRequest) -> ApiResult<Booking> {
+    let mut conn = db.acquire().await?;
+    insert_booking_on(&mut conn, req).await
+}
+
+/// Validate a booking request and insert it as SCHEDULED, on a connection
+/// that may be inside a transaction
+async fn insert_booking_on(conn: &mut SqliteConnection, req: &CreateBookingRequest) -> ApiResult<Booking> {
+    if req.aircraft_id.is_none() && req.aircraft_type.trim().is_empty() {
+        return Err(ApiError::validation_error("Aircraft type cannot be empty"));
+    }
+
+    if req.scheduled_date <= Utc::now() {
+        return Err(ApiError::validation_error("Scheduled date must be in the future"));
+    }
+
+    let location = &req.departure_location;
+    if !(-90.0..=90.0).contains(&location.lat) {
+        return Err(ApiError::validation_error(format!(
+            "Invalid latitude: {}. Must be between -90 and 90",
+            location.lat
+        )));
+    }
+    if !(-180.0..=180.0).contains(&location.lon) {
+        return Err(ApiError::validation_error(format!(
+            "Invalid longitude: {}. Must be between -180 and 180",
+            location.lon
+        )));
+    }
+
+    let lesson_duration_minutes = req
+        .lesson_duration_minutes
+        .unwrap_or_else(|| lesson_duration_from_env().num_minutes());
+    if lesson_duration_minutes <= 0 {
+        return Err(ApiError::validation_error("Lesson duration must be positive"));
+    }
+    if lesson_duration_minutes > MAX_LESSON_DURATION_MINUTES {
+        return Err(ApiError::validation_error(format!(
+            "Lesson duration cannot exceed {} minutes",
+            MAX_LESSON_DURATION_MINUTES
+        )));
+    }
+
+    validate_minimum_override(req.weather_minimum_override.as_ref())?;
+
+    let student_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM students WHERE id = ? AND deleted_at IS NULL)")
+        .bind(&req.student_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    if !student_exists {
+        return Err(ApiError::not_found("Student"));
+    }
+
+    if let Some(instructor_id) = &req.instructor_id {
+        fetch_instructor(&mut *conn, instructor_id).await?;
+    }
+
+    let aircraft_type = match &req.aircraft_id {
+        Some(aircraft_id) => fetch_aircraft(&mut *conn, aircraft_id).await?.model,
+        None => req.aircraft_type.clone(),
+    };
+
+    ensure_no_overlap(&mut *conn, req.scheduled_date, lesson_duration_minutes, None).await?;
+
     // Generate UUID
     let id = uuid::Uuid::new_v4().to_string();
 
     // Serialize location to JSON
-    let location_json = serde_json::to_string(&req.departure_location)?;
+    let location_json = serde_json::to_string(location)?;
 
     // Insert booking
     sqlx::query(
-        "INSERT INTO bookings (id, student_id, aircraft_type, scheduled_date, departure_location, status) VALUES (?, ?, ?, ?, ?, ?)"
+        "INSERT INTO bookings (id, student_id, instructor_id, aircraft_id, aircraft_type, scheduled_date, departure_location, status,
+         lesson_duration_minutes, weather_minimum_override)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&id)
     .bind(&req.student_id)
-    .bind(&req.aircraft_type)
-    .bind(&req.scheduled_date)
+    .bind(&req.instructor_id)
+    .bind(&req.aircraft_id)
+    .bind(&aircraft_type)
+    .bind(req.scheduled_date)
     .bind(&location_json)
     .bind(BookingStatus::Scheduled.as_str())
-    .execute(&state.db)
+    .bind(lesson_duration_minutes)
+    .bind(req.weather_minimum_override.clone().map(SqlJson))
+    .execute(&mut *conn)
     .await?;
 
     // Fetch created booking
-    let booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
-    )
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
     .bind(&id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *conn)
     .await?;
 
-    tracing::info!("Created booking {} for student {}", booking.id, booking.student_id);
-    Ok((StatusCode::CREATED, Json(booking.into())))
+    Ok(booking)
+}
+
+fn validate_minimum_override(overrides: Option<&WeatherMinimumOverride>) -> ApiResult<()> {
+    match overrides {
+        Some(overrides) => overrides.validate().map_err(ApiError::validation_error),
+        None => Ok(()),
+    }
+}
+
+/// Upcoming lessons taught by the booking's instructor, including held ones
+/// that may still fly; empty when no instructor is assigned
+pub(crate) async fn fetch_instructor_schedule(db: &SqlitePool, booking: &Booking) -> sqlx::Result<Vec<Booking>> {
+    let Some(instructor_id) = &booking.instructor_id else {
+        return Ok(vec![]);
+    };
+
+    sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {}
+         FROM bookings
+         WHERE instructor_id = ? AND status IN ('SCHEDULED', 'WEATHER_HOLD')
+         AND scheduled_date > ? AND deleted_at IS NULL
+         ORDER BY scheduled_date ASC
+         LIMIT 50",
+        BOOKING_COLUMNS
+    ))
+    .bind(instructor_id)
+    .bind(Utc::now())
+    .fetch_all(db)
+    .await
+}
+
+/// Reject a lesson whose interval intersects another non-cancelled booking's
+/// (see `booking_interval`); `exclude_id` skips the booking being moved
+async fn ensure_no_overlap<'e, E>(
+    db: E,
+    scheduled_date: DateTime<Utc>,
+    lesson_duration_minutes: i64,
+    exclude_id: Option<&str>,
+) -> ApiResult<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let end = scheduled_date + chrono::Duration::minutes(lesson_duration_minutes);
+
+    // The raw column bounds let the scheduled_date index narrow the scan to
+    // bookings that could overlap. They're compared as text, which can't tell
+    // apart timestamps written in different formats on the same day, so each
+    // side is padded by a day and the exact check follows.
+    let earliest_start = scheduled_date - chrono::Duration::minutes(MAX_LESSON_DURATION_MINUTES + 24 * 60);
+    let latest_start = end + chrono::Duration::days(1);
+
+    // Compared as whole epoch seconds so back-to-back lessons don't collide
+    let conflicting: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM bookings
+         WHERE scheduled_date > ? AND scheduled_date < ?
+         AND status != 'CANCELLED'
+         AND deleted_at IS NULL
+         AND id != ?
+         AND CAST(strftime('%s', scheduled_date) AS INTEGER) < CAST(strftime('%s', ?) AS INTEGER)
+         AND CAST(strftime('%s', scheduled_date) AS INTEGER) + lesson_duration_minutes * 60
+             > CAST(strftime('%s', ?) AS INTEGER)
+         ORDER BY scheduled_date
+         LIMIT 1"
+    )
+    .bind(earliest_start)
+    .bind(latest_start)
+    .bind(exclude_id.unwrap_or_default())
+    .bind(end)
+    .bind(scheduled_date)
+    .fetch_optional(db)
+    .await?;
+
+    match conflicting {
+        Some(booking_id) => Err(ApiError::with_details(
+            "CONFLICT",
+            "Requested time overlaps an existing booking",
+            booking_id,
+        )),
+        None => Ok(()),
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct RescheduleOptionsResponse {
     pub options: Vec<RescheduleOption>,
+    /// False when no forecast could be fetched, so the options were not
+    /// checked against the weather
+    pub weather_available: bool,
+    /// Why the options are degraded, suitable for showing to the user
+    pub warning: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RescheduleRequest {
     pub new_scheduled_date: DateTime<Utc>,
+    /// Replaces the booking's minimums override; omitted keeps the current
+    /// one and `null` removes it
+    #[serde(default, deserialize_with = "nullable")]
+    pub weather_minimum_override: Option<Option<WeatherMinimumOverride>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RescheduleSuggestionsParams {
+    /// Skip cached options and generate new ones
+    #[serde(default)]
+    pub refresh: bool,
 }
 
 /// GET /api/bookings/:id/reschedule-suggestions
 /// Returns 3 AI-generated reschedule options
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings/{id}/reschedule-suggestions",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id"), ("refresh" = Option<bool>, Query, description = "Skip cached suggestions")),
+    responses(
+        (status = 200, description = "Suggested reschedule times"),
+        (status = 404, description = "Booking or student not found", body = ApiError),
+    )
+)]
 pub async fn get_reschedule_suggestions(
     Path(id): Path<String>,
+    Query(params): Query<RescheduleSuggestionsParams>,
     State(state): State<AppState>,
 ) -> ApiResult<Json<RescheduleOptionsResponse>> {
     tracing::debug!("Starting reschedule suggestions for booking {}", id);
 
     // Fetch the booking
-    let booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
-    )
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
     .bind(&id)
     .fetch_optional(&state.db)
     .await?
@@ -161,124 +713,1503 @@ pub async fn get_reschedule_suggestions(
 
     tracing::debug!("Found booking: {:?}", booking.id);
 
-    // Fetch the student
-    let student = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
-    )
+    // Without a forecast the options are still returned, but flagged so the
+    // user knows they were not checked against the weather
+    let weather_forecast = state.weather_client
+        .fetch_location_forecast(&booking.departure_location)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch weather forecast: {}", e);
+            vec![]
+        });
+    let weather_available = !weather_forecast.is_empty();
+
+    let options = suggest_options(&state, &booking, &weather_forecast, params.refresh).await?;
+
+    let warning = (!weather_available).then(|| {
+        "Weather forecast is unavailable, so these options have not been checked against the weather".to_string()
+    });
+
+    Ok(Json(RescheduleOptionsResponse { options, weather_available, warning }))
+}
+
+/// Reschedule options for one booking against an already fetched forecast.
+/// `refresh` skips cached options; newly generated ones are recorded in the
+/// booking's history.
+async fn suggest_options(
+    state: &AppState,
+    booking: &Booking,
+    weather_forecast: &[WeatherData],
+    refresh: bool,
+) -> ApiResult<Vec<RescheduleOption>> {
+    if !refresh {
+        if let Some(cached) = state.ai_client.cached_reschedule_options(booking, DEFAULT_RESCHEDULE_OPTIONS).await {
+            // Recorded when they were generated
+            return Ok(cached);
+        }
+    }
+
+    let student = sqlx::query_as::<_, Student>(&format!(
+        "SELECT {} FROM students WHERE id = ?",
+        STUDENT_COLUMNS
+    ))
     .bind(&booking.student_id)
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| crate::error::ApiError::not_found("Student"))?;
 
-    // Fetch weather forecast using client from AppState
-    let weather_forecast = state.weather_client
-        .fetch_forecast(
-            booking.departure_location.lat,
-            booking.departure_location.lon
-        )
+    // Only this booking's instructor's lessons affect availability
+    let instructor_schedule = fetch_instructor_schedule(&state.db, booking)
         .await
         .unwrap_or_else(|e| {
-            tracing::warn!("Failed to fetch weather forecast: {}", e);
+            tracing::warn!("Failed to fetch instructor schedule: {}", e);
             vec![]
         });
 
-    // Fetch instructor schedule (other bookings to determine availability)
-    let instructor_schedule = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status
-         FROM bookings
-         WHERE status = 'SCHEDULED' AND scheduled_date > datetime('now')
-         ORDER BY scheduled_date ASC
-         LIMIT 50"
+    let student_minimums = load_flight_minimums(
+        &state.db,
+        student.training_level,
+        booking.aircraft_id.as_deref(),
+        booking.weather_minimum_override.as_deref(),
     )
-    .fetch_all(&state.db)
-    .await
-    .unwrap_or_else(|e| {
-        tracing::warn!("Failed to fetch instructor schedule: {}", e);
-        vec![]
-    });
+    .await?;
 
     // Generate reschedule options using AI
     let options = state
         .ai_client
-        .generate_reschedule_options(&booking, &student, &weather_forecast, &instructor_schedule)
+        .refresh_reschedule_options(
+            booking,
+            &student,
+            weather_forecast,
+            &instructor_schedule,
+            &student_minimums,
+            DEFAULT_RESCHEDULE_OPTIONS,
+        )
+        .await?;
+
+    if let Err(e) = record_offered_options(&state.db, booking, &options).await {
+        tracing::error!("Failed to record reschedule options for booking {}: {}", booking.id, e);
+    }
+
+    Ok(options)
+}
+
+/// Largest number of bookings in one batch suggestions request
+const MAX_BATCH_SUGGESTIONS: usize = 100;
+
+/// Forecast fetches and option generations in flight at once during a batch;
+/// each generation may call the AI provider
+const BATCH_SUGGESTION_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BatchSuggestionsRequest {
+    /// Bookings to suggest new times for; omitted means every booking on
+    /// weather hold
+    #[serde(default)]
+    pub booking_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct BatchSuggestionsResponse {
+    /// Reschedule options by booking id
+    pub suggestions: BTreeMap<String, Vec<RescheduleOption>>,
+    /// Bookings that got no options, with the reason
+    pub failed: BTreeMap<String, String>,
+}
+
+/// POST /api/reschedule-suggestions/batch
+/// Reschedule options for several bookings at once, by default every booking
+/// on weather hold. Forecasts are fetched once per departure location and
+/// cached suggestions are reused, as for a single booking.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reschedule-suggestions/batch",
+    tag = "bookings",
+    request_body = BatchSuggestionsRequest,
+    responses(
+        (status = 200, description = "Suggested reschedule times by booking id, with per-booking failures", body = BatchSuggestionsResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn batch_reschedule_suggestions(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<BatchSuggestionsRequest>,
+) -> ApiResult<Json<BatchSuggestionsResponse>> {
+    if let Some(ids) = &req.booking_ids {
+        if ids.len() > MAX_BATCH_SUGGESTIONS {
+            return Err(ApiError::validation_error(format!(
+                "Too many bookings: {}. At most {} may be requested at once",
+                ids.len(),
+                MAX_BATCH_SUGGESTIONS
+            )));
+        }
+    }
+
+    let response = generate_batch_suggestions(&state, req.booking_ids.as_deref()).await?;
+
+    tracing::info!(
+        "Batch reschedule suggestions: {} bookings answered, {} failed",
+        response.suggestions.len(),
+        response.failed.len()
+    );
+    Ok(Json(response))
+}
+
+async fn generate_batch_suggestions(
+    state: &AppState,
+    booking_ids: Option<&[String]>,
+) -> ApiResult<BatchSuggestionsResponse> {
+    let mut response = BatchSuggestionsResponse::default();
+
+    let bookings = match booking_ids {
+        Some(ids) => {
+            let mut unique: Vec<&String> = Vec::new();
+            for id in ids {
+                if !unique.contains(&id) {
+                    unique.push(id);
+                }
+            }
+
+            let mut bookings = Vec::new();
+            for id in unique {
+                let booking = sqlx::query_as::<_, Booking>(&format!(
+                    "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+                    BOOKING_COLUMNS
+                ))
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+                match booking {
+                    Some(booking) => bookings.push(booking),
+                    None => {
+                        response.failed.insert(id.clone(), "Booking not found".to_string());
+                    }
+                }
+            }
+            bookings
+        }
+        None => {
+            sqlx::query_as::<_, Booking>(&format!(
+                "SELECT {} FROM bookings WHERE status = ? AND deleted_at IS NULL ORDER BY scheduled_date",
+                BOOKING_COLUMNS
+            ))
+            .bind(BookingStatus::WeatherHold.as_str())
+            .fetch_all(&state.db)
+            .await?
+        }
+    };
+
+    // One forecast per departure location; as for a single booking, options
+    // are still generated when it can't be fetched
+    let mut locations: Vec<(String, Location)> = Vec::new();
+    for booking in &bookings {
+        let key = location_key(&booking.departure_location);
+        if !locations.iter().any(|(k, _)| *k == key) {
+            locations.push((key, booking.departure_location.clone()));
+        }
+    }
+    let forecasts: HashMap<String, Vec<WeatherData>> = stream::iter(locations)
+        .map(|(key, location)| async move {
+            let forecast = state.weather_client.fetch_location_forecast(&location).await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to fetch weather forecast for {}: {}", key, e);
+                vec![]
+            });
+            (key, forecast)
+        })
+        .buffer_unordered(BATCH_SUGGESTION_CONCURRENCY)
+        .collect()
+        .await;
+
+    let jobs: Vec<(Booking, Vec<WeatherData>)> = bookings
+        .into_iter()
+        .map(|booking| {
+            let forecast = forecasts[&location_key(&booking.departure_location)].clone();
+            (booking, forecast)
+        })
+        .collect();
+    let results: Vec<(String, ApiResult<Vec<RescheduleOption>>)> = stream::iter(jobs)
+        .map(|(booking, forecast)| async move {
+            let result = suggest_options(state, &booking, &forecast, false).await;
+            (booking.id, result)
+        })
+        .buffer_unordered(BATCH_SUGGESTION_CONCURRENCY)
+        .collect()
+        .await;
+
+    for (id, result) in results {
+        match result {
+            Ok(options) => {
+                response.suggestions.insert(id, options);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to suggest reschedule options for booking {}: {}", id, e.error.message);
+                response.failed.insert(id, e.error.message);
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+fn location_key(location: &Location) -> String {
+    format!("{},{}", location.lat, location.lon)
+}
+
+/// Store the options offered for a booking in its reschedule history,
+/// returning the new event's id, or `None` when the offer is unchanged.
+///
+/// This happens when the options are generated, not when a reschedule is
+/// committed: the student may pick one, choose a time of their own or never
+/// reschedule at all, and the history should show what was offered in every
+/// case. Options served from the cache were recorded when generated, and an
+/// offer identical to the last one recorded is skipped, so polling for
+/// suggestions doesn't grow the history; it shows how the offer changed as
+/// the forecast did.
+async fn record_offered_options(
+    db: &SqlitePool,
+    booking: &Booking,
+    options: &[RescheduleOption],
+) -> anyhow::Result<Option<String>> {
+    let suggestions = serde_json::to_string(options)?;
+
+    let last_offer: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT ai_suggestions FROM reschedule_events
+         WHERE booking_id = ? AND suggested_by = ?
+         ORDER BY created_at DESC LIMIT 1"
+    )
+    .bind(&booking.id)
+    .bind(SUGGESTED_BY_AI)
+    .fetch_optional(db)
+    .await?;
+    if last_offer.flatten().as_deref() == Some(suggestions.as_str()) {
+        return Ok(None);
+    }
+
+    let id = RescheduleEvent::record_with_suggestions(
+        db,
+        &booking.id,
+        booking.scheduled_date,
+        booking.scheduled_date,
+        SUGGESTED_BY_AI,
+        Some("Reschedule options offered"),
+        Some(&suggestions),
+    )
+    .await?;
+    Ok(Some(id))
+}
+
+/// GET /api/bookings/:id/reschedule-history
+/// Audit trail of cancellations and reschedules for a booking, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings/{id}/reschedule-history",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id")),
+    responses(
+        (status = 200, description = "Reschedule events, oldest first"),
+        (status = 404, description = "Booking not found", body = ApiError),
+    )
+)]
+pub async fn get_reschedule_history(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<RescheduleEventResponse>>> {
+    let events = fetch_reschedule_history(&state.db, &id).await?;
+
+    tracing::debug!("Retrieved {} reschedule events for booking {}", events.len(), id);
+    Ok(Json(events.into_iter().map(RescheduleEventResponse::from).collect()))
+}
+
+async fn fetch_reschedule_history(db: &SqlitePool, booking_id: &str) -> ApiResult<Vec<RescheduleEvent>> {
+    let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM bookings WHERE id = ? AND deleted_at IS NULL")
+        .bind(booking_id)
+        .fetch_optional(db)
         .await?;
+    if exists.is_none() {
+        return Err(ApiError::not_found("Booking"));
+    }
 
-    Ok(Json(RescheduleOptionsResponse { options }))
+    let events = sqlx::query_as::<_, RescheduleEvent>(
+        "SELECT id, booking_id, original_date, new_date, suggested_by, ai_suggestions, reason, created_at
+         FROM reschedule_events
+         WHERE booking_id = ?
+         ORDER BY created_at ASC"
+    )
+    .bind(booking_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(events)
 }
 
 /// PATCH /api/bookings/:id/reschedule
 /// Actually reschedules the booking with the selected option
+#[utoipa::path(
+    patch,
+    path = "/api/v1/bookings/{id}/reschedule",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id")),
+    responses(
+        (status = 200, description = "Rescheduled booking", body = BookingResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Booking not found", body = ApiError),
+        (status = 409, description = "Overlaps another booking", body = ApiError),
+    )
+)]
 pub async fn reschedule_booking(
     Path(id): Path<String>,
     State(state): State<AppState>,
-    Json(req): Json<RescheduleRequest>,
+    ApiJson(req): ApiJson<RescheduleRequest>,
 ) -> ApiResult<Json<BookingResponse>> {
     // Fetch the booking
-    let booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
-    )
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
     .bind(&id)
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| crate::error::ApiError::not_found("Booking"))?;
 
     // Fetch the student for notification
-    let student = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
-    )
+    let student = sqlx::query_as::<_, Student>(&format!(
+        "SELECT {} FROM students WHERE id = ?",
+        STUDENT_COLUMNS
+    ))
     .bind(&booking.student_id)
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| crate::error::ApiError::not_found("Student"))?;
 
+    let overrides = req.weather_minimum_override.as_ref().map(Option::as_ref);
+    validate_minimum_override(overrides.flatten())?;
+
+    apply_reschedule(&state.db, &booking, req.new_scheduled_date, overrides).await?;
+
+    // Notify via WebSocket
+    let notification = Notification::BookingRescheduled {
+        booking_id: id.clone(),
+        student_id: booking.student_id.clone(),
+        student_name: student.name.clone(),
+        old_date: booking.scheduled_date,
+        new_date: req.new_scheduled_date,
+    };
+
+    let _ = state.notification_tx.send(serde_json::to_string(&notification)?);
+
+    // Fetch updated booking
+    let updated_booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
+    .bind(&id)
+    .fetch_one(&state.db)
+    .await?;
+
+    tracing::info!("Rescheduled booking {} from {} to {}", id, booking.scheduled_date, req.new_scheduled_date);
+    Ok(Json(updated_booking.into()))
+}
+
+/// Move a booking to `new_date` and record the reschedule event. `overrides`
+/// of `Some` replaces the minimums override (`Some(None)` removes it); `None`
+/// keeps the current one.
+pub(crate) async fn apply_reschedule(
+    db: &SqlitePool,
+    booking: &Booking,
+    new_date: DateTime<Utc>,
+    overrides: Option<Option<&WeatherMinimumOverride>>,
+) -> ApiResult<()> {
+    ensure_no_overlap(db, new_date, booking.lesson_duration_minutes, Some(&booking.id)).await?;
+
+    let overrides = overrides.unwrap_or(booking.weather_minimum_override.as_deref());
+
     // Update booking with new date
     sqlx::query(
-        "UPDATE bookings SET scheduled_date = ?, status = ? WHERE id = ?"
+        "UPDATE bookings SET scheduled_date = ?, status = ?,
+         weather_minimum_override = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
     )
-    .bind(&req.new_scheduled_date)
+    .bind(new_date)
     .bind(BookingStatus::Rescheduled.as_str())
-    .bind(&id)
-    .execute(&state.db)
+    .bind(overrides.cloned().map(SqlJson))
+    .bind(&booking.id)
+    .execute(db)
     .await?;
 
     // Log reschedule event
-    let reschedule_event_id = uuid::Uuid::new_v4().to_string();
-    if let Err(e) = sqlx::query(
-        "INSERT INTO reschedule_events (id, booking_id, old_date, new_date, reason, created_at)
-         VALUES (?, ?, ?, ?, ?, datetime('now'))"
+    if let Err(e) = RescheduleEvent::record(
+        db,
+        &booking.id,
+        booking.scheduled_date,
+        new_date,
+        SUGGESTED_BY_USER,
+        Some("User requested reschedule"),
     )
-    .bind(&reschedule_event_id)
-    .bind(&id)
-    .bind(&booking.scheduled_date)
-    .bind(&req.new_scheduled_date)
-    .bind("User requested reschedule")
-    .execute(&state.db)
-    .await {
-        tracing::error!("Failed to log reschedule event for booking {}: {}", id, e);
+    .await
+    {
+        tracing::error!("Failed to log reschedule event for booking {}: {}", booking.id, e);
         // Continue even if audit logging fails, but log the error
     }
 
+    Ok(())
+}
+
+/// POST /api/bookings/:id/cancel
+/// Cancels a booking on behalf of the user
+#[utoipa::path(
+    post,
+    path = "/api/v1/bookings/{id}/cancel",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id")),
+    responses(
+        (status = 200, description = "Cancelled booking", body = BookingResponse),
+        (status = 404, description = "Booking not found", body = ApiError),
+        (status = 409, description = "Completed bookings cannot be cancelled", body = ApiError),
+    )
+)]
+pub async fn cancel_booking(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<BookingResponse>> {
+    // Fetch the booking
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
+    .bind(&id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| crate::error::ApiError::not_found("Booking"))?;
+
+    match booking.status {
+        BookingStatus::Completed => {
+            return Err(crate::error::ApiError::conflict("Completed bookings cannot be cancelled"));
+        }
+        BookingStatus::Cancelled => {
+            // Already cancelled, nothing to do
+            return Ok(Json(booking.into()));
+        }
+        _ => {}
+    }
+
+    let cancelled_booking =
+        cancel_for_user(&state.db, &state.notification_tx, &booking, "User cancelled booking").await?;
+    Ok(Json(cancelled_booking.into()))
+}
+
+/// Cancel a booking at the user's request, recording why and notifying
+/// dashboards. Returns the updated booking.
+async fn cancel_for_user(
+    db: &SqlitePool,
+    notification_tx: &NotificationChannel,
+    booking: &Booking,
+    reason: &str,
+) -> ApiResult<Booking> {
+    sqlx::query(
+        "UPDATE bookings SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(BookingStatus::Cancelled.as_str())
+    .bind(&booking.id)
+    .execute(db)
+    .await?;
+
+    // Log cancellation event
+    if let Err(e) = RescheduleEvent::record(
+        db,
+        &booking.id,
+        booking.scheduled_date,
+        booking.scheduled_date,
+        SUGGESTED_BY_USER,
+        Some(reason),
+    )
+    .await
+    {
+        tracing::error!("Failed to log cancellation event for booking {}: {}", booking.id, e);
+    }
+
     // Notify via WebSocket
-    let notification = serde_json::json!({
-        "type": "booking_rescheduled",
-        "booking_id": id,
-        "old_date": booking.scheduled_date,
-        "new_date": req.new_scheduled_date,
-        "student_name": student.name,
-    });
+    let notification = Notification::BookingCancelled {
+        booking_id: booking.id.clone(),
+        student_id: booking.student_id.clone(),
+        original_date: booking.scheduled_date,
+    };
 
-    let _ = state.notification_tx.send(notification.to_string());
+    let _ = notification_tx.send(serde_json::to_string(&notification)?);
 
-    // Fetch updated booking
-    let updated_booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, aircraft_type, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
+    let cancelled_booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
+    .bind(&booking.id)
+    .fetch_one(db)
+    .await?;
+
+    tracing::info!("Cancelled booking {}", booking.id);
+    Ok(cancelled_booking)
+}
+
+/// What the student wants done with a booking on weather hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HoldDecision {
+    /// Keep the booking; the hold is cleared if the forecast is safe now
+    Keep,
+    /// Give up the slot
+    Cancel,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AcknowledgeHoldRequest {
+    pub decision: HoldDecision,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AcknowledgeHoldResponse {
+    pub booking: BookingResponse,
+    /// Why the booking is still held, when the student kept it but the
+    /// forecast is still below minimums
+    pub reason: Option<String>,
+}
+
+/// POST /api/bookings/:id/acknowledge-hold
+/// Records the student's decision on a held booking. Keeping it re-runs the
+/// safety check against the latest forecast and releases the hold if the
+/// weather has improved; otherwise the booking stays held until the scheduler
+/// clears or cancels it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/bookings/{id}/acknowledge-hold",
+    tag = "bookings",
+    params(("id" = String, Path, description = "Booking id")),
+    responses(
+        (status = 200, description = "Booking after the decision"),
+        (status = 404, description = "Booking not found", body = ApiError),
+        (status = 409, description = "Booking is not on weather hold", body = ApiError),
+        (status = 502, description = "Weather provider unavailable", body = ApiError),
     )
+)]
+pub async fn acknowledge_hold(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<AcknowledgeHoldRequest>,
+) -> ApiResult<Json<AcknowledgeHoldResponse>> {
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
+    .bind(&id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Booking"))?;
+
+    if booking.status != BookingStatus::WeatherHold {
+        return Err(ApiError::conflict(format!(
+            "Booking is {}, not on weather hold",
+            booking.status.as_str()
+        )));
+    }
+
+    if req.decision == HoldDecision::Cancel {
+        let cancelled =
+            cancel_for_user(&state.db, &state.notification_tx, &booking, "Student cancelled after weather hold").await?;
+        return Ok(Json(AcknowledgeHoldResponse { booking: cancelled.into(), reason: None }));
+    }
+
+    let student = fetch_student(&state.db, &booking.student_id).await?;
+    let forecast = state
+        .weather_client
+        .fetch_location_forecast(&booking.departure_location)
+        .await
+        .map_err(|e| ApiError::external_api_error("Weather", e.to_string()))?;
+
+    let reason = match select_forecast_slot(&forecast, booking.scheduled_date) {
+        Some(weather) => {
+            let minimums = load_flight_minimums(
+                &state.db,
+                student.training_level,
+                booking.aircraft_id.as_deref(),
+                booking.weather_minimum_override.as_deref(),
+            )
+            .await?;
+            let (is_safe, violations) = is_flight_safe(
+                &student.training_level,
+                weather,
+                &minimums,
+                None,
+                booking.departure_location.elevation_ft,
+            );
+            if is_safe {
+                release_weather_hold(&state.db, &booking, &student, &state.notification_tx).await?;
+                None
+            } else {
+                join_violations(&violations)
+            }
+        }
+        None => Some("No forecast covers the flight time yet".to_string()),
+    };
+
+    if let Some(reason) = &reason {
+        // Nothing moved, so this is a status event rather than a reschedule
+        BookingStatusEvent::record(
+            &state.db,
+            &booking.id,
+            BookingStatus::WeatherHold,
+            BookingStatus::WeatherHold,
+            SUGGESTED_BY_USER,
+            Some(&format!("Student kept booking on weather hold: {}", reason)),
+        )
+        .await?;
+    }
+
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
     .bind(&id)
     .fetch_one(&state.db)
     .await?;
 
-    tracing::info!("Rescheduled booking {} from {} to {}", id, booking.scheduled_date, req.new_scheduled_date);
-    Ok(Json(updated_booking.into()))
+    tracing::info!("Weather hold acknowledged for booking {}, now {}", id, booking.status.as_str());
+    Ok(Json(AcknowledgeHoldResponse { booking: booking.into(), reason }))
 }
 
 // Add uuid dependency to server/Cargo.toml
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::test_support::{self, insert_test_student, test_state, FailingBackend};
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = test_support::setup_test_db().await;
+        insert_test_student(&pool).await;
+        pool
+    }
+
+    fn booking_request() -> CreateBookingRequest {
+        CreateBookingRequest {
+            student_id: "student1".to_string(),
+            instructor_id: None,
+            aircraft_id: None,
+            aircraft_type: "Cessna 172".to_string(),
+            scheduled_date: Utc::now() + Duration::days(1),
+            departure_location: Location {
+                lat: 33.8113,
+                lon: -118.1515,
+                name: "KTOA".to_string(),
+                elevation_ft: None,
+            },
+            lesson_duration_minutes: None,
+            weather_minimum_override: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weather_minimum_override_stored_and_validated() {
+        let db = setup_test_db().await;
+        let overrides = WeatherMinimumOverride {
+            min_visibility_sm: Some(8.0),
+            no_icing: Some(true),
+            ..Default::default()
+        };
+
+        let req = CreateBookingRequest {
+            weather_minimum_override: Some(overrides.clone()),
+            ..booking_request()
+        };
+        let booking = insert_booking(&db, &req).await.unwrap();
+        assert_eq!(booking.weather_minimum_override.as_deref(), Some(&overrides));
+
+        let req = CreateBookingRequest {
+            scheduled_date: Utc::now() + Duration::days(2),
+            weather_minimum_override: Some(WeatherMinimumOverride {
+                max_wind_speed_kt: Some(-1.0),
+                ..Default::default()
+            }),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_bookings_page_metadata() {
+        let db = setup_test_db().await;
+        for day in 1..=15 {
+            let req = CreateBookingRequest {
+                scheduled_date: Utc::now() + Duration::days(day),
+                ..booking_request()
+            };
+            insert_booking(&db, &req).await.unwrap();
+        }
+
+        let params = PaginationParams { page: 2, limit: 10 };
+        let page = fetch_bookings_page(&db, &BookingFilter::default(), &params).await.unwrap();
+
+        assert_eq!(page.items.len(), 5);
+        assert_eq!(page.total, 15);
+        assert_eq!(page.total_pages, 2);
+        assert_eq!(page.page, 2);
+    }
+
+    /// Three bookings on consecutive days; the second belongs to student2 and
+    /// the third is cancelled
+    async fn insert_filter_fixtures(db: &SqlitePool) -> Vec<Booking> {
+        sqlx::query(
+            "INSERT INTO students (id, name, email, phone, training_level)
+             VALUES ('student2', 'Jane Roe', 'jane@example.com', '+1987654321', 'PRIVATE_PILOT')"
+        )
+        .execute(db)
+        .await
+        .unwrap();
+
+        let mut bookings = Vec::new();
+        for (day, student_id) in [(1, "student1"), (2, "student2"), (3, "student1")] {
+            let req = CreateBookingRequest {
+                student_id: student_id.to_string(),
+                scheduled_date: Utc::now() + Duration::days(day),
+                ..booking_request()
+            };
+            bookings.push(insert_booking(db, &req).await.unwrap());
+        }
+
+        sqlx::query("UPDATE bookings SET status = 'CANCELLED' WHERE id = ?")
+            .bind(&bookings[2].id)
+            .execute(db)
+            .await
+            .unwrap();
+
+        bookings
+    }
+
+    #[tokio::test]
+    async fn test_filter_bookings_by_status() {
+        let db = setup_test_db().await;
+        let bookings = insert_filter_fixtures(&db).await;
+
+        let filter = BookingFilter {
+            status: Some("cancelled".to_string()),
+            ..Default::default()
+        };
+        let page = fetch_bookings_page(&db, &filter, &PaginationParams::default()).await.unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].id, bookings[2].id);
+    }
+
+    #[tokio::test]
+    async fn test_filter_bookings_by_student_and_date_range() {
+        let db = setup_test_db().await;
+        let bookings = insert_filter_fixtures(&db).await;
+
+        let filter = BookingFilter {
+            student_id: Some("student1".to_string()),
+            ..Default::default()
+        };
+        let page = fetch_bookings_page(&db, &filter, &PaginationParams::default()).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert!(page.items.iter().all(|b| b.student_id == "student1"));
+
+        let filter = BookingFilter {
+            from: Some(bookings[1].scheduled_date),
+            to: Some(bookings[2].scheduled_date),
+            ..Default::default()
+        };
+        let page = fetch_bookings_page(&db, &filter, &PaginationParams::default()).await.unwrap();
+        let ids: Vec<&str> = page.items.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec![bookings[2].id.as_str(), bookings[1].id.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_bookings_only_listed_on_request() {
+        let db = setup_test_db().await;
+        let bookings = insert_filter_fixtures(&db).await;
+
+        sqlx::query("UPDATE bookings SET deleted_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(&bookings[0].id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let page = fetch_bookings_page(&db, &BookingFilter::default(), &PaginationParams::default()).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert!(page.items.iter().all(|b| b.id != bookings[0].id));
+
+        let filter = BookingFilter {
+            include_deleted: true,
+            ..Default::default()
+        };
+        let page = fetch_bookings_page(&db, &filter, &PaginationParams::default()).await.unwrap();
+        assert_eq!(page.total, 3);
+        let deleted = page.items.iter().find(|b| b.id == bookings[0].id).unwrap();
+        assert!(deleted.deleted_at.is_some());
+
+        // Only admin keys may ask for deleted bookings
+        let state = test_state(db, weather_core::weather::MockWeatherClient::new());
+        let err = list_bookings(Query(filter), Query(PaginationParams::default()), State(state.clone()), Extension(ApiRole::Instructor))
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, "FORBIDDEN");
+        let err = get_booking(
+            Path(bookings[0].id.clone()),
+            Query(IncludeDeletedParams { include_deleted: true }),
+            State(state.clone()),
+            Extension(ApiRole::ReadOnly),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, "FORBIDDEN");
+        let Json(booking) = get_booking(
+            Path(bookings[0].id.clone()),
+            Query(IncludeDeletedParams { include_deleted: true }),
+            State(state),
+            Extension(ApiRole::Admin),
+        )
+        .await
+        .unwrap();
+        assert!(booking.deleted_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_booking_filters_rejected() {
+        let db = setup_test_db().await;
+
+        let unknown_status = BookingFilter {
+            status: Some("GROUNDED".to_string()),
+            ..Default::default()
+        };
+        let err = fetch_bookings_page(&db, &unknown_status, &PaginationParams::default()).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+
+        let inverted = BookingFilter {
+            from: Some(Utc::now() + Duration::days(2)),
+            to: Some(Utc::now()),
+            ..Default::default()
+        };
+        let err = fetch_bookings_page(&db, &inverted, &PaginationParams::default()).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_success() {
+        let db = setup_test_db().await;
+
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+
+        assert_eq!(booking.student_id, "student1");
+        assert_eq!(booking.status, BookingStatus::Scheduled);
+    }
+
+    /// Three bookings a day apart, with the middle one in the past
+    fn bulk_requests_with_bad_row() -> Vec<CreateBookingRequest> {
+        vec![
+            booking_request(),
+            CreateBookingRequest {
+                scheduled_date: Utc::now() - Duration::days(1),
+                ..booking_request()
+            },
+            CreateBookingRequest {
+                scheduled_date: Utc::now() + Duration::days(2),
+                ..booking_request()
+            },
+        ]
+    }
+
+    async fn count_bookings(db: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM bookings").fetch_one(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_all_valid() {
+        let db = setup_test_db().await;
+        let requests: Vec<_> = (1..=3)
+            .map(|day| CreateBookingRequest {
+                scheduled_date: Utc::now() + Duration::days(day),
+                ..booking_request()
+            })
+            .collect();
+
+        let summary = import_bookings(&db, &requests, false).await.unwrap();
+
+        assert_eq!(summary.created, 3);
+        assert!(summary.errors.is_empty());
+        assert_eq!(count_bookings(&db).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_bad_row_rolls_back_batch() {
+        let db = setup_test_db().await;
+
+        let summary = import_bookings(&db, &bulk_requests_with_bad_row(), false).await.unwrap();
+
+        assert_eq!(summary.created, 0);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.errors[0].index, 1);
+        assert_eq!(summary.errors[0].message, "Scheduled date must be in the future");
+        assert_eq!(count_bookings(&db).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_partial_keeps_valid_rows() {
+        let db = setup_test_db().await;
+
+        let summary = import_bookings(&db, &bulk_requests_with_bad_row(), true).await.unwrap();
+
+        assert_eq!(summary.created, 2);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.errors[0].index, 1);
+        assert_eq!(count_bookings(&db).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_unknown_student() {
+        let db = setup_test_db().await;
+        let req = CreateBookingRequest {
+            student_id: "missing".to_string(),
+            ..booking_request()
+        };
+
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_past_date() {
+        let db = setup_test_db().await;
+        let req = CreateBookingRequest {
+            scheduled_date: Utc::now() - Duration::hours(1),
+            ..booking_request()
+        };
+
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_invalid_coordinates() {
+        let db = setup_test_db().await;
+
+        let mut req = booking_request();
+        req.departure_location.lat = 91.0;
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+
+        let mut req = booking_request();
+        req.departure_location.lon = -180.5;
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_empty_aircraft_type() {
+        let db = setup_test_db().await;
+        let req = CreateBookingRequest {
+            aircraft_type: "  ".to_string(),
+            ..booking_request()
+        };
+
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_create_booking_with_fleet_aircraft() {
+        let db = setup_test_db().await;
+        sqlx::query("INSERT INTO aircraft (id, tail_number, model, ifr_capable) VALUES ('aircraft1', 'N12345', 'Piper Archer', 1)")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let req = CreateBookingRequest {
+            aircraft_id: Some("aircraft1".to_string()),
+            aircraft_type: String::new(),
+            ..booking_request()
+        };
+        let booking = insert_booking(&db, &req).await.unwrap();
+        assert_eq!(booking.aircraft_id.as_deref(), Some("aircraft1"));
+        assert_eq!(booking.aircraft_type, "Piper Archer");
+
+        let req = CreateBookingRequest {
+            aircraft_id: Some("missing".to_string()),
+            scheduled_date: Utc::now() + Duration::days(2),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_instructor_schedule_only_includes_their_bookings() {
+        let db = setup_test_db().await;
+        sqlx::query("INSERT INTO instructors (id, name, email) VALUES ('cfi1', 'Jane Smith', 'jane@example.com'), ('cfi2', 'Bob Jones', 'bob@example.com')")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let mut bookings = Vec::new();
+        for (day, instructor_id) in [(1, Some("cfi1")), (2, Some("cfi2")), (3, Some("cfi1")), (4, None)] {
+            let req = CreateBookingRequest {
+                instructor_id: instructor_id.map(str::to_string),
+                scheduled_date: Utc::now() + Duration::days(day),
+                ..booking_request()
+            };
+            bookings.push(insert_booking(&db, &req).await.unwrap());
+        }
+        // A held lesson may still fly, so it keeps blocking the instructor
+        sqlx::query("UPDATE bookings SET status = 'WEATHER_HOLD' WHERE id = ?")
+            .bind(&bookings[2].id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let schedule = fetch_instructor_schedule(&db, &bookings[0]).await.unwrap();
+        let ids: Vec<&str> = schedule.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec![bookings[0].id.as_str(), bookings[2].id.as_str()]);
+
+        assert!(fetch_instructor_schedule(&db, &bookings[3]).await.unwrap().is_empty());
+
+        let req = CreateBookingRequest {
+            instructor_id: Some("missing".to_string()),
+            scheduled_date: Utc::now() + Duration::days(5),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_create_overlapping_booking_conflicts() {
+        let db = setup_test_db().await;
+        let first = insert_booking(&db, &booking_request()).await.unwrap();
+
+        let req = CreateBookingRequest {
+            scheduled_date: first.scheduled_date + Duration::minutes(30),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+
+        assert_eq!(err.error.code, "CONFLICT");
+        assert_eq!(err.error.details.as_deref(), Some(first.id.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry_returns_original_booking() {
+        let db = setup_test_db().await;
+        let req = booking_request();
+
+        let first = insert_booking_idempotent(&db, "retry-1", &req).await.unwrap();
+        let retried = insert_booking_idempotent(&db, "retry-1", &req).await.unwrap();
+
+        assert_eq!(retried.id, first.id);
+        assert_eq!(count_bookings(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_reused_with_different_body_conflicts() {
+        let db = setup_test_db().await;
+        let req = booking_request();
+        insert_booking_idempotent(&db, "retry-2", &req).await.unwrap();
+
+        let changed = CreateBookingRequest {
+            aircraft_type: "Piper Archer".to_string(),
+            scheduled_date: req.scheduled_date + Duration::days(1),
+            ..booking_request()
+        };
+        let err = insert_booking_idempotent(&db, "retry-2", &changed).await.unwrap_err();
+
+        assert_eq!(err.error.code, "CONFLICT");
+        assert_eq!(count_bookings(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_longest_lesson_still_found_by_overlap_check() {
+        let db = setup_test_db().await;
+        let req = CreateBookingRequest {
+            lesson_duration_minutes: Some(MAX_LESSON_DURATION_MINUTES),
+            ..booking_request()
+        };
+        let long = insert_booking(&db, &req).await.unwrap();
+
+        // Starts a minute before the day-long lesson ends
+        let req = CreateBookingRequest {
+            scheduled_date: long.scheduled_date + Duration::minutes(MAX_LESSON_DURATION_MINUTES - 1),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.details.as_deref(), Some(long.id.as_str()));
+
+        let req = CreateBookingRequest {
+            scheduled_date: long.scheduled_date + Duration::days(3),
+            lesson_duration_minutes: Some(MAX_LESSON_DURATION_MINUTES + 1),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_back_to_back_bookings_allowed() {
+        let db = setup_test_db().await;
+        let first = insert_booking(&db, &booking_request()).await.unwrap();
+        assert_eq!(first.lesson_duration_minutes, 120);
+
+        // Starts the minute the first lesson ends
+        let req = CreateBookingRequest {
+            scheduled_date: first.scheduled_date + Duration::minutes(120),
+            lesson_duration_minutes: Some(60),
+            ..booking_request()
+        };
+        let second = insert_booking(&db, &req).await.unwrap();
+
+        // Ends the minute the first lesson starts
+        let req = CreateBookingRequest {
+            scheduled_date: first.scheduled_date - Duration::minutes(90),
+            lesson_duration_minutes: Some(90),
+            ..booking_request()
+        };
+        insert_booking(&db, &req).await.unwrap();
+
+        // Overlaps the last 30 minutes of the second lesson
+        let req = CreateBookingRequest {
+            scheduled_date: second.scheduled_date + Duration::minutes(30),
+            ..booking_request()
+        };
+        let err = insert_booking(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.details.as_deref(), Some(second.id.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_history_in_order() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        let new_date = booking.scheduled_date + Duration::days(2);
+
+        for (id, suggested_by, reason, offset) in [
+            ("event2", "USER", "User requested reschedule", 10),
+            ("event1", "SYSTEM", "Visibility below minimums", 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO reschedule_events (id, booking_id, original_date, new_date, suggested_by, reason, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(&booking.id)
+            .bind(booking.scheduled_date)
+            .bind(new_date)
+            .bind(suggested_by)
+            .bind(reason)
+            .bind(Utc::now() + Duration::seconds(offset))
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let history = fetch_reschedule_history(&db, &booking.id).await.unwrap();
+
+        let ids: Vec<_> = history.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["event1", "event2"]);
+        assert_eq!(history[0].reason.as_deref(), Some("Visibility below minimums"));
+        assert_eq!(history[1].suggested_by, "USER");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_booking() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        let state = test_state(db.clone(), weather_core::weather::MockWeatherClient::new());
+        let mut rx = state.notification_tx.subscribe();
+
+        let Json(cancelled) = cancel_booking(Path(booking.id.clone()), State(state.clone())).await.unwrap();
+        assert_eq!(cancelled.status, "CANCELLED");
+
+        let notification: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(notification["type"], "booking_cancelled");
+        assert_eq!(notification["booking_id"], booking.id.as_str());
+
+        let history = fetch_reschedule_history(&db, &booking.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].suggested_by, SUGGESTED_BY_USER);
+        assert_eq!(history[0].reason.as_deref(), Some("User cancelled booking"));
+
+        // Cancelling again changes nothing
+        let Json(again) = cancel_booking(Path(booking.id.clone()), State(state.clone())).await.unwrap();
+        assert_eq!(again.status, "CANCELLED");
+        assert!(rx.try_recv().is_err());
+
+        let err = cancel_booking(Path("missing".to_string()), State(state.clone())).await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+
+        let req = CreateBookingRequest {
+            scheduled_date: booking.scheduled_date + Duration::days(1),
+            ..booking_request()
+        };
+        let completed = insert_booking(&db, &req).await.unwrap();
+        sqlx::query("UPDATE bookings SET status = 'COMPLETED' WHERE id = ?")
+            .bind(&completed.id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let err = cancel_booking(Path(completed.id), State(state)).await.unwrap_err();
+        assert_eq!(err.error.code, "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_keeps_or_clears_override() {
+        let db = setup_test_db().await;
+        let overrides = WeatherMinimumOverride {
+            min_visibility_sm: Some(8.0),
+            ..Default::default()
+        };
+        let req = CreateBookingRequest {
+            weather_minimum_override: Some(overrides.clone()),
+            ..booking_request()
+        };
+        let booking = insert_booking(&db, &req).await.unwrap();
+        let reload = |id: String| {
+            let db = db.clone();
+            async move {
+                sqlx::query_as::<_, Booking>(&format!("SELECT {} FROM bookings WHERE id = ?", BOOKING_COLUMNS))
+                    .bind(id)
+                    .fetch_one(&db)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // Omitted: the override is kept
+        let req: RescheduleRequest = serde_json::from_value(serde_json::json!({
+            "new_scheduled_date": booking.scheduled_date + Duration::days(1),
+        }))
+        .unwrap();
+        assert!(req.weather_minimum_override.is_none());
+        apply_reschedule(&db, &booking, req.new_scheduled_date, None).await.unwrap();
+        let booking = reload(booking.id.clone()).await;
+        assert_eq!(booking.weather_minimum_override.as_deref(), Some(&overrides));
+
+        // Explicit null: the override is removed
+        let req: RescheduleRequest = serde_json::from_value(serde_json::json!({
+            "new_scheduled_date": booking.scheduled_date + Duration::days(1),
+            "weather_minimum_override": null,
+        }))
+        .unwrap();
+        let cleared = req.weather_minimum_override.as_ref().map(Option::as_ref);
+        assert_eq!(cleared, Some(None));
+        apply_reschedule(&db, &booking, req.new_scheduled_date, cleared).await.unwrap();
+        let booking = reload(booking.id.clone()).await;
+        assert!(booking.weather_minimum_override.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_offered_options_stored_in_history() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        let options = vec![RescheduleOption {
+            date_time: booking.scheduled_date + Duration::days(1),
+            reason: "Clear skies".to_string(),
+            weather_score: 9.0,
+            instructor_available: true,
+        }];
+
+        assert!(record_offered_options(&db, &booking, &options).await.unwrap().is_some());
+        // The same offer again isn't a new event
+        assert!(record_offered_options(&db, &booking, &options).await.unwrap().is_none());
+
+        let history = fetch_reschedule_history(&db, &booking.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].suggested_by, SUGGESTED_BY_AI);
+
+        let response = RescheduleEventResponse::from(history[0].clone());
+        assert_eq!(response.ai_suggestions, Some(serde_json::to_value(&options).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_suggestions_flag_missing_weather() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        let state = test_state(db, weather_core::weather::MockWeatherClient::new().with_forecast_error("API down"));
+
+        let Json(response) = get_reschedule_suggestions(
+            Path(booking.id),
+            Query(RescheduleSuggestionsParams::default()),
+            State(state),
+        )
+        .await
+        .unwrap();
+
+        assert!(!response.weather_available);
+        assert!(response.warning.is_some());
+        assert_eq!(response.options.len(), DEFAULT_RESCHEDULE_OPTIONS);
+    }
+
+    #[tokio::test]
+    async fn test_batch_suggestions_reuse_cache_and_share_forecast() {
+        let db = setup_test_db().await;
+        let mut held = Vec::new();
+        for day in 1..=2 {
+            let req = CreateBookingRequest {
+                scheduled_date: Utc::now() + Duration::days(day),
+                ..booking_request()
+            };
+            let booking = insert_booking(&db, &req).await.unwrap();
+            sqlx::query("UPDATE bookings SET status = ? WHERE id = ?")
+                .bind(BookingStatus::WeatherHold.as_str())
+                .bind(&booking.id)
+                .execute(&db)
+                .await
+                .unwrap();
+            held.push(booking);
+        }
+        insert_booking(&db, &CreateBookingRequest {
+            scheduled_date: Utc::now() + Duration::days(3),
+            ..booking_request()
+        })
+        .await
+        .unwrap();
+
+        // The first booking already has suggestions cached
+        let cache = std::sync::Arc::new(weather_core::ai::AiCache::with_ttl(
+            weather_core::ai::DEFAULT_AI_CACHE_TTL_HOURS,
+        ));
+        let cached = RescheduleOption {
+            date_time: held[0].scheduled_date + Duration::days(1),
+            reason: "Cached suggestion".to_string(),
+            weather_score: 9.0,
+            instructor_available: true,
+        };
+        cache
+            .set(
+                weather_core::ai::reschedule_cache_key(&held[0]),
+                weather_core::ai::RescheduleResponse { options: vec![cached; DEFAULT_RESCHEDULE_OPTIONS] },
+            )
+            .await;
+
+        let weather_client = std::sync::Arc::new(weather_core::weather::MockWeatherClient::new().with_forecast(vec![]));
+        let state = AppState {
+            ai_client: std::sync::Arc::new(weather_core::ai::AiRescheduleClient::with_backend(Box::new(FailingBackend), cache)),
+            weather_client: weather_client.clone(),
+            ..test_state(db.clone(), weather_core::weather::MockWeatherClient::new())
+        };
+
+        let response = generate_batch_suggestions(&state, None).await.unwrap();
+
+        assert!(response.failed.is_empty());
+        assert_eq!(response.suggestions.len(), 2);
+        let hit = &response.suggestions[&held[0].id];
+        assert!(hit.iter().all(|option| option.reason == "Cached suggestion"));
+        let miss = &response.suggestions[&held[1].id];
+        assert_eq!(miss.len(), DEFAULT_RESCHEDULE_OPTIONS);
+        assert!(miss.iter().all(|option| option.reason != "Cached suggestion"));
+
+        // Both bookings depart from the same airport
+        assert_eq!(weather_client.requests().len(), 1);
+
+        // Only the freshly generated options are recorded
+        assert!(fetch_reschedule_history(&db, &held[0].id).await.unwrap().is_empty());
+        assert_eq!(fetch_reschedule_history(&db, &held[1].id).await.unwrap().len(), 1);
+
+        let response = generate_batch_suggestions(&state, Some(&["missing".to_string()])).await.unwrap();
+        assert_eq!(response.failed["missing"], "Booking not found");
+    }
+
+    #[tokio::test]
+    async fn test_acknowledging_hold_with_improved_weather_reschedules() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        sqlx::query("UPDATE bookings SET status = ? WHERE id = ?")
+            .bind(BookingStatus::WeatherHold.as_str())
+            .bind(&booking.id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let calm = weather_core::weather::WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots: 5.0,
+            ceiling_ft: Some(5000.0),
+            temperature_f: 65.0,
+            conditions: "Clear".to_string(),
+            has_thunderstorms: false,
+            has_icing: false,
+            date_time: booking.scheduled_date,
+            ..Default::default()
+        };
+        let state = test_state(db.clone(), weather_core::weather::MockWeatherClient::new().with_forecast(vec![calm]));
+        let mut rx = state.notification_tx.subscribe();
+
+        let Json(response) = acknowledge_hold(
+            Path(booking.id.clone()),
+            State(state.clone()),
+            ApiJson(AcknowledgeHoldRequest { decision: HoldDecision::Keep }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.booking.status, BookingStatus::Scheduled.as_str());
+        assert!(response.reason.is_none());
+
+        let notification: Notification = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert!(matches!(notification, Notification::WeatherHoldReleased { .. }));
+
+        // No longer held, so a second acknowledgement conflicts
+        let err = acknowledge_hold(
+            Path(booking.id.clone()),
+            State(state),
+            ApiJson(AcknowledgeHoldRequest { decision: HoldDecision::Cancel }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_keeping_hold_in_bad_weather_records_no_reschedule() {
+        let db = setup_test_db().await;
+        let booking = insert_booking(&db, &booking_request()).await.unwrap();
+        sqlx::query("UPDATE bookings SET status = ? WHERE id = ?")
+            .bind(BookingStatus::WeatherHold.as_str())
+            .bind(&booking.id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let windy = weather_core::weather::WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots: 30.0,
+            ceiling_ft: Some(5000.0),
+            temperature_f: 65.0,
+            conditions: "Clear".to_string(),
+            has_thunderstorms: false,
+            has_icing: false,
+            date_time: booking.scheduled_date,
+            ..Default::default()
+        };
+        let state = test_state(db.clone(), weather_core::weather::MockWeatherClient::new().with_forecast(vec![windy]));
+
+        let Json(response) = acknowledge_hold(
+            Path(booking.id.clone()),
+            State(state),
+            ApiJson(AcknowledgeHoldRequest { decision: HoldDecision::Keep }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.booking.status, BookingStatus::WeatherHold.as_str());
+        assert!(response.reason.as_deref().unwrap().contains("Wind speed"));
+
+        assert!(fetch_reschedule_history(&db, &booking.id).await.unwrap().is_empty());
+        let decisions: Vec<(String, String)> =
+            sqlx::query_as("SELECT to_status, changed_by FROM booking_status_events WHERE booking_id = ?")
+                .bind(&booking.id)
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(decisions, vec![("WEATHER_HOLD".to_string(), SUGGESTED_BY_USER.to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_history_unknown_booking() {
+        let db = setup_test_db().await;
+
+        let err = fetch_reschedule_history(&db, "missing").await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+}
@@ -0,0 +1,155 @@
+use axum::{extract::State, http::StatusCode, Json};
+use weather_core::weather::WeatherSource;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+use crate::AppState;
+
+/// How long each dependency gets to answer before it's reported unhealthy
+const DEPENDENCY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Location used to ping the weather API (KTOA, Torrance). Current weather is
+/// cached, so frequent probes don't spend API quota.
+const PROBE_LOCATION: (f64, f64) = (33.8034, -118.3396);
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: &'static str,
+    pub checks: DependencyChecks,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyChecks {
+    pub database: DependencyStatus,
+    /// Absent when READINESS_CHECK_WEATHER=false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather_api: Option<DependencyStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyStatus {
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DependencyStatus {
+    fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self { status: "ok", error: None },
+            Err(error) => Self { status: "error", error: Some(error) },
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl ReadinessResponse {
+    fn is_ready(&self) -> bool {
+        self.checks.database.is_ok() && self.checks.weather_api.iter().all(DependencyStatus::is_ok)
+    }
+}
+
+/// GET /health/ready - Readiness probe: 200 when the database (and, unless
+/// READINESS_CHECK_WEATHER=false, the weather API) respond, otherwise 503
+pub async fn readiness_check(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let check_weather = std::env::var("READINESS_CHECK_WEATHER")
+        .map(|v| v != "false" && v != "0")
+        .unwrap_or(true);
+    let weather = check_weather.then_some(state.weather_client.as_ref());
+
+    let report = check_readiness(&state.db, weather).await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        tracing::warn!("Readiness check failed: {:?}", report.checks);
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report))
+}
+
+async fn check_readiness(db: &SqlitePool, weather: Option<&dyn WeatherSource>) -> ReadinessResponse {
+    let database = DependencyStatus::from_result(check_database(db).await);
+    let weather_api = match weather {
+        Some(weather) => Some(DependencyStatus::from_result(check_weather_api(weather).await)),
+        None => None,
+    };
+
+    let mut report = ReadinessResponse {
+        status: "ok",
+        checks: DependencyChecks { database, weather_api },
+    };
+    if !report.is_ready() {
+        report.status = "unavailable";
+    }
+    report
+}
+
+async fn check_database(db: &SqlitePool) -> Result<(), String> {
+    match tokio::time::timeout(DEPENDENCY_TIMEOUT, sqlx::query("SELECT 1").execute(db)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("timed out".to_string()),
+    }
+}
+
+async fn check_weather_api(weather: &dyn WeatherSource) -> Result<(), String> {
+    let (lat, lon) = PROBE_LOCATION;
+    match tokio::time::timeout(DEPENDENCY_TIMEOUT, weather.fetch_current_weather(lat, lon)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("timed out".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use weather_core::weather::WeatherData;
+    use crate::test_support::setup_test_db;
+
+    struct UnreachableWeather;
+
+    #[async_trait]
+    impl WeatherSource for UnreachableWeather {
+        async fn fetch_current_weather(&self, _lat: f64, _lon: f64) -> anyhow::Result<WeatherData> {
+            anyhow::bail!("Weather API returned status: 401 Unauthorized")
+        }
+
+        async fn fetch_forecast(&self, _lat: f64, _lon: f64) -> anyhow::Result<Vec<WeatherData>> {
+            anyhow::bail!("Weather API returned status: 401 Unauthorized")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ready_when_database_responds() {
+        let db = setup_test_db().await;
+
+        let report = check_readiness(&db, None).await;
+
+        assert!(report.is_ready());
+        assert_eq!(report.status, "ok");
+        assert!(report.checks.weather_api.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_when_dependencies_fail() {
+        let db = setup_test_db().await;
+        let weather = UnreachableWeather;
+
+        let report = check_readiness(&db, Some(&weather)).await;
+        assert_eq!(report.status, "unavailable");
+        assert_eq!(report.checks.database.status, "ok");
+        assert_eq!(report.checks.weather_api.as_ref().unwrap().status, "error");
+
+        db.close().await;
+        let report = check_readiness(&db, None).await;
+        assert!(!report.is_ready());
+        assert_eq!(report.checks.database.status, "error");
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    error::{ApiError, ApiJson, ApiResult},
+    routes::pagination::{Paginated, PaginationParams},
+    AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use weather_core::models::Instructor;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite, SqlitePool};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateInstructorRequest {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    /// e.g. ["CFI", "CFII", "MEI"]
+    #[serde(default)]
+    pub certifications: Vec<String>,
+}
+
+/// Partial update; omitted fields keep their current value
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateInstructorRequest {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: Option<String>,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: Option<String>,
+    pub certifications: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstructorResponse {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub certifications: Vec<String>,
+}
+
+impl From<Instructor> for InstructorResponse {
+    fn from(instructor: Instructor) -> Self {
+        Self {
+            id: instructor.id,
+            name: instructor.name,
+            email: instructor.email,
+            certifications: instructor.certifications,
+        }
+    }
+}
+
+/// GET /api/instructors - Instructors ordered by name
+#[utoipa::path(
+    get,
+    path = "/api/v1/instructors",
+    tag = "instructors",
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Page of instructors ordered by name"),
+    )
+)]
+pub async fn list_instructors(
+    Query(params): Query<PaginationParams>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<Paginated<InstructorResponse>>> {
+    let (page, limit, offset) = params.clamped();
+
+    let instructors = sqlx::query_as::<_, Instructor>(
+        "SELECT id, name, email, certifications FROM instructors ORDER BY name LIMIT ? OFFSET ?"
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM instructors")
+        .fetch_one(&state.db)
+        .await?;
+
+    tracing::debug!("Retrieved {} of {} instructors", instructors.len(), total);
+    Ok(Json(Paginated::new(instructors, page, limit, total).map(InstructorResponse::from)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/instructors",
+    tag = "instructors",
+    responses(
+        (status = 201, description = "Instructor created"),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn create_instructor(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<CreateInstructorRequest>,
+) -> ApiResult<(StatusCode, Json<InstructorResponse>)> {
+    let instructor = insert_instructor(&state.db, &req).await?;
+
+    tracing::info!("Created instructor {} ({})", instructor.name, instructor.id);
+    Ok((StatusCode::CREATED, Json(instructor.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/instructors/{id}",
+    tag = "instructors",
+    params(("id" = String, Path, description = "Instructor id")),
+    responses(
+        (status = 200, description = "The instructor"),
+        (status = 404, description = "Instructor not found", body = ApiError),
+    )
+)]
+pub async fn get_instructor(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<InstructorResponse>> {
+    let instructor = fetch_instructor(&state.db, &id).await?;
+    Ok(Json(instructor.into()))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/instructors/{id}",
+    tag = "instructors",
+    params(("id" = String, Path, description = "Instructor id")),
+    responses(
+        (status = 200, description = "Updated instructor"),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Instructor not found", body = ApiError),
+    )
+)]
+pub async fn update_instructor(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<UpdateInstructorRequest>,
+) -> ApiResult<Json<InstructorResponse>> {
+    req.validate()
+        .map_err(|e| ApiError::validation_error(e.to_string()))?;
+
+    let current = fetch_instructor(&state.db, &id).await?;
+    let certifications = req.certifications.as_ref().unwrap_or(&current.certifications);
+
+    sqlx::query(
+        "UPDATE instructors SET name = ?, email = ?, certifications = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(req.name.as_ref().unwrap_or(&current.name))
+    .bind(req.email.as_ref().unwrap_or(&current.email))
+    .bind(serde_json::to_string(certifications)?)
+    .bind(&id)
+    .execute(&state.db)
+    .await?;
+
+    let instructor = fetch_instructor(&state.db, &id).await?;
+
+    tracing::info!("Updated instructor {} ({})", instructor.name, instructor.id);
+    Ok(Json(instructor.into()))
+}
+
+/// DELETE /api/instructors/:id
+/// The instructor's bookings are kept but left without an instructor
+#[utoipa::path(
+    delete,
+    path = "/api/v1/instructors/{id}",
+    tag = "instructors",
+    params(("id" = String, Path, description = "Instructor id")),
+    responses(
+        (status = 204, description = "Instructor deleted"),
+        (status = 404, description = "Instructor not found", body = ApiError),
+    )
+)]
+pub async fn delete_instructor(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<StatusCode> {
+    let instructor = fetch_instructor(&state.db, &id).await?;
+
+    sqlx::query("DELETE FROM instructors WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await?;
+
+    tracing::info!("Deleted instructor {} ({})", instructor.name, instructor.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn insert_instructor(db: &SqlitePool, req: &CreateInstructorRequest) -> ApiResult<Instructor> {
+    req.validate()
+        .map_err(|e| ApiError::validation_error(e.to_string()))?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+
+    sqlx::query("INSERT INTO instructors (id, name, email, certifications) VALUES (?, ?, ?, ?)")
+        .bind(&id)
+        .bind(&req.name)
+        .bind(&req.email)
+        .bind(serde_json::to_string(&req.certifications)?)
+        .execute(db)
+        .await?;
+
+    fetch_instructor(db, &id).await
+}
+
+pub(crate) async fn fetch_instructor<'e, E>(db: E, id: &str) -> ApiResult<Instructor>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as::<_, Instructor>("SELECT id, name, email, certifications FROM instructors WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Instructor"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    #[tokio::test]
+    async fn test_certifications_round_trip() {
+        let db = setup_test_db().await;
+        let req = CreateInstructorRequest {
+            name: "Jane Smith".to_string(),
+            email: "jane@example.com".to_string(),
+            certifications: vec!["CFI".to_string(), "CFII".to_string()],
+        };
+
+        let created = insert_instructor(&db, &req).await.unwrap();
+        let fetched = fetch_instructor(&db, &created.id).await.unwrap();
+
+        assert_eq!(fetched.name, "Jane Smith");
+        assert_eq!(fetched.certifications, vec!["CFI", "CFII"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_email_rejected() {
+        let db = setup_test_db().await;
+        let req = CreateInstructorRequest {
+            name: "Jane Smith".to_string(),
+            email: "not-an-email".to_string(),
+            certifications: vec![],
+        };
+
+        let err = insert_instructor(&db, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+}
@@ -1,13 +1,32 @@
+pub mod admin;
+pub mod aircraft;
 pub mod alerts;
 pub mod bookings;
+pub mod health;
+pub mod instructors;
+pub mod pagination;
+pub mod stats;
 pub mod students;
 pub mod weather;
+pub mod weather_minimums;
 pub mod websocket;
 
 use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Response},
 };
+use serde::{Deserialize, Deserializer};
+
+/// Deserializer for PATCH fields that can be cleared. Used with
+/// `#[serde(default, deserialize_with = "nullable")]`, an omitted field is
+/// `None` (keep), `null` is `Some(None)` (clear) and a value is `Some(Some(v))`.
+pub(crate) fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
 
 pub async fn serve_spa() -> Response {
     match tokio::fs::read_to_string("dist/index.html").await {
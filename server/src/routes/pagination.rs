@@ -0,0 +1,103 @@
+use crate::routes::{alerts::WeatherAlert, bookings::BookingResponse, students::StudentResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Largest page a client may request
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PaginationParams {
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+impl Default for PaginationParams {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            limit: default_limit(),
+        }
+    }
+}
+
+impl PaginationParams {
+    /// Page and limit clamped to valid values, and the matching row offset
+    pub fn clamped(&self) -> (i64, i64, i64) {
+        clamp_page(self.page, self.limit)
+    }
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// Clamp a requested page (1-based) and limit (1..=100), returning
+/// `(page, limit, offset)`
+pub fn clamp_page(page: i64, limit: i64) -> (i64, i64, i64) {
+    let page = page.max(1);
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    (page, limit, (page - 1) * limit)
+}
+
+/// One page of a list endpoint, with enough metadata to build a pager
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(
+    PaginatedBookings = Paginated<BookingResponse>,
+    PaginatedStudents = Paginated<StudentResponse>,
+    PaginatedAlerts = Paginated<WeatherAlert>
+)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub page: i64,
+    pub limit: i64,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(items: Vec<T>, page: i64, limit: i64, total: i64) -> Self {
+        Self {
+            items,
+            page,
+            limit,
+            total,
+            total_pages: (total + limit - 1) / limit,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paginated<U> {
+        Paginated {
+            items: self.items.into_iter().map(f).collect(),
+            page: self.page,
+            limit: self.limit,
+            total: self.total,
+            total_pages: self.total_pages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_pages_rounds_up() {
+        assert_eq!(Paginated::new(vec![(); 10], 1, 10, 15).total_pages, 2);
+        assert_eq!(Paginated::new(vec![(); 10], 1, 10, 10).total_pages, 1);
+        assert_eq!(Paginated::<()>::new(vec![], 1, 10, 0).total_pages, 0);
+    }
+
+    #[test]
+    fn test_clamp_page() {
+        assert_eq!(clamp_page(0, 500), (1, 100, 0));
+        assert_eq!(clamp_page(3, 0), (3, 1, 2));
+        assert_eq!(clamp_page(2, 25), (2, 25, 25));
+    }
+}
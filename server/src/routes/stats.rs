@@ -0,0 +1,171 @@
+use axum::{extract::State, Json};
+use chrono::{Duration, Utc};
+use weather_core::models::BookingStatus;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+use crate::{error::ApiResult, routes::alerts::SEVERITIES, AppState};
+
+const BOOKING_STATUSES: [BookingStatus; 5] = [
+    BookingStatus::Scheduled,
+    BookingStatus::WeatherHold,
+    BookingStatus::Cancelled,
+    BookingStatus::Rescheduled,
+    BookingStatus::Completed,
+];
+
+/// Dashboard counts; every status and severity is present, zero when unused
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    /// Bookings (excluding deleted ones) per status
+    pub bookings_by_status: BTreeMap<String, i64>,
+    /// Undismissed alerts per severity
+    pub active_alerts_by_severity: BTreeMap<String, i64>,
+    /// Bookings cancelled within the last 24 hours
+    pub cancelled_last_24h: i64,
+    /// Scheduled or held flights departing within the next 48 hours
+    pub upcoming_next_48h: i64,
+}
+
+/// GET /api/stats - Booking and alert counts for the dashboard
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Booking and alert counts", body = StatsResponse),
+    )
+)]
+pub async fn get_stats(State(state): State<AppState>) -> ApiResult<Json<StatsResponse>> {
+    Ok(Json(compute_stats(&state.db).await?))
+}
+
+async fn compute_stats(db: &SqlitePool) -> Result<StatsResponse, sqlx::Error> {
+    let mut bookings_by_status: BTreeMap<String, i64> =
+        BOOKING_STATUSES.iter().map(|status| (status.as_str().to_string(), 0)).collect();
+    let status_counts = sqlx::query_as::<_, (String, i64)>(
+        "SELECT status, COUNT(*) FROM bookings WHERE deleted_at IS NULL GROUP BY status"
+    )
+    .fetch_all(db)
+    .await?;
+    bookings_by_status.extend(status_counts);
+
+    let mut active_alerts_by_severity: BTreeMap<String, i64> =
+        SEVERITIES.iter().map(|severity| (severity.to_string(), 0)).collect();
+    let severity_counts = sqlx::query_as::<_, (String, i64)>(
+        "SELECT severity, COUNT(*) FROM weather_alerts WHERE dismissed_at IS NULL GROUP BY severity"
+    )
+    .fetch_all(db)
+    .await?;
+    active_alerts_by_severity.extend(severity_counts);
+
+    // A cancellation is the booking's last update; datetime() normalizes
+    // CURRENT_TIMESTAMP and RFC 3339 values alike
+    let cancelled_last_24h: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM bookings
+         WHERE status = 'CANCELLED' AND deleted_at IS NULL
+         AND datetime(updated_at) >= datetime('now', '-1 day')"
+    )
+    .fetch_one(db)
+    .await?;
+
+    let now = Utc::now();
+    let upcoming_next_48h: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM bookings
+         WHERE status IN ('SCHEDULED', 'WEATHER_HOLD')
+         AND scheduled_date BETWEEN ? AND ?
+         AND deleted_at IS NULL"
+    )
+    .bind(now)
+    .bind(now + Duration::hours(48))
+    .fetch_one(db)
+    .await?;
+
+    Ok(StatsResponse {
+        bookings_by_status,
+        active_alerts_by_severity,
+        cancelled_last_24h,
+        upcoming_next_48h,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use crate::test_support::{self, insert_test_student};
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = test_support::setup_test_db().await;
+        insert_test_student(&pool).await;
+        pool
+    }
+
+    async fn insert_booking(db: &SqlitePool, id: &str, status: &str, scheduled_date: DateTime<Utc>, updated_at: DateTime<Utc>) {
+        sqlx::query(
+            "INSERT INTO bookings (id, student_id, scheduled_date, departure_location, status, updated_at)
+             VALUES (?, 'student1', ?, '{\"lat\": 33.8, \"lon\": -118.1, \"name\": \"KTOA\"}', ?, ?)"
+        )
+        .bind(id)
+        .bind(scheduled_date)
+        .bind(status)
+        .bind(updated_at)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_alert(db: &SqlitePool, id: &str, severity: &str, dismissed: bool) {
+        sqlx::query(
+            "INSERT INTO weather_alerts (id, severity, message, location, created_at, dismissed_at)
+             VALUES (?, ?, 'Test alert', 'KTOA', ?, ?)"
+        )
+        .bind(id)
+        .bind(severity)
+        .bind(Utc::now())
+        .bind(if dismissed { Some(Utc::now()) } else { None })
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stats_aggregates() {
+        let db = setup_test_db().await;
+        let now = Utc::now();
+
+        insert_booking(&db, "soon", "SCHEDULED", now + Duration::hours(6), now).await;
+        insert_booking(&db, "held", "WEATHER_HOLD", now + Duration::hours(30), now).await;
+        insert_booking(&db, "later", "SCHEDULED", now + Duration::days(5), now).await;
+        insert_booking(&db, "cancelled_today", "CANCELLED", now + Duration::hours(12), now).await;
+        insert_booking(&db, "cancelled_last_week", "CANCELLED", now - Duration::days(6), now - Duration::days(7)).await;
+        insert_booking(&db, "done", "COMPLETED", now - Duration::days(1), now - Duration::days(1)).await;
+        insert_booking(&db, "deleted", "SCHEDULED", now + Duration::hours(2), now).await;
+        sqlx::query("UPDATE bookings SET deleted_at = CURRENT_TIMESTAMP WHERE id = 'deleted'")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        insert_alert(&db, "a1", "high", false).await;
+        insert_alert(&db, "a2", "high", false).await;
+        insert_alert(&db, "a3", "severe", false).await;
+        insert_alert(&db, "a4", "severe", true).await;
+
+        let stats = compute_stats(&db).await.unwrap();
+
+        assert_eq!(stats.bookings_by_status["SCHEDULED"], 2);
+        assert_eq!(stats.bookings_by_status["WEATHER_HOLD"], 1);
+        assert_eq!(stats.bookings_by_status["CANCELLED"], 2);
+        assert_eq!(stats.bookings_by_status["COMPLETED"], 1);
+        assert_eq!(stats.bookings_by_status["RESCHEDULED"], 0);
+
+        assert_eq!(stats.active_alerts_by_severity["high"], 2);
+        assert_eq!(stats.active_alerts_by_severity["severe"], 1);
+        assert_eq!(stats.active_alerts_by_severity["clear"], 0);
+
+        assert_eq!(stats.cancelled_last_24h, 1);
+        assert_eq!(stats.upcoming_next_48h, 2);
+    }
+}
@@ -1,13 +1,25 @@
-use crate::{error::ApiResult, AppState};
+use crate::{
+    auth::ApiRole,
+    error::{ApiError, ApiJson, ApiResult},
+    routes::pagination::{Paginated, PaginationParams},
+    AppState,
+};
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
-use core::models::{Student, TrainingLevel};
+use chrono::{DateTime, Utc};
+use weather_core::models::{Student, TrainingLevel};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+/// Columns selected into `Student`
+pub(crate) const STUDENT_COLUMNS: &str =
+    "id, name, email, phone, training_level, notify_email, notify_sms, created_at, updated_at, deleted_at";
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateStudentRequest {
     #[validate(length(min = 1, message = "Name cannot be empty"))]
@@ -17,15 +29,72 @@ pub struct CreateStudentRequest {
     #[validate(length(min = 1, message = "Phone cannot be empty"))]
     pub phone: String,
     pub training_level: String,
+    /// Email cancellation notices (default true)
+    #[serde(default)]
+    pub notify_email: Option<bool>,
+    /// Text cancellation notices (default true)
+    #[serde(default)]
+    pub notify_sms: Option<bool>,
+}
+
+/// Partial update; omitted fields keep their current value
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateStudentRequest {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: Option<String>,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, message = "Phone cannot be empty"))]
+    pub phone: Option<String>,
+    pub training_level: Option<String>,
+    pub notify_email: Option<bool>,
+    pub notify_sms: Option<bool>,
+}
+
+/// Admin option to see soft-deleted rows; other keys get 403
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncludeDeletedParams {
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// Soft-deleted rows are only shown to admin keys
+pub(crate) fn authorize_include_deleted(include_deleted: bool, role: ApiRole) -> ApiResult<()> {
+    if include_deleted && !role.satisfies(ApiRole::Admin) {
+        return Err(ApiError::forbidden("include_deleted requires an admin API key"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteStudentParams {
+    /// Required to delete a student who still has active bookings
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Serialize)]
+pub struct DeleteStudentResponse {
+    pub id: String,
+    /// Bookings soft-deleted along with the student
+    pub deleted_bookings: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StudentResponse {
     pub id: String,
     pub name: String,
     pub email: String,
     pub phone: String,
+    #[schema(value_type = TrainingLevel)]
     pub training_level: String,
+    pub notify_email: bool,
+    pub notify_sms: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Student> for StudentResponse {
@@ -36,66 +105,398 @@ impl From<Student> for StudentResponse {
             email: student.email,
             phone: student.phone,
             training_level: student.training_level.as_str().to_string(),
+            notify_email: student.notify_email,
+            notify_sms: student.notify_sms,
+            created_at: student.created_at,
+            updated_at: student.updated_at,
+            deleted_at: student.deleted_at,
         }
     }
 }
 
+/// GET /api/students - Students ordered by name; soft-deleted students are
+/// only listed with `?include_deleted=true` from an admin key
+#[utoipa::path(
+    get,
+    path = "/api/v1/students",
+    tag = "students",
+    params(PaginationParams, IncludeDeletedParams),
+    responses(
+        (status = 200, description = "Page of students ordered by name", body = PaginatedStudents),
+        (status = 403, description = "include_deleted requires an admin API key", body = ApiError),
+    )
+)]
 pub async fn list_students(
+    Query(params): Query<PaginationParams>,
+    Query(deleted): Query<IncludeDeletedParams>,
     State(state): State<AppState>,
-) -> ApiResult<Json<Vec<StudentResponse>>> {
-    let students = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students ORDER BY name"
-    )
+    Extension(role): Extension<ApiRole>,
+) -> ApiResult<Json<Paginated<StudentResponse>>> {
+    authorize_include_deleted(deleted.include_deleted, role)?;
+    let (page, limit, offset) = params.clamped();
+
+    let students = sqlx::query_as::<_, Student>(&format!(
+        "SELECT {} FROM students WHERE deleted_at IS NULL OR ? ORDER BY name LIMIT ? OFFSET ?",
+        STUDENT_COLUMNS
+    ))
+    .bind(deleted.include_deleted)
+    .bind(limit)
+    .bind(offset)
     .fetch_all(&state.db)
     .await?;
 
-    tracing::debug!("Retrieved {} students", students.len());
-    Ok(Json(students.into_iter().map(StudentResponse::from).collect()))
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM students WHERE deleted_at IS NULL OR ?")
+        .bind(deleted.include_deleted)
+        .fetch_one(&state.db)
+        .await?;
+
+    tracing::debug!("Retrieved {} of {} students", students.len(), total);
+    Ok(Json(Paginated::new(students, page, limit, total).map(StudentResponse::from)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/students",
+    tag = "students",
+    responses(
+        (status = 201, description = "Student created", body = StudentResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
 pub async fn create_student(
     State(state): State<AppState>,
-    Json(req): Json<CreateStudentRequest>,
+    ApiJson(req): ApiJson<CreateStudentRequest>,
 ) -> ApiResult<(StatusCode, Json<StudentResponse>)> {
+    let student = insert_student(&state.db, &req).await?;
+
+    tracing::info!("Created student {} ({})", student.name, student.id);
+    Ok((StatusCode::CREATED, Json(student.into())))
+}
+
+pub(crate) async fn insert_student(db: &SqlitePool, req: &CreateStudentRequest) -> ApiResult<Student> {
     // Validate input fields
     req.validate()
         .map_err(|e| crate::error::ApiError::validation_error(e.to_string()))?;
 
     // Validate training level
-    let training_level = match req.training_level.as_str() {
-        "STUDENT_PILOT" => TrainingLevel::StudentPilot,
-        "PRIVATE_PILOT" => TrainingLevel::PrivatePilot,
-        "INSTRUMENT_RATED" => TrainingLevel::InstrumentRated,
-        _ => {
-            return Err(crate::error::ApiError::validation_error(
-                format!("Invalid training level: {}. Must be one of: STUDENT_PILOT, PRIVATE_PILOT, INSTRUMENT_RATED", req.training_level)
-            ));
-        }
-    };
+    let training_level = parse_training_level(&req.training_level)?;
 
     // Generate UUID
     let id = uuid::Uuid::new_v4().to_string();
 
     // Insert student
     sqlx::query(
-        "INSERT INTO students (id, name, email, phone, training_level) VALUES (?, ?, ?, ?, ?)"
+        "INSERT INTO students (id, name, email, phone, training_level, notify_email, notify_sms)
+         VALUES (?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&id)
     .bind(&req.name)
     .bind(&req.email)
     .bind(&req.phone)
     .bind(training_level.as_str())
-    .execute(&state.db)
+    .bind(req.notify_email.unwrap_or(true))
+    .bind(req.notify_sms.unwrap_or(true))
+    .execute(db)
     .await?;
 
     // Fetch created student
-    let student = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
+    let student = sqlx::query_as::<_, Student>(&format!("SELECT {} FROM students WHERE id = ?", STUDENT_COLUMNS))
+        .bind(&id)
+        .fetch_one(db)
+        .await?;
+
+    Ok(student)
+}
+
+/// GET /api/students/:id
+/// Soft-deleted students are only returned with `?include_deleted=true` from
+/// an admin key
+#[utoipa::path(
+    get,
+    path = "/api/v1/students/{id}",
+    tag = "students",
+    params(("id" = String, Path, description = "Student id"), IncludeDeletedParams),
+    responses(
+        (status = 200, description = "The student", body = StudentResponse),
+        (status = 403, description = "include_deleted requires an admin API key", body = ApiError),
+        (status = 404, description = "Student not found", body = ApiError),
+    )
+)]
+pub async fn get_student(
+    Path(id): Path<String>,
+    Query(params): Query<IncludeDeletedParams>,
+    State(state): State<AppState>,
+    Extension(role): Extension<ApiRole>,
+) -> ApiResult<Json<StudentResponse>> {
+    authorize_include_deleted(params.include_deleted, role)?;
+    let student = find_student(&state.db, &id, params.include_deleted).await?;
+    Ok(Json(student.into()))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/students/{id}",
+    tag = "students",
+    params(("id" = String, Path, description = "Student id")),
+    responses(
+        (status = 200, description = "Updated student", body = StudentResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Student not found", body = ApiError),
     )
+)]
+pub async fn update_student(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<UpdateStudentRequest>,
+) -> ApiResult<Json<StudentResponse>> {
+    req.validate()
+        .map_err(|e| ApiError::validation_error(e.to_string()))?;
+
+    let training_level = req
+        .training_level
+        .as_deref()
+        .map(parse_training_level)
+        .transpose()?;
+
+    let current = fetch_student(&state.db, &id).await?;
+
+    sqlx::query(
+        "UPDATE students SET name = ?, email = ?, phone = ?, training_level = ?, notify_email = ?, notify_sms = ?,
+         updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(req.name.as_ref().unwrap_or(&current.name))
+    .bind(req.email.as_ref().unwrap_or(&current.email))
+    .bind(req.phone.as_ref().unwrap_or(&current.phone))
+    .bind(training_level.unwrap_or(current.training_level).as_str())
+    .bind(req.notify_email.unwrap_or(current.notify_email))
+    .bind(req.notify_sms.unwrap_or(current.notify_sms))
     .bind(&id)
-    .fetch_one(&state.db)
+    .execute(&state.db)
     .await?;
 
-    tracing::info!("Created student {} ({})", student.name, student.id);
-    Ok((StatusCode::CREATED, Json(student.into())))
+    let student = fetch_student(&state.db, &id).await?;
+
+    tracing::info!("Updated student {} ({})", student.name, student.id);
+    Ok(Json(student.into()))
+}
+
+/// DELETE /api/students/:id
+/// Soft-deletes the student and their bookings, which keeps the booking
+/// history for audit but takes active bookings off the schedule, so a student
+/// with active bookings is only removed when `?force=true` is given
+#[utoipa::path(
+    delete,
+    path = "/api/v1/students/{id}",
+    tag = "students",
+    params(("id" = String, Path, description = "Student id"), DeleteStudentParams),
+    responses(
+        (status = 200, description = "Student and bookings soft-deleted"),
+        (status = 404, description = "Student not found", body = ApiError),
+        (status = 409, description = "Student has active bookings", body = ApiError),
+    )
+)]
+pub async fn delete_student(
+    Path(id): Path<String>,
+    Query(params): Query<DeleteStudentParams>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<DeleteStudentResponse>> {
+    let response = remove_student(&state.db, &id, params.force).await?;
+    Ok(Json(response))
+}
+
+async fn remove_student(db: &SqlitePool, id: &str, force: bool) -> ApiResult<DeleteStudentResponse> {
+    let student = fetch_student(db, id).await?;
+
+    let active_bookings: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM bookings
+         WHERE student_id = ? AND status IN ('SCHEDULED', 'WEATHER_HOLD', 'RESCHEDULED') AND deleted_at IS NULL"
+    )
+    .bind(id)
+    .fetch_one(db)
+    .await?;
+
+    if active_bookings > 0 && !force {
+        return Err(ApiError::conflict(format!(
+            "Student has {} active booking(s) that would be deleted. Pass ?force=true to proceed",
+            active_bookings
+        )));
+    }
+
+    let deleted_at = Utc::now();
+    let mut tx = db.begin().await?;
+
+    let deleted_bookings = sqlx::query(
+        "UPDATE bookings SET deleted_at = ?, updated_at = CURRENT_TIMESTAMP WHERE student_id = ? AND deleted_at IS NULL"
+    )
+    .bind(deleted_at)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected() as i64;
+
+    sqlx::query("UPDATE students SET deleted_at = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(deleted_at)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        "Soft-deleted student {} ({}) and {} booking(s)",
+        student.name,
+        student.id,
+        deleted_bookings
+    );
+
+    Ok(DeleteStudentResponse {
+        id: student.id,
+        deleted_bookings,
+    })
+}
+
+/// Student by id; soft-deleted students count as missing
+pub(crate) async fn fetch_student(db: &SqlitePool, id: &str) -> ApiResult<Student> {
+    find_student(db, id, false).await
+}
+
+async fn find_student(db: &SqlitePool, id: &str, include_deleted: bool) -> ApiResult<Student> {
+    sqlx::query_as::<_, Student>(&format!(
+        "SELECT {} FROM students WHERE id = ? AND (deleted_at IS NULL OR ?)",
+        STUDENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(include_deleted)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Student"))
+}
+
+pub(crate) fn parse_training_level(value: &str) -> ApiResult<TrainingLevel> {
+    TrainingLevel::try_from(value.to_string()).map_err(|e| {
+        ApiError::validation_error(format!(
+            "{}. Must be one of: STUDENT_PILOT, PRIVATE_PILOT, INSTRUMENT_RATED",
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert_test_student, setup_test_db, test_state};
+    use weather_core::weather::MockWeatherClient;
+
+    async fn insert_student_with_booking(db: &SqlitePool) {
+        insert_test_student(db).await;
+
+        sqlx::query(
+            "INSERT INTO bookings (id, student_id, aircraft_type, scheduled_date, departure_location, status)
+             VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind("booking1")
+        .bind("student1")
+        .bind("Cessna 172")
+        .bind(chrono::Utc::now() + chrono::Duration::days(1))
+        .bind(r#"{"lat":33.8113,"lon":-118.1515,"name":"KTOA"}"#)
+        .bind("SCHEDULED")
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_active_bookings_requires_force() {
+        let db = setup_test_db().await;
+        insert_student_with_booking(&db).await;
+
+        let err = remove_student(&db, "student1", false).await.unwrap_err();
+        assert_eq!(err.error.code, "CONFLICT");
+
+        let response = remove_student(&db, "student1", true).await.unwrap();
+        assert_eq!(response.deleted_bookings, 1);
+
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bookings WHERE deleted_at IS NULL")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_student_hidden_but_retained() {
+        let db = setup_test_db().await;
+        insert_student_with_booking(&db).await;
+
+        remove_student(&db, "student1", true).await.unwrap();
+
+        let err = fetch_student(&db, "student1").await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+
+        let student = find_student(&db, "student1", true).await.unwrap();
+        assert!(student.deleted_at.is_some());
+        assert!(student.created_at.is_some());
+
+        let rows: (i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM students WHERE deleted_at IS NOT NULL),
+                    (SELECT COUNT(*) FROM bookings WHERE deleted_at IS NOT NULL)"
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(rows, (1, 1));
+
+        // Deleting again finds nothing to delete
+        let err = remove_student(&db, "student1", true).await.unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_include_deleted_requires_admin() {
+        let db = setup_test_db().await;
+        insert_student_with_booking(&db).await;
+        remove_student(&db, "student1", true).await.unwrap();
+        let state = test_state(db, MockWeatherClient::new());
+        let get = |role| {
+            get_student(
+                Path("student1".to_string()),
+                Query(IncludeDeletedParams { include_deleted: true }),
+                State(state.clone()),
+                Extension(role),
+            )
+        };
+        let list = |role| {
+            list_students(
+                Query(PaginationParams::default()),
+                Query(IncludeDeletedParams { include_deleted: true }),
+                State(state.clone()),
+                Extension(role),
+            )
+        };
+
+        for role in [ApiRole::Instructor, ApiRole::ReadOnly] {
+            assert_eq!(get(role).await.unwrap_err().error.code, "FORBIDDEN");
+            assert_eq!(list(role).await.unwrap_err().error.code, "FORBIDDEN");
+        }
+
+        let Json(student) = get(ApiRole::Admin).await.unwrap();
+        assert!(student.deleted_at.is_some());
+        let Json(page) = list(ApiRole::Admin).await.unwrap();
+        assert_eq!(page.total, 1);
+    }
+
+    #[test]
+    fn test_update_request_validation() {
+        let req = UpdateStudentRequest {
+            email: Some("not-an-email".to_string()),
+            ..Default::default()
+        };
+        assert!(req.validate().is_err());
+
+        let req = UpdateStudentRequest {
+            phone: Some("+15551234567".to_string()),
+            ..Default::default()
+        };
+        assert!(req.validate().is_ok());
+    }
 }
@@ -1,26 +1,100 @@
-use axum::{extract::Query, Json};
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use weather_core::models::{Booking, Location, TrainingLevel, WeatherMinimum, BOOKING_COLUMNS};
+use weather_core::weather::{
+    calculate_weather_score, fahrenheit_to_celsius, DailyForecast, feet_to_meters, flight_category, is_flight_safe,
+    knots_to_meters_per_second, load_flight_minimums, miles_to_km, FlightCategory, SafetyViolation, Units,
+    WeatherData, WeatherError,
+};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-use crate::{error::ApiError, AppState};
+use crate::{
+    error::{ApiError, ApiJson, ApiResult},
+    routes::students::{fetch_student, parse_training_level},
+    scheduler::select_forecast_slot,
+    AppState,
+};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct WeatherQuery {
     lat: f64,
     lon: f64,
+    /// "imperial" (default) or "metric"
+    #[serde(default)]
+    units: Units,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct WeatherResponse {
     pub location: String,
-    pub temperature_f: f64,
+    pub units: Units,
+    #[serde(flatten)]
+    pub readings: WeatherReadings,
     pub conditions: String,
-    pub visibility_miles: f64,
-    pub wind_speed_knots: f64,
-    pub ceiling_ft: Option<f64>,
+    pub flight_category: FlightCategory,
     pub has_thunderstorms: bool,
     pub has_icing: bool,
 }
 
+/// Unit-dependent values; field names carry the unit so clients can't misread them
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum WeatherReadings {
+    Imperial {
+        temperature_f: f64,
+        visibility_miles: f64,
+        wind_speed_knots: f64,
+        wind_gust_knots: Option<f64>,
+        ceiling_ft: Option<f64>,
+    },
+    Metric {
+        temperature_c: f64,
+        visibility_km: f64,
+        wind_speed_ms: f64,
+        wind_gust_ms: Option<f64>,
+        ceiling_m: Option<f64>,
+    },
+}
+
+impl WeatherReadings {
+    fn new(weather: &WeatherData, units: Units) -> Self {
+        match units {
+            Units::Imperial => WeatherReadings::Imperial {
+                temperature_f: weather.temperature_f,
+                visibility_miles: weather.visibility_miles,
+                wind_speed_knots: weather.wind_speed_knots,
+                wind_gust_knots: weather.wind_gust_knots,
+                ceiling_ft: weather.ceiling_ft,
+            },
+            Units::Metric => WeatherReadings::Metric {
+                temperature_c: fahrenheit_to_celsius(weather.temperature_f),
+                visibility_km: miles_to_km(weather.visibility_miles),
+                wind_speed_ms: knots_to_meters_per_second(weather.wind_speed_knots),
+                wind_gust_ms: weather.wind_gust_knots.map(knots_to_meters_per_second),
+                ceiling_m: weather.ceiling_ft.map(feet_to_meters),
+            },
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/weather",
+    tag = "weather",
+    params(WeatherQuery),
+    responses(
+        (status = 200, description = "Current weather", body = WeatherResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "No weather for this location", body = ApiError),
+        (status = 502, description = "Weather provider unavailable", body = ApiError),
+        (status = 503, description = "Weather provider rate limit reached", body = ApiError),
+    )
+)]
 pub async fn get_weather(
     Query(params): Query<WeatherQuery>,
     axum::extract::State(state): axum::extract::State<AppState>,
@@ -33,19 +107,498 @@ pub async fn get_weather(
         .await
         .map_err(|e| {
             tracing::error!("Weather API error for lat={}, lon={}: {}", params.lat, params.lon, e);
-            ApiError::external_api_error("OpenWeatherMap", format!("Unable to fetch weather data: {}", e))
+            weather_api_error(e, "Unable to fetch weather data")
         })?;
 
     let response = WeatherResponse {
         location: format!("{:.4},{:.4}", params.lat, params.lon),
-        temperature_f: weather_data.temperature_f,
+        units: params.units,
+        readings: WeatherReadings::new(&weather_data, params.units),
         conditions: weather_data.conditions.clone(),
-        visibility_miles: weather_data.visibility_miles,
-        wind_speed_knots: weather_data.wind_speed_knots,
-        ceiling_ft: weather_data.ceiling_ft,
+        flight_category: flight_category(&weather_data),
         has_thunderstorms: weather_data.has_thunderstorms,
         has_icing: weather_data.has_icing,
     };
 
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+/// Days returned by the daily outlook
+const DAILY_FORECAST_DAYS: usize = 7;
+
+#[derive(Serialize, ToSchema)]
+pub struct DailyForecastResponse {
+    pub location: String,
+    pub units: Units,
+    pub days: Vec<DailyOutlook>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DailyOutlook {
+    /// Calendar date at the location
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub temperatures: TemperatureRange,
+    /// Representative daytime conditions
+    #[serde(flatten)]
+    pub readings: WeatherReadings,
+    pub conditions: String,
+    pub flight_category: FlightCategory,
+    /// Probability of precipitation, 0.0 to 1.0
+    pub precipitation_probability: f64,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum TemperatureRange {
+    Imperial { temp_high_f: f64, temp_low_f: f64 },
+    Metric { temp_high_c: f64, temp_low_c: f64 },
+}
+
+impl DailyOutlook {
+    fn new(day: &DailyForecast, units: Units) -> Self {
+        let weather = &day.weather;
+        let offset = chrono::Duration::seconds(weather.utc_offset_seconds.unwrap_or(0) as i64);
+
+        Self {
+            date: (weather.date_time + offset).date_naive(),
+            temperatures: match units {
+                Units::Imperial => TemperatureRange::Imperial {
+                    temp_high_f: day.temp_high_f,
+                    temp_low_f: day.temp_low_f,
+                },
+                Units::Metric => TemperatureRange::Metric {
+                    temp_high_c: fahrenheit_to_celsius(day.temp_high_f),
+                    temp_low_c: fahrenheit_to_celsius(day.temp_low_f),
+                },
+            },
+            readings: WeatherReadings::new(weather, units),
+            conditions: weather.conditions.clone(),
+            flight_category: flight_category(weather),
+            precipitation_probability: day.precipitation_probability,
+            sunrise: weather.sunrise,
+            sunset: weather.sunset,
+        }
+    }
+}
+
+/// GET /api/weather/daily - Seven-day outlook for a location
+#[utoipa::path(
+    get,
+    path = "/api/v1/weather/daily",
+    tag = "weather",
+    params(WeatherQuery),
+    responses(
+        (status = 200, description = "Seven-day outlook", body = DailyForecastResponse),
+        (status = 404, description = "No outlook for this location", body = ApiError),
+        (status = 502, description = "Weather provider unavailable", body = ApiError),
+        (status = 503, description = "Weather provider rate limit reached", body = ApiError),
+    )
+)]
+pub async fn get_daily_forecast(
+    Query(params): Query<WeatherQuery>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Json<DailyForecastResponse>> {
+    let days = state
+        .weather_client
+        .fetch_daily_forecast(params.lat, params.lon)
+        .await
+        .map_err(|e| {
+            tracing::error!("Daily forecast error for lat={}, lon={}: {:#}", params.lat, params.lon, e);
+            weather_api_error(e, "Daily forecast unavailable")
+        })?;
+
+    Ok(Json(DailyForecastResponse {
+        location: format!("{:.4},{:.4}", params.lat, params.lon),
+        units: params.units,
+        days: days
+            .iter()
+            .take(DAILY_FORECAST_DAYS)
+            .map(|day| DailyOutlook::new(day, params.units))
+            .collect(),
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct SafetyCheckRequest {
+    lat: f64,
+    lon: f64,
+    scheduled_date: DateTime<Utc>,
+    training_level: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SafetyCheckResponse {
+    pub is_safe: bool,
+    pub violations: Vec<ViolationResponse>,
+    pub score: f32,
+    pub category: FlightCategory,
+    /// Forecast time the check was run against
+    pub forecast_time: DateTime<Utc>,
+}
+
+/// A structured violation plus its human-readable message
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ViolationResponse {
+    #[serde(flatten)]
+    pub violation: SafetyViolation,
+    pub message: String,
+}
+
+/// POST /api/weather/check - Preview whether a flight at this time and place
+/// would be safe for a training level, without creating a booking
+#[utoipa::path(
+    post,
+    path = "/api/v1/weather/check",
+    tag = "weather",
+    responses(
+        (status = 200, description = "Safety verdict for the forecast slot", body = SafetyCheckResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 502, description = "Weather provider unavailable", body = ApiError),
+        (status = 503, description = "Weather provider rate limit reached", body = ApiError),
+    )
+)]
+pub async fn check_weather_safety(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    ApiJson(req): ApiJson<SafetyCheckRequest>,
+) -> ApiResult<Json<SafetyCheckResponse>> {
+    let training_level = parse_training_level(&req.training_level)?;
+
+    let forecast = state
+        .weather_client
+        .fetch_forecast(req.lat, req.lon)
+        .await
+        .map_err(|e| {
+            tracing::error!("Forecast error for lat={}, lon={}: {}", req.lat, req.lon, e);
+            weather_api_error(e, "Unable to fetch forecast")
+        })?;
+
+    let weather = select_forecast_slot(&forecast, req.scheduled_date).ok_or_else(|| {
+        ApiError::validation_error(format!("No forecast available for {}", req.scheduled_date))
+    })?;
+
+    let minimums = load_flight_minimums(&state.db, training_level, None, None).await?;
+
+    Ok(Json(evaluate_safety(training_level, weather, &minimums, None)))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BookingWeatherParams {
+    /// Also evaluate the forecast slot nearest the flight
+    #[serde(default)]
+    pub include_forecast: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookingWeatherResponse {
+    pub booking_id: String,
+    pub location: Location,
+    pub scheduled_date: DateTime<Utc>,
+    /// Weather observed now at the departure airport
+    pub current: Option<WeatherEvaluation>,
+    /// Forecast slot nearest the flight, when requested and available
+    pub forecast: Option<WeatherEvaluation>,
+    /// False when the weather provider couldn't be reached for part of the
+    /// response; `warning` says which
+    pub weather_available: bool,
+    pub warning: Option<String>,
+}
+
+/// The weather exactly as the source reported it, with the verdict the
+/// scheduler would reach for this booking
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WeatherEvaluation {
+    pub weather: WeatherData,
+    #[serde(flatten)]
+    pub safety: SafetyCheckResponse,
+}
+
+/// GET /api/bookings/:id/weather - The weather behind a booking's safety
+/// decision, evaluated against the student's (and aircraft's) minimums
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings/{id}/weather",
+    tag = "weather",
+    params(
+        ("id" = String, Path, description = "Booking id"),
+        ("include_forecast" = Option<bool>, Query, description = "Also evaluate the forecast slot nearest the flight"),
+    ),
+    responses(
+        (status = 200, description = "Current weather and verdict; degraded when the provider is unavailable", body = BookingWeatherResponse),
+        (status = 404, description = "Booking or student not found", body = ApiError),
+    )
+)]
+pub async fn get_booking_weather(
+    Path(id): Path<String>,
+    Query(params): Query<BookingWeatherParams>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Json<BookingWeatherResponse>> {
+    let booking = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {} FROM bookings WHERE id = ? AND deleted_at IS NULL",
+        BOOKING_COLUMNS
+    ))
+    .bind(&id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Booking"))?;
+
+    let student = fetch_student(&state.db, &booking.student_id).await?;
+    let minimums = load_flight_minimums(
+        &state.db,
+        student.training_level,
+        booking.aircraft_id.as_deref(),
+        booking.weather_minimum_override.as_deref(),
+    )
+    .await?;
+    let location = &booking.departure_location;
+    let evaluate = |weather: WeatherData| WeatherEvaluation {
+        safety: evaluate_safety(student.training_level, &weather, &minimums, location.elevation_ft),
+        weather,
+    };
+
+    // Provider failures degrade the response instead of failing it
+    let mut unavailable = Vec::new();
+    let current = match state.weather_client.fetch_current_weather(location.lat, location.lon).await {
+        Ok(weather) => Some(evaluate(weather)),
+        Err(e) => {
+            tracing::warn!("Failed to fetch current weather for booking {}: {:#}", booking.id, e);
+            unavailable.push("current weather");
+            None
+        }
+    };
+
+    let forecast = if params.include_forecast {
+        match state.weather_client.fetch_forecast(location.lat, location.lon).await {
+            Ok(forecast) => select_forecast_slot(&forecast, booking.scheduled_date).cloned().map(evaluate),
+            Err(e) => {
+                tracing::warn!("Failed to fetch forecast for booking {}: {:#}", booking.id, e);
+                unavailable.push("forecast");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let warning = (!unavailable.is_empty())
+        .then(|| format!("Weather provider unavailable: no {}", unavailable.join(" or ")));
+
+    Ok(Json(BookingWeatherResponse {
+        booking_id: booking.id.clone(),
+        location: booking.departure_location.clone(),
+        scheduled_date: booking.scheduled_date,
+        current,
+        forecast,
+        weather_available: unavailable.is_empty(),
+        warning,
+    }))
+}
+
+/// Keep the meaning of a typed `WeatherError` (bad key, rate limit, unknown
+/// location); anything else the source reports is a provider error
+fn weather_api_error(err: anyhow::Error, context: &str) -> ApiError {
+    match err.downcast::<WeatherError>() {
+        Ok(err) => err.into(),
+        Err(err) => ApiError::external_api_error("OpenWeatherMap", format!("{}: {:#}", context, err)),
+    }
+}
+
+fn evaluate_safety(
+    training_level: TrainingLevel,
+    weather: &WeatherData,
+    minimums: &WeatherMinimum,
+    elevation_ft: Option<f64>,
+) -> SafetyCheckResponse {
+    let (is_safe, violations) = is_flight_safe(&training_level, weather, minimums, None, elevation_ft);
+
+    SafetyCheckResponse {
+        is_safe,
+        violations: violations
+            .into_iter()
+            .map(|violation| ViolationResponse {
+                message: violation.to_string(),
+                violation,
+            })
+            .collect(),
+        score: calculate_weather_score(&training_level, weather),
+        category: flight_category(weather),
+        forecast_time: weather.date_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert_test_student, setup_test_db, test_state};
+    use chrono::Utc;
+
+    fn sample_weather() -> WeatherData {
+        WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots: 10.0,
+            wind_gust_knots: Some(20.0),
+            ceiling_ft: Some(3000.0),
+            temperature_f: 68.0,
+            conditions: "Clear".to_string(),
+            has_thunderstorms: false,
+            has_icing: false,
+            date_time: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_units_are_imperial() {
+        let query: WeatherQuery = serde_json::from_str(r#"{ "lat": 33.8, "lon": -118.1 }"#).unwrap();
+        assert_eq!(query.units, Units::Imperial);
+
+        let json = serde_json::to_value(WeatherReadings::new(&sample_weather(), query.units)).unwrap();
+        assert_eq!(json["temperature_f"], 68.0);
+        assert_eq!(json["visibility_miles"], 10.0);
+        assert!(json.get("temperature_c").is_none());
+    }
+
+    #[test]
+    fn test_evaluate_safety_reports_structured_violations() {
+        let minimums = weather_core::weather::default_weather_minimums();
+        let student = &minimums[&TrainingLevel::StudentPilot];
+
+        let calm = WeatherData {
+            wind_speed_knots: 5.0,
+            wind_gust_knots: None,
+            ceiling_ft: Some(5000.0),
+            ..sample_weather()
+        };
+        let check = evaluate_safety(TrainingLevel::StudentPilot, &calm, student, None);
+        assert!(check.is_safe, "{:?}", check.violations);
+        assert!(check.violations.is_empty());
+        assert_eq!(check.category, FlightCategory::Vfr);
+
+        let stormy = WeatherData {
+            has_thunderstorms: true,
+            ..calm
+        };
+        let check = evaluate_safety(TrainingLevel::StudentPilot, &stormy, student, None);
+        assert!(!check.is_safe);
+
+        let json = serde_json::to_value(&check).unwrap();
+        assert_eq!(json["violations"][0]["code"], "thunderstorms");
+        assert_eq!(json["violations"][0]["message"], SafetyViolation::Thunderstorms.to_string());
+        assert_eq!(json["category"], "VFR");
+    }
+
+    #[test]
+    fn test_daily_outlook_uses_local_date_and_units() {
+        let day = DailyForecast {
+            weather: WeatherData {
+                // 02:00 UTC is the previous evening in Los Angeles
+                date_time: DateTime::parse_from_rfc3339("2025-06-02T02:00:00Z").unwrap().with_timezone(&Utc),
+                utc_offset_seconds: Some(-7 * 3600),
+                ..sample_weather()
+            },
+            temp_high_f: 86.0,
+            temp_low_f: 50.0,
+            precipitation_probability: 0.3,
+        };
+
+        let json = serde_json::to_value(DailyOutlook::new(&day, Units::Imperial)).unwrap();
+        assert_eq!(json["date"], "2025-06-01");
+        assert_eq!(json["temp_high_f"], 86.0);
+        assert_eq!(json["wind_speed_knots"], 10.0);
+        assert_eq!(json["flight_category"], "VFR");
+        assert_eq!(json["precipitation_probability"], 0.3);
+
+        let json = serde_json::to_value(DailyOutlook::new(&day, Units::Metric)).unwrap();
+        assert!((json["temp_high_c"].as_f64().unwrap() - 30.0).abs() < 1e-9);
+        assert!((json["temp_low_c"].as_f64().unwrap() - 10.0).abs() < 1e-9);
+        assert!(json.get("temp_high_f").is_none());
+    }
+
+    async fn setup_booking() -> (sqlx::SqlitePool, String) {
+        let db = setup_test_db().await;
+        insert_test_student(&db).await;
+        sqlx::query(
+            "INSERT INTO bookings (id, student_id, scheduled_date, departure_location, status)
+             VALUES ('booking1', 'student1', ?, '{\"lat\": 33.8, \"lon\": -118.1, \"name\": \"KTOA\"}', 'SCHEDULED')"
+        )
+        .bind(Utc::now() + chrono::Duration::hours(6))
+        .execute(&db)
+        .await
+        .unwrap();
+
+        (db, "booking1".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_booking_weather_reports_source_data_and_verdict() {
+        let (db, booking_id) = setup_booking().await;
+        let gusty = WeatherData {
+            wind_speed_knots: 18.0,
+            wind_gust_knots: Some(28.0),
+            ..sample_weather()
+        };
+        let forecast = vec![WeatherData {
+            date_time: Utc::now() + chrono::Duration::hours(6),
+            wind_speed_knots: 5.0,
+            wind_gust_knots: None,
+            ceiling_ft: Some(5000.0),
+            ..sample_weather()
+        }];
+        let weather_client = weather_core::weather::MockWeatherClient::new()
+            .with_current(gusty)
+            .with_forecast(forecast);
+        let state = test_state(db.clone(), weather_client);
+
+        let Json(response) = get_booking_weather(
+            Path(booking_id.clone()),
+            Query(BookingWeatherParams { include_forecast: true }),
+            axum::extract::State(state),
+        )
+        .await
+        .unwrap();
+
+        assert!(response.weather_available);
+        let current = response.current.unwrap();
+        assert_eq!(current.weather.wind_gust_knots, Some(28.0));
+        assert!(!current.safety.is_safe);
+        assert!(!current.safety.violations.is_empty());
+        let forecast = response.forecast.unwrap();
+        assert!(forecast.safety.is_safe, "{:?}", forecast.safety.violations);
+
+        // Provider down: still a 200, flagged as degraded
+        let state = test_state(db.clone(), weather_core::weather::MockWeatherClient::new().with_current_error("API down"));
+        let Json(response) = get_booking_weather(
+            Path(booking_id),
+            Query(BookingWeatherParams::default()),
+            axum::extract::State(state),
+        )
+        .await
+        .unwrap();
+        assert!(!response.weather_available);
+        assert!(response.current.is_none());
+        assert!(response.warning.unwrap().contains("current weather"));
+
+        let state = test_state(db, weather_core::weather::MockWeatherClient::new());
+        let err = get_booking_weather(
+            Path("missing".to_string()),
+            Query(BookingWeatherParams::default()),
+            axum::extract::State(state),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_metric_conversion() {
+        let json = serde_json::to_value(WeatherReadings::new(&sample_weather(), Units::Metric)).unwrap();
+
+        assert!((json["temperature_c"].as_f64().unwrap() - 20.0).abs() < 1e-9);
+        assert!((json["visibility_km"].as_f64().unwrap() - 16.09344).abs() < 1e-6);
+        assert!((json["wind_speed_ms"].as_f64().unwrap() - 5.144).abs() < 1e-3);
+        assert!((json["ceiling_m"].as_f64().unwrap() - 914.4).abs() < 1e-6);
+        assert!(json.get("temperature_f").is_none());
+    }
+}
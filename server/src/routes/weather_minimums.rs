@@ -0,0 +1,218 @@
+use crate::{
+    auth::ApiRole,
+    error::{ApiError, ApiJson, ApiResult},
+    routes::nullable,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use weather_core::models::{TrainingLevel, WeatherMinimum};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+const MINIMUM_COLUMNS: &str = "id, training_level, min_visibility_sm, max_wind_speed_kt, max_crosswind_kt, gust_margin_kt,
+        max_density_altitude_ft, min_ceiling_ft, allow_imc, no_thunderstorms, no_icing, allow_night";
+
+/// Partial update; omitted fields keep their current value and a `null`
+/// ceiling removes the ceiling minimum
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateWeatherMinimumRequest {
+    pub min_visibility_sm: Option<f64>,
+    pub max_wind_speed_kt: Option<f64>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub min_ceiling_ft: Option<Option<f64>>,
+    pub allow_imc: Option<bool>,
+    pub no_thunderstorms: Option<bool>,
+    pub no_icing: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WeatherMinimumResponse {
+    pub id: String,
+    pub training_level: String,
+    pub min_visibility_sm: f64,
+    pub max_wind_speed_kt: f64,
+    pub max_crosswind_kt: Option<f64>,
+    pub gust_margin_kt: f64,
+    pub max_density_altitude_ft: Option<f64>,
+    pub min_ceiling_ft: Option<f64>,
+    pub allow_imc: bool,
+    pub no_thunderstorms: bool,
+    pub no_icing: bool,
+    pub allow_night: bool,
+}
+
+impl From<WeatherMinimum> for WeatherMinimumResponse {
+    fn from(minimum: WeatherMinimum) -> Self {
+        Self {
+            id: minimum.id,
+            training_level: minimum.training_level.as_str().to_string(),
+            min_visibility_sm: minimum.min_visibility_sm,
+            max_wind_speed_kt: minimum.max_wind_speed_kt,
+            max_crosswind_kt: minimum.max_crosswind_kt,
+            gust_margin_kt: minimum.gust_margin_kt,
+            max_density_altitude_ft: minimum.max_density_altitude_ft,
+            min_ceiling_ft: minimum.min_ceiling_ft,
+            allow_imc: minimum.allow_imc,
+            no_thunderstorms: minimum.no_thunderstorms,
+            no_icing: minimum.no_icing,
+            allow_night: minimum.allow_night,
+        }
+    }
+}
+
+/// GET /api/weather-minimums - Weather minimums for every training level
+#[utoipa::path(
+    get,
+    path = "/api/v1/weather-minimums",
+    tag = "weather-minimums",
+    responses(
+        (status = 200, description = "Minimums for every training level"),
+    )
+)]
+pub async fn list_weather_minimums(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<WeatherMinimumResponse>>> {
+    let minimums = sqlx::query_as::<_, WeatherMinimum>(&format!(
+        "SELECT {} FROM weather_minimums ORDER BY min_visibility_sm DESC",
+        MINIMUM_COLUMNS
+    ))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(minimums.into_iter().map(WeatherMinimumResponse::from).collect()))
+}
+
+/// PATCH /api/weather-minimums/:training_level - Update minimums for one training level
+/// Requires an admin API key
+#[utoipa::path(
+    patch,
+    path = "/api/v1/weather-minimums/{training_level}",
+    tag = "weather-minimums",
+    params(("training_level" = TrainingLevel, Path, description = "Training level to update")),
+    responses(
+        (status = 200, description = "Updated minimums"),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 403, description = "Admin API key required", body = ApiError),
+    )
+)]
+pub async fn update_weather_minimum(
+    Path(training_level): Path<String>,
+    State(state): State<AppState>,
+    Extension(role): Extension<ApiRole>,
+    ApiJson(req): ApiJson<UpdateWeatherMinimumRequest>,
+) -> ApiResult<Json<WeatherMinimumResponse>> {
+    if !role.satisfies(ApiRole::Admin) {
+        return Err(ApiError::forbidden("Updating weather minimums requires an admin API key"));
+    }
+
+    let training_level = TrainingLevel::try_from(training_level).map_err(|e| {
+        ApiError::validation_error(format!(
+            "{}. Must be one of: STUDENT_PILOT, PRIVATE_PILOT, INSTRUMENT_RATED",
+            e
+        ))
+    })?;
+
+    let updated = apply_minimum_update(&state.db, training_level, &req).await?;
+
+    tracing::info!("Updated weather minimums for {}", training_level.as_str());
+    Ok(Json(updated.into()))
+}
+
+async fn apply_minimum_update(
+    db: &SqlitePool,
+    training_level: TrainingLevel,
+    req: &UpdateWeatherMinimumRequest,
+) -> ApiResult<WeatherMinimum> {
+    let non_negative = [
+        ("min_visibility_sm", req.min_visibility_sm),
+        ("max_wind_speed_kt", req.max_wind_speed_kt),
+        ("min_ceiling_ft", req.min_ceiling_ft.flatten()),
+    ];
+    for (field, value) in non_negative {
+        if value.is_some_and(|v| v < 0.0 || !v.is_finite()) {
+            return Err(ApiError::validation_error(format!("{} must be a non-negative number", field)));
+        }
+    }
+
+    let current = sqlx::query_as::<_, WeatherMinimum>(&format!(
+        "SELECT {} FROM weather_minimums WHERE training_level = ?",
+        MINIMUM_COLUMNS
+    ))
+    .bind(training_level.as_str())
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Weather minimums"))?;
+
+    sqlx::query(
+        "UPDATE weather_minimums
+         SET min_visibility_sm = ?, max_wind_speed_kt = ?, min_ceiling_ft = ?,
+             allow_imc = ?, no_thunderstorms = ?, no_icing = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?"
+    )
+    .bind(req.min_visibility_sm.unwrap_or(current.min_visibility_sm))
+    .bind(req.max_wind_speed_kt.unwrap_or(current.max_wind_speed_kt))
+    .bind(req.min_ceiling_ft.unwrap_or(current.min_ceiling_ft))
+    .bind(req.allow_imc.unwrap_or(current.allow_imc))
+    .bind(req.no_thunderstorms.unwrap_or(current.no_thunderstorms))
+    .bind(req.no_icing.unwrap_or(current.no_icing))
+    .bind(&current.id)
+    .execute(db)
+    .await?;
+
+    let updated = sqlx::query_as::<_, WeatherMinimum>(&format!(
+        "SELECT {} FROM weather_minimums WHERE id = ?",
+        MINIMUM_COLUMNS
+    ))
+    .bind(&current.id)
+    .fetch_one(db)
+    .await?;
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    #[tokio::test]
+    async fn test_partial_update_keeps_other_fields() {
+        let db = setup_test_db().await;
+        let req = UpdateWeatherMinimumRequest {
+            max_wind_speed_kt: Some(15.0),
+            ..Default::default()
+        };
+
+        let updated = apply_minimum_update(&db, TrainingLevel::StudentPilot, &req).await.unwrap();
+
+        assert_eq!(updated.max_wind_speed_kt, 15.0);
+        assert_eq!(updated.min_visibility_sm, 5.0);
+        assert_eq!(updated.min_ceiling_ft, Some(3000.0));
+    }
+
+    #[tokio::test]
+    async fn test_null_ceiling_clears_it() {
+        let db = setup_test_db().await;
+        let req: UpdateWeatherMinimumRequest = serde_json::from_str(r#"{"min_ceiling_ft": null}"#).unwrap();
+
+        let updated = apply_minimum_update(&db, TrainingLevel::StudentPilot, &req).await.unwrap();
+
+        assert_eq!(updated.min_ceiling_ft, None);
+        assert_eq!(updated.min_visibility_sm, 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_negative_visibility_rejected() {
+        let db = setup_test_db().await;
+        let req = UpdateWeatherMinimumRequest {
+            min_visibility_sm: Some(-1.0),
+            ..Default::default()
+        };
+
+        let err = apply_minimum_update(&db, TrainingLevel::PrivatePilot, &req).await.unwrap_err();
+        assert_eq!(err.error.code, "VALIDATION_ERROR");
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{AppState, NotificationChannel};
+use crate::{
+    routes::alerts::{active_alerts, WeatherAlert, SEVERITIES},
+    AppState, NotificationChannel,
+};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -7,24 +10,86 @@ use axum::{
     response::Response,
 };
 use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::time::Duration;
+use tokio::sync::watch;
 use tokio::time::interval;
 
+/// First frame sent on connect: alerts that fired before the client connected.
+/// Tagged `"type": "snapshot"` to distinguish it from live notifications.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename = "snapshot")]
+struct SnapshotMessage {
+    alerts: Vec<WeatherAlert>,
+}
+
+/// Client filter, sent as `{ "subscribe": { "student_id": "...", "min_severity": "high" } }`.
+/// Without one, the client receives every notification.
+#[derive(Debug, Default, Clone, Deserialize)]
+struct Subscription {
+    student_id: Option<String>,
+    min_severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscribeMessage {
+    subscribe: Subscription,
+}
+
+impl Subscription {
+    /// Notifications without a `student_id` (e.g. government alerts) reach every
+    /// student, and `min_severity` only filters notifications that carry a severity
+    fn matches(&self, notification: &serde_json::Value) -> bool {
+        if let (Some(wanted), Some(student_id)) = (&self.student_id, notification["student_id"].as_str()) {
+            if wanted != student_id {
+                return false;
+            }
+        }
+
+        if let (Some(min), Some(severity)) = (&self.min_severity, notification["severity"].as_str()) {
+            if let (Some(min_rank), Some(rank)) = (severity_rank(min), severity_rank(severity)) {
+                return rank <= min_rank;
+            }
+        }
+
+        true
+    }
+}
+
+/// Position in SEVERITIES, where 0 is the most severe
+fn severity_rank(severity: &str) -> Option<usize> {
+    SEVERITIES.iter().position(|s| *s == severity)
+}
+
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
 ) -> Response {
-    ws.on_upgrade(move |socket| handle_socket(socket, state.notification_tx))
+    ws.on_upgrade(move |socket| handle_socket(socket, state.db, state.notification_tx))
 }
 
-async fn handle_socket(socket: WebSocket, tx: NotificationChannel) {
+async fn handle_socket(socket: WebSocket, db: SqlitePool, tx: NotificationChannel) {
     let (mut sender, mut receiver) = socket.split();
 
-    // Subscribe to broadcast channel
+    // Subscribe to broadcast channel before taking the snapshot so no alert
+    // falls between the two
     let mut rx = tx.subscribe();
 
+    // Latest subscription from the client, read by the send task
+    let (subscription_tx, subscription_rx) = watch::channel(Subscription::default());
+
     // Spawn task to send notifications
     let mut send_task = tokio::spawn(async move {
+        match snapshot_message(&db).await {
+            Ok(snapshot) => {
+                if sender.send(Message::Text(snapshot)).await.is_err() {
+                    return;
+                }
+            }
+            Err(e) => tracing::error!("Failed to load alert snapshot: {}", e),
+        }
+
         // Send periodic pings
         let mut ping_interval = interval(Duration::from_secs(30));
 
@@ -34,6 +99,9 @@ async fn handle_socket(socket: WebSocket, tx: NotificationChannel) {
                 msg = rx.recv() => {
                     match msg {
                         Ok(notification) => {
+                            if !should_forward(&subscription_rx.borrow(), &notification) {
+                                continue;
+                            }
                             if sender.send(Message::Text(notification)).await.is_err() {
                                 break;
                             }
@@ -51,10 +119,22 @@ async fn handle_socket(socket: WebSocket, tx: NotificationChannel) {
         }
     });
 
-    // Spawn task to receive messages (mainly pongs)
+    // Spawn task to receive messages (subscriptions and pongs)
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
             match msg {
+                Message::Text(text) => match serde_json::from_str::<SubscribeMessage>(&text) {
+                    Ok(SubscribeMessage { subscribe }) => {
+                        if let Some(min) = &subscribe.min_severity {
+                            if severity_rank(min).is_none() {
+                                tracing::warn!("Ignoring unknown min_severity in subscription: {}", min);
+                            }
+                        }
+                        tracing::debug!("WebSocket subscription updated: {:?}", subscribe);
+                        let _ = subscription_tx.send(subscribe);
+                    }
+                    Err(e) => tracing::debug!("Ignoring unrecognized WebSocket message: {}", e),
+                },
                 Message::Pong(_) => {
                     // Client is alive
                 }
@@ -78,3 +158,74 @@ async fn handle_socket(socket: WebSocket, tx: NotificationChannel) {
 
     tracing::info!("WebSocket connection closed");
 }
+
+fn should_forward(subscription: &Subscription, notification: &str) -> bool {
+    match serde_json::from_str::<serde_json::Value>(notification) {
+        Ok(value) => subscription.matches(&value),
+        // Forward anything we can't inspect rather than silently dropping it
+        Err(_) => true,
+    }
+}
+
+async fn snapshot_message(db: &SqlitePool) -> anyhow::Result<String> {
+    let alerts = active_alerts(db).await?;
+    tracing::debug!("Sending snapshot of {} active alerts", alerts.len());
+    Ok(serde_json::to_string(&SnapshotMessage { alerts })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::test_support::setup_test_db;
+
+    #[test]
+    fn test_subscription_filters_by_student_and_severity() {
+        let SubscribeMessage { subscribe } = serde_json::from_str(
+            r#"{ "subscribe": { "student_id": "s1", "min_severity": "high" } }"#,
+        )
+        .unwrap();
+
+        let severe_for_s1 = r#"{"type":"weather_alert","student_id":"s1","severity":"severe"}"#;
+        let moderate_for_s1 = r#"{"type":"weather_alert","student_id":"s1","severity":"moderate"}"#;
+        let high_for_s2 = r#"{"type":"weather_alert","student_id":"s2","severity":"high"}"#;
+        let government = r#"{"type":"weather_alert","severity":"severe"}"#;
+        let cancelled_for_s1 = r#"{"type":"booking_cancelled","student_id":"s1"}"#;
+
+        assert!(should_forward(&subscribe, severe_for_s1));
+        assert!(!should_forward(&subscribe, moderate_for_s1));
+        assert!(!should_forward(&subscribe, high_for_s2));
+        assert!(should_forward(&subscribe, government));
+        assert!(should_forward(&subscribe, cancelled_for_s1));
+
+        // No subscription receives everything
+        let everything = Subscription::default();
+        assert!(should_forward(&everything, moderate_for_s1));
+        assert!(should_forward(&everything, high_for_s2));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_contains_only_active_alerts() {
+        let db = setup_test_db().await;
+        for (id, dismissed_at) in [("active", None), ("dismissed", Some(Utc::now()))] {
+            sqlx::query(
+                "INSERT INTO weather_alerts (id, severity, message, location, created_at, dismissed_at)
+                 VALUES (?, 'high', 'Test alert', '(33.8113, -118.1515)', ?, ?)"
+            )
+            .bind(id)
+            .bind(Utc::now())
+            .bind(dismissed_at)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let frame: serde_json::Value =
+            serde_json::from_str(&snapshot_message(&db).await.unwrap()).unwrap();
+
+        assert_eq!(frame["type"], "snapshot");
+        let alerts = frame["alerts"].as_array().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0]["id"], "active");
+    }
+}
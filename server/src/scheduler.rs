@@ -1,29 +1,246 @@
-use crate::NotificationChannel;
-use chrono::{Duration, Utc};
-use core::models::{Booking, BookingStatus};
-use serde_json::json;
+use crate::{routes::students::STUDENT_COLUMNS, telemetry, AppState, NotificationChannel};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use weather_core::ai::{AiRescheduleClient, DEFAULT_RESCHEDULE_OPTIONS};
+use weather_core::models::{
+    booking_interval, Booking, BookingStatus, BookingStatusEvent, Notification, RescheduleEvent, Student, WeatherMinimum,
+    BOOKING_COLUMNS, SUGGESTED_BY_SYSTEM,
+};
+use weather_core::notifications::{
+    create_webhook_notifier, format_conflict_sms, format_hold_released_sms, format_weather_hold_sms, EmailProvider,
+    SmsProvider, WebhookNotifier,
+};
+use weather_core::weather::{
+    weather_trend, OneCallAlert, WeatherData, WeatherSource, WeatherTrend,
+};
 use sqlx::SqlitePool;
+use std::str::FromStr;
+use std::sync::Arc;
 use tokio_cron_scheduler::{Job, JobScheduler};
+use tokio_util::sync::CancellationToken;
 
+/// Sends the student email and SMS when a booking is held or cancelled for
+/// weather, and the school's webhook when one is configured
+#[derive(Clone)]
+pub struct ConflictNotifier {
+    pub ai_client: Arc<AiRescheduleClient>,
+    pub email_provider: Arc<dyn EmailProvider>,
+    pub sms_provider: Arc<dyn SmsProvider>,
+    /// Copied on every conflict email, e.g. a dispatch inbox (`CC_EMAILS`)
+    pub cc_emails: Vec<String>,
+    /// Receives every hold, cancellation and release (`WEBHOOK_URL`)
+    pub webhook: Option<Arc<WebhookNotifier>>,
+}
+
+impl ConflictNotifier {
+    /// Send conflict notifications on the channels the student opted into;
+    /// failures are logged but never returned so they can't undo a cancellation.
+    /// The email copies the instructor, when known, and `cc_emails`.
+    async fn notify(
+        &self,
+        booking: &Booking,
+        student: &Student,
+        instructor_email: Option<&str>,
+        forecast: &[WeatherData],
+        instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+    ) {
+        if student.notify_email {
+            let mut cc: Vec<String> = instructor_email.map(str::to_string).into_iter().collect();
+            for email in &self.cc_emails {
+                if !cc.contains(email) {
+                    cc.push(email.clone());
+                }
+            }
+            self.send_email(booking, student, &cc, forecast, instructor_schedule, minimums).await;
+        } else {
+            tracing::debug!("Student {} opted out of email, skipping for booking {}", student.id, booking.id);
+        }
+
+        if student.notify_sms {
+            let date = booking.scheduled_date.format("%Y-%m-%d %H:%M UTC").to_string();
+            let message = match booking.status {
+                BookingStatus::WeatherHold => format_weather_hold_sms(&student.name, &date),
+                _ => format_conflict_sms(&student.name, &date),
+            };
+            if let Err(e) = self.sms_provider.send_sms(&student.phone, &message).await {
+                tracing::error!("Failed to send conflict SMS for booking {}: {}", booking.id, e);
+            }
+        } else {
+            tracing::debug!("Student {} opted out of SMS, skipping for booking {}", student.id, booking.id);
+        }
+    }
+
+    /// Tell the student a held booking is back on; SMS only, as there are no
+    /// reschedule options to send
+    async fn notify_hold_released(&self, booking: &Booking, student: &Student) {
+        if !student.notify_sms {
+            return;
+        }
+
+        let message = format_hold_released_sms(
+            &student.name,
+            &booking.scheduled_date.format("%Y-%m-%d %H:%M UTC").to_string(),
+        );
+        if let Err(e) = self.sms_provider.send_sms(&student.phone, &message).await {
+            tracing::error!("Failed to send hold release SMS for booking {}: {}", booking.id, e);
+        }
+    }
+
+    /// POST the notification to the webhook, if any; like email and SMS,
+    /// failures are only logged
+    async fn send_webhook(&self, notification: &Notification) {
+        let Some(webhook) = &self.webhook else {
+            return;
+        };
+        if let Err(e) = webhook.send(notification).await {
+            tracing::error!("Failed to deliver webhook notification: {:#}", e);
+        }
+    }
+
+    /// Email the cancellation with reschedule options
+    async fn send_email(
+        &self,
+        booking: &Booking,
+        student: &Student,
+        cc: &[String],
+        forecast: &[WeatherData],
+        instructor_schedule: &[Booking],
+        minimums: &WeatherMinimum,
+    ) {
+        let options = match self
+            .ai_client
+            .generate_reschedule_options(
+                booking,
+                student,
+                forecast,
+                instructor_schedule,
+                minimums,
+                DEFAULT_RESCHEDULE_OPTIONS,
+            )
+            .await
+        {
+            Ok(options) => options,
+            Err(e) => {
+                tracing::error!("Failed to generate reschedule options for booking {}: {}", booking.id, e);
+                vec![]
+            }
+        };
+
+        if let Err(e) = self
+            .email_provider
+            .send_conflict_email(&student.email, cc, booking, &options)
+            .await
+        {
+            tracing::error!("Failed to send conflict email for booking {}: {}", booking.id, e);
+        }
+    }
+}
+
+/// Default conflict check: every hour at minute 0
+pub const DEFAULT_CONFLICT_CRON: &str = "0 0 * * * *";
+
+/// Default alert check: every 5 minutes
+pub const DEFAULT_ALERT_CRON: &str = "0 */5 * * * *";
+
+/// Default alert threshold: anything short of near-perfect weather alerts
+pub const DEFAULT_ALERT_SCORE_THRESHOLD: f32 = 9.0;
+
+/// Cron schedules (with seconds field) and alerting settings for the
+/// background jobs
+#[derive(Debug, Clone)]
+pub struct ScheduleConfig {
+    pub conflict_cron: String,
+    pub alert_cron: String,
+    /// Weather scores (0-10) below this produce an alert
+    pub alert_score_threshold: f32,
+}
+
+impl ScheduleConfig {
+    /// Read `CONFLICT_CRON`, `ALERT_CRON` and `ALERT_SCORE_THRESHOLD`, falling
+    /// back to the defaults, and reject invalid values up front
+    pub fn from_env() -> anyhow::Result<Self> {
+        let config = Self::new(
+            std::env::var("CONFLICT_CRON").unwrap_or_else(|_| DEFAULT_CONFLICT_CRON.to_string()),
+            std::env::var("ALERT_CRON").unwrap_or_else(|_| DEFAULT_ALERT_CRON.to_string()),
+        )?;
+
+        match std::env::var("ALERT_SCORE_THRESHOLD") {
+            Ok(value) => {
+                let threshold = value.parse().map_err(|_| {
+                    anyhow::anyhow!("Invalid ALERT_SCORE_THRESHOLD '{}': not a number", value)
+                })?;
+                config.with_alert_score_threshold(threshold)
+            }
+            Err(_) => Ok(config),
+        }
+    }
+
+    pub fn new(conflict_cron: String, alert_cron: String) -> anyhow::Result<Self> {
+        validate_cron("CONFLICT_CRON", &conflict_cron)?;
+        validate_cron("ALERT_CRON", &alert_cron)?;
+
+        Ok(Self {
+            conflict_cron,
+            alert_cron,
+            alert_score_threshold: DEFAULT_ALERT_SCORE_THRESHOLD,
+        })
+    }
+
+    /// Alert only when the weather score falls below `threshold`, which must
+    /// be within the 0-10 score range
+    pub fn with_alert_score_threshold(mut self, threshold: f32) -> anyhow::Result<Self> {
+        if !(0.0..=10.0).contains(&threshold) {
+            anyhow::bail!("Invalid ALERT_SCORE_THRESHOLD {}: must be between 0 and 10", threshold);
+        }
+        self.alert_score_threshold = threshold;
+        Ok(self)
+    }
+}
+
+fn validate_cron(name: &str, expression: &str) -> anyhow::Result<()> {
+    cron::Schedule::from_str(expression)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Invalid {} expression '{}': {}", name, expression, e))
+}
+
+/// Run the conflict and alert jobs until `shutdown` is cancelled, using the
+/// database, channel, AI client and weather source from `state`
 pub async fn start_weather_monitor(
-    db: SqlitePool,
-    notification_tx: NotificationChannel,
+    state: AppState,
+    email_provider: Arc<dyn EmailProvider>,
+    sms_provider: Arc<dyn SmsProvider>,
+    schedule: ScheduleConfig,
+    shutdown: CancellationToken,
 ) -> anyhow::Result<()> {
     tracing::info!("Starting weather monitoring scheduler...");
 
-    let scheduler = JobScheduler::new().await?;
+    let AppState { db, notification_tx, ai_client, weather_client } = state;
+    let notifier = ConflictNotifier {
+        ai_client,
+        email_provider,
+        sms_provider,
+        cc_emails: cc_emails_from_env(),
+        webhook: create_webhook_notifier().map(Arc::new),
+    };
+
+    let mut scheduler = JobScheduler::new().await?;
 
-    // Job 1: Run every hour (at minute 0) - Conflict detection
+    // Job 1: Conflict detection (hourly by default)
     let hourly_db = db.clone();
     let hourly_tx = notification_tx.clone();
-    let hourly_job = Job::new_async("0 0 * * * *", move |_uuid, _lock| {
+    let hourly_notifier = notifier.clone();
+    let hourly_weather = weather_client.clone();
+    let hourly_job = Job::new_async(schedule.conflict_cron.as_str(), move |_uuid, _lock| {
         let db = hourly_db.clone();
         let tx = hourly_tx.clone();
+        let notifier = hourly_notifier.clone();
+        let weather_client = hourly_weather.clone();
 
         Box::pin(async move {
-            tracing::info!("Running hourly weather check...");
+            tracing::info!("Running scheduled weather check...");
 
-            match check_all_flights(&db, &tx).await {
+            match check_all_flights(&db, &tx, &notifier, weather_client.as_ref()).await {
                 Ok(summary) => {
                     tracing::info!(
                         "Weather check completed: {} flights checked, {} conflicts found",
@@ -38,17 +255,19 @@ pub async fn start_weather_monitor(
         })
     })?;
 
-    // Job 2: Run every 5 minutes - Weather alert generation
+    // Job 2: Weather alert generation (every 5 minutes by default)
     let alert_db = db.clone();
     let alert_tx = notification_tx.clone();
-    let alert_job = Job::new_async("0 */5 * * * *", move |_uuid, _lock| {
+    let alert_score_threshold = schedule.alert_score_threshold;
+    let alert_job = Job::new_async(schedule.alert_cron.as_str(), move |_uuid, _lock| {
         let db = alert_db.clone();
         let tx = alert_tx.clone();
+        let weather_client = weather_client.clone();
 
         Box::pin(async move {
-            tracing::info!("Running 5-minute weather alert check...");
+            tracing::info!("Running scheduled weather alert check...");
 
-            match generate_weather_alerts(&db, &tx).await {
+            match generate_weather_alerts(&db, &tx, weather_client.as_ref(), alert_score_threshold).await {
                 Ok(alert_count) => {
                     tracing::info!("Generated {} weather alerts", alert_count);
                 }
@@ -63,14 +282,73 @@ pub async fn start_weather_monitor(
     scheduler.add(alert_job).await?;
     scheduler.start().await?;
 
-    tracing::info!("Weather monitoring scheduler started (hourly conflicts + 5-minute alerts)");
+    tracing::info!(
+        "Weather monitoring scheduler started (conflicts: '{}', alerts: '{}')",
+        schedule.conflict_cron,
+        schedule.alert_cron
+    );
+
+    // Keep scheduler running until the server shuts down
+    shutdown.cancelled().await;
 
-    // Keep scheduler running
-    tokio::time::sleep(tokio::time::Duration::from_secs(u64::MAX)).await;
+    tracing::info!("Stopping weather monitoring scheduler...");
+    scheduler.shutdown().await?;
+    tracing::info!("Weather monitoring scheduler stopped");
 
     Ok(())
 }
 
+/// Largest gap allowed between a booking and the nearest forecast entry
+const MAX_FORECAST_SLOT_GAP_HOURS: i64 = 3;
+
+/// Window in which a repeat alert for the same booking and severity is suppressed
+const ALERT_DEDUP_WINDOW_HOURS: i64 = 6;
+
+/// Forecast requests the alert pass runs at once
+const ALERT_FORECAST_CONCURRENCY: usize = 8;
+
+/// Default for `WEATHER_HOLD_CANCEL_HOURS`
+pub const DEFAULT_HOLD_CANCEL_HOURS: i64 = 6;
+
+/// How long before the flight a booking that is still unsafe gets cancelled
+/// instead of held, from `WEATHER_HOLD_CANCEL_HOURS`
+fn hold_cancel_window_from_env() -> Duration {
+    hold_cancel_window_from_value(std::env::var("WEATHER_HOLD_CANCEL_HOURS").ok().as_deref())
+}
+
+/// Cancellation window from a raw hours value, falling back to
+/// `DEFAULT_HOLD_CANCEL_HOURS` when it is missing, unparseable or negative
+fn hold_cancel_window_from_value(value: Option<&str>) -> Duration {
+    let hours = match value.and_then(|s| s.parse::<i64>().ok()) {
+        Some(hours) if hours < 0 => {
+            tracing::warn!(
+                "Ignoring negative WEATHER_HOLD_CANCEL_HOURS ({}), using {}",
+                hours,
+                DEFAULT_HOLD_CANCEL_HOURS
+            );
+            DEFAULT_HOLD_CANCEL_HOURS
+        }
+        Some(hours) => hours,
+        None => DEFAULT_HOLD_CANCEL_HOURS,
+    };
+
+    Duration::hours(hours)
+}
+
+/// Addresses copied on every conflict email, from the comma-separated `CC_EMAILS`
+pub fn cc_emails_from_env() -> Vec<String> {
+    std::env::var("CC_EMAILS")
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|email| !email.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Debug)]
 pub struct ConflictSummary {
     pub total_checked: usize,
@@ -80,21 +358,26 @@ pub struct ConflictSummary {
 async fn check_all_flights(
     db: &SqlitePool,
     notification_tx: &NotificationChannel,
+    notifier: &ConflictNotifier,
+    weather_source: &dyn WeatherSource,
 ) -> anyhow::Result<ConflictSummary> {
-    use core::weather::WeatherClient;
     use std::collections::HashMap;
 
     let now = Utc::now();
     let check_until = now + Duration::hours(48);
+    let cancel_within = hold_cancel_window_from_env();
 
-    // Query bookings in next 48 hours
-    let bookings = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, scheduled_date, departure_location, status
+    // Query bookings in next 48 hours, including held ones so they can be
+    // released or cancelled
+    let bookings = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {}
          FROM bookings
-         WHERE status = 'SCHEDULED'
+         WHERE status IN ('SCHEDULED', 'WEATHER_HOLD')
          AND scheduled_date BETWEEN ? AND ?
-         ORDER BY scheduled_date"
-    )
+         AND deleted_at IS NULL
+         ORDER BY scheduled_date",
+        BOOKING_COLUMNS
+    ))
     .bind(now)
     .bind(check_until)
     .fetch_all(db)
@@ -105,44 +388,38 @@ async fn check_all_flights(
 
     tracing::info!("Checking {} scheduled flights", total);
 
-    // Get weather client
-    let weather_client = match WeatherClient::from_env() {
-        Ok(client) => client,
-        Err(e) => {
-            tracing::error!("Failed to create weather client: {}", e);
-            return Ok(ConflictSummary {
-                total_checked: total,
-                conflicts_found: 0,
-            });
-        }
-    };
-
-    // Cache weather by location to avoid duplicate API calls
-    let mut location_cache: HashMap<String, core::weather::WeatherData> = HashMap::new();
+    // Cache forecasts by location to avoid duplicate API calls
+    let mut location_cache: HashMap<String, Vec<weather_core::weather::WeatherData>> = HashMap::new();
 
     for booking in bookings {
-        // Check cache for weather data
+        // Check cache for forecast data
         let location_key = format!("{},{}", booking.departure_location.lat, booking.departure_location.lon);
 
-        let weather = if let Some(cached) = location_cache.get(&location_key) {
-            cached.clone()
-        } else {
-            match weather_client.fetch_current_weather(
-                booking.departure_location.lat,
-                booking.departure_location.lon,
-            ).await {
-                Ok(w) => {
-                    location_cache.insert(location_key.clone(), w.clone());
-                    w
+        if !location_cache.contains_key(&location_key) {
+            match weather_source.fetch_location_forecast(&booking.departure_location).await {
+                Ok(forecast) => {
+                    location_cache.insert(location_key.clone(), forecast);
                 }
                 Err(e) => {
-                    tracing::error!("Failed to fetch weather for booking {}: {}", booking.id, e);
+                    tracing::error!("Failed to fetch forecast for booking {}: {}", booking.id, e);
                     continue;
                 }
             }
-        };
+        }
+
+        // Evaluate the forecast across the lesson, not the weather right now
+        let forecast = &location_cache[&location_key];
+        let slots = lesson_forecast_slots(forecast, &booking);
+        if slots.is_empty() {
+            tracing::warn!(
+                "No forecast covering {} for booking {}, skipping",
+                booking.scheduled_date,
+                booking.id
+            );
+            continue;
+        }
 
-        match check_flight_safety(db, &booking, notification_tx, &weather).await {
+        match check_flight_safety(db, &booking, notification_tx, &slots, notifier, forecast, cancel_within).await {
             Ok(true) => {
                 // Flight is safe, no action needed
             }
@@ -162,82 +439,270 @@ async fn check_all_flights(
     })
 }
 
+/// Pick the forecast entry closest to `scheduled_date`
+///
+/// Returns None if no entry is within `MAX_FORECAST_SLOT_GAP_HOURS`, e.g. when
+/// the forecast doesn't extend far enough.
+pub(crate) fn select_forecast_slot(
+    forecast: &[weather_core::weather::WeatherData],
+    scheduled_date: chrono::DateTime<Utc>,
+) -> Option<&weather_core::weather::WeatherData> {
+    forecast
+        .iter()
+        .min_by_key(|w| (w.date_time - scheduled_date).num_seconds().abs())
+        .filter(|w| (w.date_time - scheduled_date).abs() <= Duration::hours(MAX_FORECAST_SLOT_GAP_HOURS))
+}
+
+/// Forecast entries covering the whole lesson: the one closest to its start
+/// (see `select_forecast_slot`) and every later entry before it ends, in time
+/// order. Empty when the forecast doesn't reach the lesson.
+pub(crate) fn lesson_forecast_slots(
+    forecast: &[weather_core::weather::WeatherData],
+    booking: &Booking,
+) -> Vec<weather_core::weather::WeatherData> {
+    let Some(start_slot) = select_forecast_slot(forecast, booking.scheduled_date) else {
+        return Vec::new();
+    };
+    let (_, lesson_end) = booking_interval(booking);
+
+    let mut slots: Vec<weather_core::weather::WeatherData> = forecast
+        .iter()
+        .filter(|w| w.date_time > start_slot.date_time && w.date_time < lesson_end)
+        .cloned()
+        .collect();
+    slots.sort_by_key(|w| w.date_time);
+    slots.insert(0, start_slot.clone());
+    slots
+}
+
+/// Evaluate a booking's forecast slots, returning whether it is safe. The
+/// lesson is unsafe if any slot is; the first unsafe slot (or the first slot
+/// when all are safe) is the one recorded.
+///
+/// An unsafe booking more than `cancel_within` before the flight goes on
+/// weather hold, as the forecast may still improve; closer than that it is
+/// cancelled. A held booking whose forecast is safe again is released back to
+/// `Scheduled`.
 async fn check_flight_safety(
     db: &SqlitePool,
     booking: &Booking,
     notification_tx: &NotificationChannel,
-    weather: &core::weather::WeatherData,
+    slots: &[weather_core::weather::WeatherData],
+    notifier: &ConflictNotifier,
+    forecast: &[WeatherData],
+    cancel_within: Duration,
 ) -> anyhow::Result<bool> {
-    use core::models::Student;
-    use core::weather::{is_flight_safe, default_weather_minimums};
+    use weather_core::weather::{is_flight_safe, join_violations, load_flight_minimums};
 
     // Fetch student
-    let student = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
-    )
+    let student = sqlx::query_as::<_, Student>(&format!(
+        "SELECT {} FROM students WHERE id = ?",
+        STUDENT_COLUMNS
+    ))
     .bind(&booking.student_id)
     .fetch_one(db)
     .await?;
 
     // Check safety
-    let minimums = default_weather_minimums();
-    let student_minimums = minimums
-        .get(&student.training_level)
-        .ok_or_else(|| anyhow::anyhow!("No minimums for training level"))?;
-
-    let (is_safe, reason) = is_flight_safe(&student.training_level, &weather, student_minimums);
-
-    if !is_safe {
-        tracing::warn!(
-            "Unsafe weather for booking {}: {}",
-            booking.id,
-            reason.as_deref().unwrap_or("Unknown")
+    let student_minimums = load_flight_minimums(
+        db,
+        student.training_level,
+        booking.aircraft_id.as_deref(),
+        booking.weather_minimum_override.as_deref(),
+    )
+    .await?;
+
+    let mut evaluations = slots.iter().map(|weather| {
+        let (is_safe, violations) = is_flight_safe(
+            &student.training_level,
+            weather,
+            &student_minimums,
+            None,
+            booking.departure_location.elevation_ft,
         );
+        (weather, is_safe, violations)
+    });
+    let first = evaluations
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No forecast slots for booking {}", booking.id))?;
+    let (weather, is_safe, violations) = if first.1 {
+        evaluations.find(|(_, is_safe, _)| !is_safe).unwrap_or(first)
+    } else {
+        first
+    };
+    let reason = join_violations(&violations);
 
-        // Cancel booking
-        sqlx::query(
-            "UPDATE bookings SET status = ? WHERE id = ?"
-        )
-        .bind(BookingStatus::Cancelled.as_str())
-        .bind(&booking.id)
-        .execute(db)
-        .await?;
+    // Record every evaluation so safety decisions are auditable
+    sqlx::query(
+        "INSERT INTO weather_checks (id, booking_id, checked_at, weather_data, is_safe, reason)
+         VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&booking.id)
+    .bind(Utc::now())
+    .bind(serde_json::to_string(weather)?)
+    .bind(is_safe)
+    .bind(&reason)
+    .execute(db)
+    .await?;
+    telemetry::record_weather_check(is_safe);
 
-        // Create reschedule event
-        let reschedule_id = uuid::Uuid::new_v4().to_string();
-        sqlx::query(
-            "INSERT INTO reschedule_events (id, booking_id, original_date, new_date, suggested_by)
-             VALUES (?, ?, ?, ?, ?)"
+    if is_safe {
+        if booking.status == BookingStatus::WeatherHold {
+            let released = release_weather_hold(db, booking, &student, notification_tx).await?;
+            notifier.send_webhook(&released).await;
+            notifier.notify_hold_released(booking, &student).await;
+        }
+        return Ok(true);
+    }
+
+    tracing::warn!(
+        "Unsafe weather for booking {}: {}",
+        booking.id,
+        reason.as_deref().unwrap_or("Unknown")
+    );
+
+    let new_status = if booking.scheduled_date - Utc::now() <= cancel_within {
+        BookingStatus::Cancelled
+    } else {
+        BookingStatus::WeatherHold
+    };
+    if new_status == booking.status {
+        // Still unsafe but not yet close to the flight; the student already knows
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "UPDATE bookings SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(new_status.as_str())
+    .bind(&booking.id)
+    .execute(db)
+    .await?;
+
+    let reason = reason.unwrap_or_default();
+    let (event_reason, notification) = match new_status {
+        BookingStatus::WeatherHold => (
+            format!("Weather hold: {}", reason),
+            Notification::WeatherHold {
+                booking_id: booking.id.clone(),
+                student_id: booking.student_id.clone(),
+                student_name: student.name.clone(),
+                message: format!("Flight on weather hold: {}", reason),
+                original_date: booking.scheduled_date,
+            },
+        ),
+        _ => {
+            telemetry::record_flight_cancelled();
+            (
+                reason.clone(),
+                Notification::WeatherConflict {
+                    booking_id: booking.id.clone(),
+                    student_id: booking.student_id.clone(),
+                    student_name: student.name.clone(),
+                    message: format!("Flight cancelled: {}", reason),
+                    original_date: booking.scheduled_date,
+                },
+            )
+        }
+    };
+
+    BookingStatusEvent::record(db, &booking.id, booking.status, new_status, SUGGESTED_BY_SYSTEM, Some(&event_reason))
+        .await?;
+    if new_status == BookingStatus::Cancelled {
+        // The cancelled slot starts the booking's reschedule history
+        RescheduleEvent::record(
+            db,
+            &booking.id,
+            booking.scheduled_date,
+            booking.scheduled_date, // Placeholder, will be updated when student reschedules
+            SUGGESTED_BY_SYSTEM,
+            Some(&event_reason),
         )
-        .bind(&reschedule_id)
-        .bind(&booking.id)
-        .bind(&booking.scheduled_date)
-        .bind(&booking.scheduled_date) // Placeholder, will be updated when student reschedules
-        .bind("SYSTEM")
-        .execute(db)
         .await?;
+    }
+
+    // Send WebSocket notification
+    let _ = notification_tx.send(serde_json::to_string(&notification)?);
+    notifier.send_webhook(&notification).await;
 
-        // Send WebSocket notification
-        let notification = json!({
-            "type": "WEATHER_CONFLICT",
-            "booking_id": booking.id,
-            "message": format!("Flight cancelled: {}", reason.unwrap_or_default()),
-            "student_name": student.name,
-            "original_date": booking.scheduled_date.to_rfc3339(),
+    // Log notification sent
+    tracing::info!("Sent conflict notification for booking {}", booking.id);
+
+    // Email/SMS the student with reschedule options, worded for the new status
+    let booking = Booking {
+        status: new_status,
+        ..booking.clone()
+    };
+    let instructor_schedule = crate::routes::bookings::fetch_instructor_schedule(db, &booking)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch instructor schedule for booking {}: {}", booking.id, e);
+            vec![]
         });
+    let instructor_email = match &booking.instructor_id {
+        Some(instructor_id) => match crate::routes::instructors::fetch_instructor(db, instructor_id).await {
+            Ok(instructor) => Some(instructor.email),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to fetch instructor {} for booking {}: {}",
+                    instructor_id,
+                    booking.id,
+                    e.error.message
+                );
+                None
+            }
+        },
+        None => None,
+    };
+    notifier
+        .notify(
+            &booking,
+            &student,
+            instructor_email.as_deref(),
+            forecast,
+            &instructor_schedule,
+            &student_minimums,
+        )
+        .await;
 
-        let _ = notification_tx.send(serde_json::to_string(&notification)?);
+    Ok(false)
+}
 
-        // Log notification sent
-        tracing::info!("Sent conflict notification for booking {}", booking.id);
+/// The forecast for a held booking is within minimums again: schedule it,
+/// record the release and notify dashboards. Returns the notification sent.
+pub(crate) async fn release_weather_hold(
+    db: &SqlitePool,
+    booking: &Booking,
+    student: &Student,
+    notification_tx: &NotificationChannel,
+) -> anyhow::Result<Notification> {
+    sqlx::query("UPDATE bookings SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(BookingStatus::Scheduled.as_str())
+        .bind(&booking.id)
+        .execute(db)
+        .await?;
 
-        // Here we would also send email/SMS notifications
-        // but that requires additional setup, so logging for now
+    BookingStatusEvent::record(
+        db,
+        &booking.id,
+        booking.status,
+        BookingStatus::Scheduled,
+        SUGGESTED_BY_SYSTEM,
+        Some("Weather improved, hold released"),
+    )
+    .await?;
 
-        return Ok(false);
-    }
+    let notification = Notification::WeatherHoldReleased {
+        booking_id: booking.id.clone(),
+        student_id: booking.student_id.clone(),
+        student_name: student.name.clone(),
+        original_date: booking.scheduled_date,
+    };
+    let _ = notification_tx.send(serde_json::to_string(&notification)?);
 
-    Ok(true)
+    tracing::info!("Released weather hold for booking {}", booking.id);
+    Ok(notification)
 }
 
 /// Generate weather alerts for upcoming bookings
@@ -245,21 +710,22 @@ async fn check_flight_safety(
 async fn generate_weather_alerts(
     db: &SqlitePool,
     notification_tx: &NotificationChannel,
+    weather_client: &dyn WeatherSource,
+    alert_score_threshold: f32,
 ) -> anyhow::Result<usize> {
-    use core::models::Student;
-    use core::weather::{WeatherClient, calculate_weather_score};
-
     let now = Utc::now();
     let check_until = now + Duration::hours(24);
 
     // Query upcoming bookings in next 24 hours
-    let bookings = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, scheduled_date, departure_location, status
+    let bookings = sqlx::query_as::<_, Booking>(&format!(
+        "SELECT {}
          FROM bookings
-         WHERE status IN ('SCHEDULED', 'RESCHEDULED')
+         WHERE status IN ('SCHEDULED', 'WEATHER_HOLD', 'RESCHEDULED')
          AND scheduled_date BETWEEN ? AND ?
-         ORDER BY scheduled_date"
-    )
+         AND deleted_at IS NULL
+         ORDER BY scheduled_date",
+        BOOKING_COLUMNS
+    ))
     .bind(now)
     .bind(check_until)
     .fetch_all(db)
@@ -272,26 +738,57 @@ async fn generate_weather_alerts(
 
     tracing::info!("Checking weather alerts for {} upcoming bookings", bookings.len());
 
-    // Get weather client
-    let weather_client = match WeatherClient::from_env() {
-        Ok(client) => client,
-        Err(e) => {
-            tracing::warn!("Weather client not available: {}. Skipping alert generation.", e);
-            return Ok(0);
+    let mut alert_count = 0;
+
+    // Surface official government alerts for each departure location
+    let mut alert_locations: Vec<(f64, f64)> = Vec::new();
+    for booking in &bookings {
+        let coords = (booking.departure_location.lat, booking.departure_location.lon);
+        if !alert_locations.contains(&coords) {
+            alert_locations.push(coords);
         }
-    };
+    }
 
-    let mut alert_count = 0;
+    for &(lat, lon) in &alert_locations {
+        match weather_client.fetch_active_alerts(lat, lon).await {
+            Ok(alerts) => {
+                let location_str = format!("({:.4}, {:.4})", lat, lon);
+                alert_count += record_government_alerts(db, notification_tx, &alerts, &location_str).await?;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch government alerts for ({}, {}): {}", lat, lon, e);
+            }
+        }
+    }
+
+    // Fetch current weather for every departure location concurrently
+    let weather_results = weather_client.fetch_current_weather_batch(&alert_locations).await;
+
+    // Forecasts only feed the trend label, so a failed fetch just leaves it off
+    let location_forecasts: Vec<((f64, f64), Vec<WeatherData>)> = stream::iter(alert_locations.iter().copied())
+        .map(|(lat, lon)| async move {
+            match weather_client.fetch_forecast(lat, lon).await {
+                Ok(forecast) => Some(((lat, lon), forecast)),
+                Err(e) => {
+                    tracing::warn!("Failed to fetch forecast for ({}, {}): {}", lat, lon, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(ALERT_FORECAST_CONCURRENCY)
+        .filter_map(|forecast| async move { forecast })
+        .collect()
+        .await;
 
-    // Group bookings by location to minimize API calls
-    let mut location_cache: std::collections::HashMap<String, core::weather::WeatherData> =
-        std::collections::HashMap::new();
+    let location_weather: Vec<((f64, f64), anyhow::Result<WeatherData>)> =
+        alert_locations.into_iter().zip(weather_results).collect();
 
     for booking in bookings {
         // Fetch student
-        let student = match sqlx::query_as::<_, Student>(
-            "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
-        )
+        let student = match sqlx::query_as::<_, Student>(&format!(
+            "SELECT {} FROM students WHERE id = ?",
+            STUDENT_COLUMNS
+        ))
         .bind(&booking.student_id)
         .fetch_one(db)
         .await {
@@ -302,90 +799,211 @@ async fn generate_weather_alerts(
             }
         };
 
-        // Get weather (cached by location)
-        let location_key = format!("{},{}", booking.departure_location.lat, booking.departure_location.lon);
-        let weather = if let Some(cached) = location_cache.get(&location_key) {
-            cached.clone()
-        } else {
-            match weather_client.fetch_current_weather(
-                booking.departure_location.lat,
-                booking.departure_location.lon,
-            ).await {
-                Ok(w) => {
-                    location_cache.insert(location_key.clone(), w.clone());
-                    w
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch weather for booking {}: {}", booking.id, e);
-                    continue;
-                }
+        let coords = (booking.departure_location.lat, booking.departure_location.lon);
+        let weather = match location_weather.iter().find(|(c, _)| *c == coords) {
+            Some((_, Ok(weather))) => weather,
+            Some((_, Err(e))) => {
+                tracing::error!("Failed to fetch weather for booking {}: {}", booking.id, e);
+                continue;
             }
+            None => continue,
         };
 
-        // Calculate weather score and severity
-        let score = calculate_weather_score(&student.training_level, &weather);
-        let severity = determine_severity(score as f64, &weather);
+        let trend = location_forecasts
+            .iter()
+            .find(|(c, _)| *c == coords)
+            .and_then(|(_, forecast)| weather_trend(&student.training_level, forecast, booking.scheduled_date));
+
+        match alert_for_booking(db, notification_tx, &booking, &student, weather, trend, alert_score_threshold).await {
+            Ok(true) => alert_count += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!("Failed to generate alert for booking {}: {}", booking.id, e);
+            }
+        }
+    }
+
+    Ok(alert_count)
+}
+
+/// Score a booking's weather and, if it is concerning (score below
+/// `alert_score_threshold`), persist
+/// and broadcast an alert. Returns whether a new alert was sent.
+///
+/// `trend`, when known, is appended to the message and stored with the alert.
+///
+/// An undismissed alert of the same severity for the same booking within the
+/// last `ALERT_DEDUP_WINDOW_HOURS` suppresses a new one, so the 5-minute pass
+/// doesn't repeat itself while conditions are unchanged.
+pub(crate) async fn alert_for_booking(
+    db: &SqlitePool,
+    notification_tx: &NotificationChannel,
+    booking: &Booking,
+    student: &Student,
+    weather: &WeatherData,
+    trend: Option<WeatherTrend>,
+    alert_score_threshold: f32,
+) -> anyhow::Result<bool> {
+    use weather_core::weather::calculate_weather_score;
+
+    // Calculate weather score and severity
+    let score = calculate_weather_score(&student.training_level, weather);
+    if score >= alert_score_threshold {
+        return Ok(false);
+    }
+
+    let severity = determine_severity(score as f64, weather);
+    let now = Utc::now();
+
+    let existing: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM weather_alerts
+         WHERE booking_id = ? AND severity = ? AND dismissed_at IS NULL AND created_at > ?
+         LIMIT 1"
+    )
+    .bind(&booking.id)
+    .bind(severity_to_string(&severity))
+    .bind(now - Duration::hours(ALERT_DEDUP_WINDOW_HOURS))
+    .fetch_optional(db)
+    .await?;
+
+    if existing.is_some() {
+        tracing::debug!(
+            "Skipping duplicate {} alert for booking {}",
+            severity_to_string(&severity),
+            booking.id
+        );
+        return Ok(false);
+    }
 
-        // Generate alert if weather is concerning (score < 9.0)
-        if score < 9.0 {
-            let message = create_alert_message(&severity, &weather, &student, score as f64);
-            let alert_id = uuid::Uuid::new_v4().to_string();
-            let now = Utc::now();
+    let mut message = create_alert_message(&severity, weather, student, score as f64);
+    if let Some(trend) = trend {
+        message.push_str(&format!(" Conditions {} toward flight time.", trend.as_str()));
+    }
+    let alert_id = uuid::Uuid::new_v4().to_string();
 
-            let location_str = format!("({:.4}, {:.4})",
-                booking.departure_location.lat,
-                booking.departure_location.lon
+    let location_str = format!("({:.4}, {:.4})",
+        booking.departure_location.lat,
+        booking.departure_location.lon
+    );
+
+    // Persist alert to database
+    sqlx::query(
+        "INSERT INTO weather_alerts (id, booking_id, severity, message, location, student_name, original_date, trend, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&alert_id)
+    .bind(&booking.id)
+    .bind(severity_to_string(&severity))
+    .bind(&message)
+    .bind(&location_str)
+    .bind(&student.name)
+    .bind(booking.scheduled_date)
+    .bind(trend.map(|trend| trend.as_str()))
+    .bind(now)
+    .execute(db)
+    .await?;
+    telemetry::record_alert(severity_to_string(&severity));
+
+    let alert = Notification::WeatherAlert {
+        id: alert_id,
+        booking_id: Some(booking.id.clone()),
+        student_id: Some(booking.student_id.clone()),
+        student_name: Some(student.name.clone()),
+        message,
+        severity: severity_to_string(&severity).to_string(),
+        location: location_str,
+        timestamp: now,
+        original_date: Some(booking.scheduled_date),
+    };
+
+    match notification_tx.send(serde_json::to_string(&alert)?) {
+        Ok(_) => {
+            tracing::info!(
+                "Sent {} alert for booking {} (score: {:.1})",
+                severity_to_string(&severity),
+                booking.id,
+                score
             );
+            Ok(true)
+        }
+        Err(e) => {
+            tracing::error!("Failed to send alert for booking {}: {}", booking.id, e);
+            Ok(false)
+        }
+    }
+}
 
-            // Persist alert to database
-            if let Err(e) = sqlx::query(
-                "INSERT INTO weather_alerts (id, booking_id, severity, message, location, student_name, original_date, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(&alert_id)
-            .bind(&booking.id)
-            .bind(severity_to_string(&severity))
-            .bind(&message)
-            .bind(&location_str)
-            .bind(&student.name)
-            .bind(&booking.scheduled_date)
-            .bind(&now)
-            .execute(db)
-            .await {
-                tracing::error!("Failed to persist alert to database: {}", e);
-                continue;
-            }
+/// Government alert events severe enough to surface to pilots
+const SEVERE_GOVERNMENT_EVENTS: [&str; 3] = ["tornado", "thunderstorm", "winter storm"];
 
-            let alert = json!({
-                "type": "weather_alert",
-                "id": alert_id,
-                "booking_id": booking.id,
-                "message": message,
-                "severity": severity_to_string(&severity),
-                "location": location_str,
-                "timestamp": now.to_rfc3339(),
-                "student_name": student.name,
-                "original_date": booking.scheduled_date.to_rfc3339(),
-            });
-
-            match notification_tx.send(serde_json::to_string(&alert)?) {
-                Ok(_) => {
-                    alert_count += 1;
-                    tracing::info!(
-                        "Sent {} alert for booking {} (score: {:.1})",
-                        severity_to_string(&severity),
-                        booking.id,
-                        score
-                    );
-                }
-                Err(e) => {
-                    tracing::error!("Failed to send alert for booking {}: {}", booking.id, e);
-                }
-            }
+fn is_severe_government_alert(alert: &OneCallAlert) -> bool {
+    let event = alert.event.to_lowercase();
+    SEVERE_GOVERNMENT_EVENTS.iter().any(|severe| event.contains(severe))
+}
+
+/// Store severe government alerts as `severe` weather alerts and broadcast them.
+/// Alerts are de-duplicated by event + start time, so an alert that is still
+/// active on the next pass is not inserted again.
+async fn record_government_alerts(
+    db: &SqlitePool,
+    notification_tx: &NotificationChannel,
+    alerts: &[OneCallAlert],
+    location_str: &str,
+) -> anyhow::Result<usize> {
+    let mut recorded = 0;
+
+    for alert in alerts.iter().filter(|a| is_severe_government_alert(a)) {
+        let event_start = DateTime::from_timestamp(alert.start, 0).unwrap_or_else(Utc::now);
+        let event_end = DateTime::from_timestamp(alert.end, 0).unwrap_or(event_start);
+        let alert_id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let message = format!(
+            "{} issued by {} until {}",
+            alert.event,
+            alert.sender_name,
+            event_end.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO weather_alerts (id, severity, message, location, created_at, event, event_start)
+             VALUES (?, 'severe', ?, ?, ?, ?, ?)"
+        )
+        .bind(&alert_id)
+        .bind(&message)
+        .bind(location_str)
+        .bind(now)
+        .bind(&alert.event)
+        .bind(event_start)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            tracing::debug!("Government alert '{}' already recorded", alert.event);
+            continue;
         }
+        telemetry::record_alert("severe");
+
+        let notification = Notification::WeatherAlert {
+            id: alert_id,
+            booking_id: None,
+            student_id: None,
+            student_name: None,
+            message,
+            severity: "severe".to_string(),
+            location: location_str.to_string(),
+            timestamp: now,
+            original_date: None,
+        };
+
+        if let Err(e) = notification_tx.send(serde_json::to_string(&notification)?) {
+            tracing::error!("Failed to send government alert '{}': {}", alert.event, e);
+        }
+
+        tracing::info!("Recorded government alert '{}' from {}", alert.event, alert.sender_name);
+        recorded += 1;
     }
 
-    Ok(alert_count)
+    Ok(recorded)
 }
 
 #[derive(Debug, Clone)]
@@ -397,7 +1015,7 @@ enum AlertSeverity {
     Clear,
 }
 
-fn determine_severity(score: f64, weather: &core::weather::WeatherData) -> AlertSeverity {
+fn determine_severity(score: f64, weather: &weather_core::weather::WeatherData) -> AlertSeverity {
     // Check for critical conditions first
     if weather.has_thunderstorms {
         return AlertSeverity::Severe;
@@ -433,11 +1051,11 @@ fn severity_to_string(severity: &AlertSeverity) -> &'static str {
 
 fn create_alert_message(
     severity: &AlertSeverity,
-    weather: &core::weather::WeatherData,
-    student: &core::models::Student,
+    weather: &weather_core::weather::WeatherData,
+    student: &weather_core::models::Student,
     score: f64,
 ) -> String {
-    use core::models::TrainingLevel;
+    use weather_core::models::TrainingLevel;
 
     let training_level_str = match student.training_level {
         TrainingLevel::StudentPilot => "student pilot",
@@ -493,3 +1111,639 @@ fn create_alert_message(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weather_core::ai::{AiCache, DEFAULT_AI_CACHE_TTL_HOURS};
+    use weather_core::models::{Location, WeatherCheck};
+    use weather_core::notifications::{MockEmailProvider, MockSmsProvider};
+    use weather_core::weather::MockWeatherClient;
+    use crate::test_support::{insert_test_student, setup_test_db};
+
+    async fn insert_test_booking(db: &SqlitePool) -> Booking {
+        insert_test_student(db).await;
+
+        let booking = Booking {
+            id: "booking1".to_string(),
+            student_id: "student1".to_string(),
+            instructor_id: None,
+            aircraft_id: None,
+            aircraft_type: "Cessna 172".to_string(),
+            scheduled_date: Utc::now() + Duration::hours(24),
+            departure_location: Location {
+                lat: 33.8113,
+                lon: -118.1515,
+                name: "KTOA".to_string(),
+                elevation_ft: None,
+            },
+            status: BookingStatus::Scheduled,
+            lesson_duration_minutes: 120,
+            weather_minimum_override: None,
+            created_at: None,
+            updated_at: None,
+            deleted_at: None,
+        };
+
+        sqlx::query(
+            "INSERT INTO bookings (id, student_id, aircraft_type, scheduled_date, departure_location, status) VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&booking.id)
+        .bind(&booking.student_id)
+        .bind(&booking.aircraft_type)
+        .bind(booking.scheduled_date)
+        .bind(serde_json::to_string(&booking.departure_location).unwrap())
+        .bind(booking.status.as_str())
+        .execute(db)
+        .await
+        .unwrap();
+
+        booking
+    }
+
+    #[test]
+    fn test_select_forecast_slot_picks_closest_entry() {
+        let now = Utc::now();
+        let forecast: Vec<WeatherData> = [0, 3, 6, 9]
+            .iter()
+            .map(|h| WeatherData {
+                date_time: now + Duration::hours(*h),
+                ..test_weather(*h as f64)
+            })
+            .collect();
+
+        let slot = select_forecast_slot(&forecast, now + Duration::hours(7)).unwrap();
+        assert_eq!(slot.date_time, now + Duration::hours(6));
+
+        // Booking beyond the end of the forecast is not evaluated
+        assert!(select_forecast_slot(&forecast, now + Duration::hours(30)).is_none());
+
+        // A gap just over the limit doesn't get truncated down to it
+        let past_gap = now + Duration::hours(9 + MAX_FORECAST_SLOT_GAP_HOURS) + Duration::minutes(59);
+        assert!(select_forecast_slot(&forecast, past_gap).is_none());
+        let at_gap = now + Duration::hours(9 + MAX_FORECAST_SLOT_GAP_HOURS);
+        assert_eq!(select_forecast_slot(&forecast, at_gap).unwrap().date_time, now + Duration::hours(9));
+    }
+
+    #[test]
+    fn test_hold_cancel_window_rejects_negative_hours() {
+        assert_eq!(hold_cancel_window_from_value(Some("12")), Duration::hours(12));
+        assert_eq!(hold_cancel_window_from_value(Some("0")), Duration::zero());
+        assert_eq!(hold_cancel_window_from_value(Some("-3")), hold_window());
+        assert_eq!(hold_cancel_window_from_value(Some("soon")), hold_window());
+        assert_eq!(hold_cancel_window_from_value(None), hold_window());
+    }
+
+    fn hold_window() -> Duration {
+        Duration::hours(DEFAULT_HOLD_CANCEL_HOURS)
+    }
+
+    fn test_notifier(email_provider: Arc<MockEmailProvider>) -> ConflictNotifier {
+        ConflictNotifier {
+            // Dummy key always uses the rule-based fallback
+            ai_client: Arc::new(AiRescheduleClient::new(
+                "dummy_key".to_string(),
+                Arc::new(AiCache::with_ttl(DEFAULT_AI_CACHE_TTL_HOURS)),
+            )),
+            email_provider,
+            sms_provider: Arc::new(MockSmsProvider::new()),
+            cc_emails: vec![],
+            webhook: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weather_hold_sends_conflict_email() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let email_provider = Arc::new(MockEmailProvider::new());
+        let notifier = test_notifier(email_provider.clone());
+        let weather = test_weather(25.0);
+
+        check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+
+        let sent = email_provider.sent_emails();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "john@example.com");
+        assert_eq!(sent[0].booking_id, booking.id);
+        assert!(sent[0].subject.starts_with("Flight Lesson On Weather Hold"));
+    }
+
+    #[tokio::test]
+    async fn test_conflict_email_copies_instructor_and_dispatch() {
+        let db = setup_test_db().await;
+        sqlx::query("INSERT INTO instructors (id, name, email, certifications) VALUES (?, ?, ?, ?)")
+            .bind("instructor1")
+            .bind("Jane Smith")
+            .bind("jane@example.com")
+            .bind("[\"CFI\"]")
+            .execute(&db)
+            .await
+            .unwrap();
+        let booking = Booking {
+            instructor_id: Some("instructor1".to_string()),
+            ..insert_test_booking(&db).await
+        };
+        sqlx::query("UPDATE bookings SET instructor_id = ? WHERE id = ?")
+            .bind("instructor1")
+            .bind(&booking.id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let email_provider = Arc::new(MockEmailProvider::new());
+        let notifier = ConflictNotifier {
+            cc_emails: vec!["dispatch@example.com".to_string(), "jane@example.com".to_string()],
+            ..test_notifier(email_provider.clone())
+        };
+        let weather = test_weather(25.0);
+
+        check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+
+        let sent = email_provider.sent_emails();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "john@example.com");
+        assert_eq!(sent[0].cc, vec!["jane@example.com", "dispatch@example.com"]);
+    }
+
+    fn test_weather(wind_speed_knots: f64) -> WeatherData {
+        WeatherData {
+            visibility_miles: 10.0,
+            wind_speed_knots,
+            ceiling_ft: Some(5000.0),
+            temperature_f: 65.0,
+            conditions: "Clear".to_string(),
+            has_thunderstorms: false,
+            has_icing: false,
+            date_time: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_flight_safety_records_weather_check() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let email_provider = Arc::new(MockEmailProvider::new());
+        let notifier = test_notifier(email_provider.clone());
+        let weather = test_weather(25.0);
+
+        let is_safe = check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+        assert!(!is_safe);
+
+        let checks = sqlx::query_as::<_, WeatherCheck>(
+            "SELECT id, booking_id, checked_at, weather_data, is_safe, reason FROM weather_checks"
+        )
+        .fetch_all(&db)
+        .await
+        .unwrap();
+
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].booking_id, booking.id);
+        assert!(!checks[0].is_safe);
+        assert!(checks[0].reason.as_deref().unwrap().contains("Wind speed"));
+    }
+
+    #[tokio::test]
+    async fn test_sms_only_student_gets_no_email() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        sqlx::query("UPDATE students SET notify_email = 0 WHERE id = ?")
+            .bind(&booking.student_id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let email_provider = Arc::new(MockEmailProvider::new());
+        let sms_provider = Arc::new(MockSmsProvider::new());
+        let notifier = ConflictNotifier {
+            sms_provider: sms_provider.clone(),
+            ..test_notifier(email_provider.clone())
+        };
+        let weather = test_weather(25.0);
+
+        check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+
+        assert!(email_provider.sent_emails().is_empty());
+        let texts = sms_provider.sent_messages();
+        assert_eq!(texts.len(), 1);
+        assert_eq!(texts[0].to, "+1234567890");
+    }
+
+    #[tokio::test]
+    async fn test_thunderstorm_forecast_holds_booking() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+        let weather = MockWeatherClient::new().with_forecast(vec![WeatherData {
+            conditions: "Thunderstorm".to_string(),
+            has_thunderstorms: true,
+            date_time: booking.scheduled_date,
+            ..test_weather(5.0)
+        }]);
+
+        let summary = check_all_flights(&db, &tx, &notifier, &weather).await.unwrap();
+        assert_eq!(summary.total_checked, 1);
+        assert_eq!(summary.conflicts_found, 1);
+        assert_eq!(weather.requests(), vec![(booking.departure_location.lat, booking.departure_location.lon)]);
+
+        let status: String = sqlx::query_scalar("SELECT status FROM bookings WHERE id = ?")
+            .bind(&booking.id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(status, BookingStatus::WeatherHold.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_metar_source_forecasts_booking_from_taf() {
+        use weather_core::weather::MetarClient;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let scheduled_date = Utc::now() + Duration::hours(12);
+        sqlx::query("UPDATE bookings SET scheduled_date = ? WHERE id = ?")
+            .bind(scheduled_date)
+            .bind(&booking.id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+
+        // Clear now, but a 1200ft ceiling from six hours in: below student minimums
+        let issued = Utc::now() - Duration::hours(1);
+        let taf = format!(
+            "TAF KTOA {} {}/{} 27008KT P6SM SCT030 FM{} 25010KT 4SM BR BKN012",
+            issued.format("%d%H%MZ"),
+            issued.format("%d%H"),
+            (issued + Duration::hours(24)).format("%d%H"),
+            (issued + Duration::hours(7)).format("%d%H00"),
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/metar"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "icaoId": "KTOA",
+                "obsTime": Utc::now().timestamp(),
+                "wspd": 8,
+                "visib": "10+",
+                "lat": 33.803,
+                "lon": -118.34
+            }])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/taf"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(taf))
+            .mount(&server)
+            .await;
+        let weather = MetarClient::new(Some("KTOA".to_string()), Some(server.uri()));
+
+        let summary = check_all_flights(&db, &tx, &notifier, &weather).await.unwrap();
+        assert_eq!(summary.total_checked, 1);
+        assert_eq!(summary.conflicts_found, 1);
+        assert_eq!(booking_status(&db, &booking.id).await, BookingStatus::WeatherHold.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_mid_lesson_thunderstorm_cancels() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let scheduled_date = Utc::now() + Duration::hours(2);
+        sqlx::query("UPDATE bookings SET scheduled_date = ? WHERE id = ?")
+            .bind(scheduled_date)
+            .bind(&booking.id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+
+        // Clear at departure, thunderstorms 90 minutes into the two-hour lesson
+        let weather = MockWeatherClient::new().with_forecast(vec![
+            WeatherData {
+                date_time: scheduled_date,
+                ..test_weather(5.0)
+            },
+            WeatherData {
+                conditions: "Thunderstorm".to_string(),
+                has_thunderstorms: true,
+                date_time: scheduled_date + Duration::minutes(90),
+                ..test_weather(5.0)
+            },
+        ]);
+
+        let summary = check_all_flights(&db, &tx, &notifier, &weather).await.unwrap();
+        assert_eq!(summary.conflicts_found, 1);
+        assert_eq!(booking_status(&db, &booking.id).await, BookingStatus::Cancelled.as_str());
+
+        let recorded: String = sqlx::query_scalar("SELECT weather_data FROM weather_checks WHERE booking_id = ?")
+            .bind(&booking.id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(recorded.contains("Thunderstorm"), "{}", recorded);
+    }
+
+    async fn booking_status(db: &SqlitePool, id: &str) -> String {
+        sqlx::query_scalar("SELECT status FROM bookings WHERE id = ?")
+            .bind(id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_weather_hold_released_when_forecast_improves() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, mut rx) = tokio::sync::broadcast::channel(16);
+        let sms_provider = Arc::new(MockSmsProvider::new());
+        let notifier = ConflictNotifier {
+            sms_provider: sms_provider.clone(),
+            ..test_notifier(Arc::new(MockEmailProvider::new()))
+        };
+
+        let windy = test_weather(25.0);
+        check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&windy), &notifier, std::slice::from_ref(&windy), hold_window())
+            .await
+            .unwrap();
+        assert_eq!(booking_status(&db, &booking.id).await, BookingStatus::WeatherHold.as_str());
+
+        // Still unsafe on the next pass: no repeat notification
+        let held = Booking {
+            status: BookingStatus::WeatherHold,
+            ..booking.clone()
+        };
+        check_flight_safety(&db, &held, &tx, std::slice::from_ref(&windy), &notifier, std::slice::from_ref(&windy), hold_window())
+            .await
+            .unwrap();
+        assert_eq!(booking_status(&db, &booking.id).await, BookingStatus::WeatherHold.as_str());
+        assert_eq!(sms_provider.sent_messages().len(), 1);
+
+        let calm = test_weather(5.0);
+        let is_safe = check_flight_safety(&db, &held, &tx, std::slice::from_ref(&calm), &notifier, std::slice::from_ref(&calm), hold_window())
+            .await
+            .unwrap();
+        assert!(is_safe);
+        assert_eq!(booking_status(&db, &booking.id).await, BookingStatus::Scheduled.as_str());
+
+        let texts = sms_provider.sent_messages();
+        assert_eq!(texts.len(), 2);
+        assert!(texts[1].message.contains("back on"));
+
+        let types: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|message| serde_json::from_str::<serde_json::Value>(&message).unwrap()["type"].to_string())
+            .collect();
+        assert_eq!(types, vec!["\"weather_hold\"", "\"weather_hold_released\""]);
+
+        // The hold and its release are status changes, not reschedules
+        let reschedules: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reschedule_events")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(reschedules, 0);
+        let transitions = sqlx::query_as::<_, BookingStatusEvent>(
+            "SELECT id, booking_id, from_status, to_status, changed_by, reason, created_at
+             FROM booking_status_events ORDER BY created_at"
+        )
+        .fetch_all(&db)
+        .await
+        .unwrap();
+        let transitions: Vec<_> = transitions.iter().map(|e| (e.from_status, e.to_status)).collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (BookingStatus::Scheduled, BookingStatus::WeatherHold),
+                (BookingStatus::WeatherHold, BookingStatus::Scheduled),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsafe_close_to_flight_cancels() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+        let weather = test_weather(25.0);
+
+        // Held bookings and scheduled ones alike are cancelled inside the window
+        let held = Booking {
+            status: BookingStatus::WeatherHold,
+            scheduled_date: Utc::now() + Duration::hours(2),
+            ..booking
+        };
+        check_flight_safety(&db, &held, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+        assert_eq!(booking_status(&db, &held.id).await, BookingStatus::Cancelled.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_and_user_reschedule_share_event_table() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+        let weather = test_weather(25.0);
+
+        // Scheduler cancels for weather (the flight is inside a 48h window),
+        // then the student picks a new slot
+        check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), Duration::hours(48))
+            .await
+            .unwrap();
+        let new_date = booking.scheduled_date + Duration::days(2);
+        crate::routes::bookings::apply_reschedule(&db, &booking, new_date, None)
+            .await
+            .unwrap();
+
+        let events = sqlx::query_as::<_, RescheduleEvent>(
+            "SELECT id, booking_id, original_date, new_date, suggested_by, ai_suggestions, reason, created_at
+             FROM reschedule_events ORDER BY created_at"
+        )
+        .fetch_all(&db)
+        .await
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].suggested_by, SUGGESTED_BY_SYSTEM);
+        assert!(events[0].reason.as_deref().unwrap().contains("Wind speed"));
+        assert_eq!(events[1].suggested_by, "USER");
+        assert_eq!(events[1].new_date, new_date);
+        assert_eq!(events[1].reason.as_deref(), Some("User requested reschedule"));
+    }
+
+    #[test]
+    fn test_schedule_config_validates_cron() {
+        let config = ScheduleConfig::new(DEFAULT_CONFLICT_CRON.to_string(), "0 */1 * * * *".to_string()).unwrap();
+        assert_eq!(config.alert_cron, "0 */1 * * * *");
+
+        let err = ScheduleConfig::new("every hour".to_string(), DEFAULT_ALERT_CRON.to_string()).unwrap_err();
+        assert!(err.to_string().contains("CONFLICT_CRON"));
+
+        assert_eq!(config.alert_score_threshold, DEFAULT_ALERT_SCORE_THRESHOLD);
+        let err = config.with_alert_score_threshold(10.5).unwrap_err();
+        assert!(err.to_string().contains("ALERT_SCORE_THRESHOLD"));
+    }
+
+    #[tokio::test]
+    async fn test_alert_score_threshold_controls_alerting() {
+        use weather_core::weather::calculate_weather_score;
+
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let student = crate::routes::students::fetch_student(&db, &booking.student_id).await.unwrap();
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+
+        let near_perfect = test_weather(8.0);
+        let moderate = test_weather(12.0);
+        let score = |weather| calculate_weather_score(&student.training_level, weather);
+        assert!((9.0..10.0).contains(&score(&near_perfect)));
+        assert!((3.0..9.0).contains(&score(&moderate)));
+
+        let default = DEFAULT_ALERT_SCORE_THRESHOLD;
+        assert!(!alert_for_booking(&db, &tx, &booking, &student, &near_perfect, None, default).await.unwrap());
+        assert!(alert_for_booking(&db, &tx, &booking, &student, &near_perfect, None, 10.0).await.unwrap());
+
+        assert!(!alert_for_booking(&db, &tx, &booking, &student, &moderate, None, 3.0).await.unwrap());
+        assert!(alert_for_booking(&db, &tx, &booking, &student, &moderate, None, default).await.unwrap());
+    }
+
+    fn government_alert(event: &str) -> OneCallAlert {
+        OneCallAlert {
+            sender_name: "NWS Los Angeles".to_string(),
+            event: event.to_string(),
+            start: 1_700_000_000,
+            end: 1_700_010_800,
+            description: "Test alert".to_string(),
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_government_alerts_deduplicated() {
+        let db = setup_test_db().await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let alerts = vec![
+            government_alert("Tornado Warning"),
+            government_alert("Flood Advisory"),
+        ];
+
+        let first = record_government_alerts(&db, &tx, &alerts, "(33.8113, -118.1515)").await.unwrap();
+        let second = record_government_alerts(&db, &tx, &alerts, "(33.8113, -118.1515)").await.unwrap();
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 0);
+
+        let severities: Vec<String> = sqlx::query_scalar("SELECT severity FROM weather_alerts")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(severities, vec!["severe".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_alert_pass_deduplicated() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let student = sqlx::query_as::<_, Student>(
+            "SELECT id, name, email, phone, training_level, notify_email, notify_sms FROM students WHERE id = ?"
+        )
+        .bind(&booking.student_id)
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let weather = test_weather(25.0);
+
+        let threshold = DEFAULT_ALERT_SCORE_THRESHOLD;
+        let trend = Some(WeatherTrend::Improving);
+        assert!(alert_for_booking(&db, &tx, &booking, &student, &weather, trend, threshold).await.unwrap());
+        assert!(!alert_for_booking(&db, &tx, &booking, &student, &weather, trend, threshold).await.unwrap());
+
+        let alerts: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT message, trend FROM weather_alerts WHERE booking_id = ?")
+                .bind(&booking.id)
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].0.ends_with("Conditions improving toward flight time."));
+        assert_eq!(alerts[0].1.as_deref(), Some("improving"));
+    }
+
+    #[tokio::test]
+    async fn test_alert_pass_uses_given_weather_source() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let weather = MockWeatherClient::new()
+            .with_current(test_weather(25.0))
+            .with_forecast_error("forecast unavailable");
+
+        let alert_count = generate_weather_alerts(&db, &tx, &weather, DEFAULT_ALERT_SCORE_THRESHOLD)
+            .await
+            .unwrap();
+
+        assert_eq!(alert_count, 1);
+        let coords = (booking.departure_location.lat, booking.departure_location.lon);
+        assert!(weather.requests().contains(&coords));
+    }
+
+    #[tokio::test]
+    async fn test_check_flight_safety_uses_minimums_from_db() {
+        let db = setup_test_db().await;
+        let booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+        let weather = test_weather(25.0);
+
+        // 25kt is over the seeded 12kt student limit; raise it in the table
+        sqlx::query("UPDATE weather_minimums SET max_wind_speed_kt = 30.0 WHERE training_level = 'STUDENT_PILOT'")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let is_safe = check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+        assert!(is_safe);
+    }
+    #[tokio::test]
+    async fn test_booking_override_makes_safe_flight_unsafe() {
+        use weather_core::models::WeatherMinimumOverride;
+
+        let db = setup_test_db().await;
+        let mut booking = insert_test_booking(&db).await;
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let notifier = test_notifier(Arc::new(MockEmailProvider::new()));
+        // 10kt is within the 12kt student limit
+        let weather = test_weather(10.0);
+
+        booking.weather_minimum_override = Some(sqlx::types::Json(WeatherMinimumOverride {
+            max_wind_speed_kt: Some(8.0),
+            ..Default::default()
+        }));
+
+        let is_safe = check_flight_safety(&db, &booking, &tx, std::slice::from_ref(&weather), &notifier, std::slice::from_ref(&weather), hold_window())
+            .await
+            .unwrap();
+        assert!(!is_safe, "the booking's 8kt override should apply instead of the 12kt level limit");
+    }
+}
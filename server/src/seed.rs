@@ -0,0 +1,168 @@
+use crate::{
+    error::ApiError,
+    routes::{
+        bookings::{insert_booking, CreateBookingRequest},
+        students::{insert_student, CreateStudentRequest},
+    },
+    scheduler::{alert_for_booking, DEFAULT_ALERT_SCORE_THRESHOLD},
+};
+use chrono::{Duration, DurationRound, Utc};
+use weather_core::models::Location;
+use weather_core::weather::WeatherData;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast;
+
+/// (name, email, phone, training level)
+const STUDENTS: [(&str, &str, &str, &str); 3] = [
+    ("Alex Rivera", "alex.rivera@example.com", "+15625550101", "STUDENT_PILOT"),
+    ("Jordan Lee", "jordan.lee@example.com", "+15625550102", "PRIVATE_PILOT"),
+    ("Sam Patel", "sam.patel@example.com", "+15625550103", "INSTRUMENT_RATED"),
+];
+
+/// (airport name, lat, lon, field elevation ft)
+const AIRPORTS: [(&str, f64, f64, f64); 4] = [
+    ("KLGB Long Beach", 33.8177, -118.1516, 60.0),
+    ("KSMO Santa Monica", 34.0158, -118.4513, 177.0),
+    ("KTOA Torrance", 33.8034, -118.3396, 103.0),
+    ("KSNA Santa Ana", 33.6757, -117.8682, 56.0),
+];
+
+/// Bookings per student, three days apart
+const BOOKINGS_PER_STUDENT: i64 = 2;
+
+/// Bookings that get a sample weather alert
+const ALERTED_BOOKINGS: usize = 2;
+
+/// Fill an empty database with demo students, bookings over the next week and
+/// a couple of weather alerts, going through the same insert paths as the
+/// API. Returns false without touching anything if students already exist.
+pub async fn seed(db: &SqlitePool) -> anyhow::Result<bool> {
+    let existing: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM students")
+        .fetch_one(db)
+        .await?;
+    if existing > 0 {
+        tracing::info!("Database already has {} students, skipping seed", existing);
+        return Ok(false);
+    }
+
+    // Whole hours so the demo schedule reads cleanly
+    let start = Utc::now().duration_trunc(Duration::hours(1))? + Duration::days(1);
+
+    let mut bookings = Vec::new();
+    for (i, (name, email, phone, training_level)) in STUDENTS.iter().enumerate() {
+        let student = insert_student(
+            db,
+            &CreateStudentRequest {
+                name: name.to_string(),
+                email: email.to_string(),
+                phone: phone.to_string(),
+                training_level: training_level.to_string(),
+                notify_email: None,
+                notify_sms: None,
+            },
+        )
+        .await
+        .map_err(api_error)?;
+
+        for day in 0..BOOKINGS_PER_STUDENT {
+            let (airport, lat, lon, elevation_ft) = AIRPORTS[(i + day as usize) % AIRPORTS.len()];
+            // Students fly three hours apart so no two lessons overlap
+            let scheduled_date = start + Duration::days(day * 3) + Duration::hours(i as i64 * 3);
+
+            let booking = insert_booking(
+                db,
+                &CreateBookingRequest {
+                    student_id: student.id.clone(),
+                    instructor_id: None,
+                    aircraft_id: None,
+                    aircraft_type: "Cessna 172".to_string(),
+                    scheduled_date,
+                    departure_location: Location {
+                        lat,
+                        lon,
+                        name: airport.to_string(),
+                        elevation_ft: Some(elevation_ft),
+                    },
+                    lesson_duration_minutes: None,
+                    weather_minimum_override: None,
+                },
+            )
+            .await
+            .map_err(api_error)?;
+
+            bookings.push((booking, student.clone()));
+        }
+    }
+
+    // Alerts are broadcast as they are created; keep a receiver so the send succeeds
+    let (notification_tx, _rx) = broadcast::channel::<String>(16);
+    let mut alerts = 0;
+    for (booking, student) in bookings.iter().take(ALERTED_BOOKINGS) {
+        let weather = stormy_weather(booking.scheduled_date);
+        if alert_for_booking(db, &notification_tx, booking, student, &weather, None, DEFAULT_ALERT_SCORE_THRESHOLD).await? {
+            alerts += 1;
+        }
+    }
+
+    tracing::info!(
+        "Seeded {} students, {} bookings and {} alerts",
+        STUDENTS.len(),
+        bookings.len(),
+        alerts
+    );
+    Ok(true)
+}
+
+/// Thunderstorms with low ceilings, bad enough to alert at every training level
+fn stormy_weather(date_time: chrono::DateTime<Utc>) -> WeatherData {
+    WeatherData {
+        visibility_miles: 2.0,
+        wind_speed_knots: 22.0,
+        wind_deg: Some(250.0),
+        wind_gust_knots: Some(35.0),
+        ceiling_ft: Some(800.0),
+        temperature_f: 58.0,
+        conditions: "thunderstorm with rain".to_string(),
+        has_thunderstorms: true,
+        has_icing: false,
+        date_time,
+        ..Default::default()
+    }
+}
+
+fn api_error(e: ApiError) -> anyhow::Error {
+    anyhow::anyhow!("{}: {}", e.error.code, e.error.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_test_db;
+
+    async fn count(db: &SqlitePool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_seed_is_idempotent() {
+        let db = setup_test_db().await;
+
+        assert!(seed(&db).await.unwrap());
+        assert_eq!(count(&db, "students").await, 3);
+        assert_eq!(count(&db, "bookings").await, 6);
+        assert_eq!(count(&db, "weather_alerts").await, 2);
+
+        let levels: Vec<String> = sqlx::query_scalar("SELECT DISTINCT training_level FROM students")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(levels.len(), 3);
+
+        assert!(!seed(&db).await.unwrap());
+        assert_eq!(count(&db, "students").await, 3);
+        assert_eq!(count(&db, "bookings").await, 6);
+    }
+}
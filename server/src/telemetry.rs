@@ -0,0 +1,185 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+const REQUEST_DURATION_METRIC: &str = "http_request_duration_seconds";
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is kept rather than replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request being handled, if called from within `propagate_request_id`
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Middleware giving every request a correlation ID
+///
+/// A well-formed `X-Request-Id` from the client is kept, otherwise a UUID is
+/// generated. The ID is echoed in the response header, attached to a tracing
+/// span so every log line for the request carries it, and added to error
+/// bodies by `ApiError`.
+pub async fn propagate_request_id(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request).instrument(span))
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Latency buckets in seconds, from fast DB reads to slow AI suggestions
+const REQUEST_DURATION_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Prometheus builder with the server's histogram buckets
+pub(crate) fn prometheus_builder() -> anyhow::Result<PrometheusBuilder> {
+    Ok(PrometheusBuilder::new().set_buckets_for_metric(
+        Matcher::Full(REQUEST_DURATION_METRIC.to_string()),
+        &REQUEST_DURATION_BUCKETS,
+    )?)
+}
+
+/// Install the global Prometheus recorder; the handle renders `/metrics`
+pub fn install_recorder() -> anyhow::Result<PrometheusHandle> {
+    Ok(prometheus_builder()?.install_recorder()?)
+}
+
+/// Middleware recording request count and latency per route
+///
+/// Requests are labelled with the matched route template (e.g.
+/// `/api/bookings/:id`) rather than the raw path, so IDs don't explode the
+/// number of series.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    record_request(method, path, response.status().as_u16(), start.elapsed());
+    response
+}
+
+fn record_request(method: String, path: String, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method),
+        ("path", path),
+        ("status", status.to_string()),
+    ];
+
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!(REQUEST_DURATION_METRIC, &labels).record(elapsed.as_secs_f64());
+}
+
+/// A booking's weather was evaluated against the student's minimums
+pub fn record_weather_check(is_safe: bool) {
+    metrics::counter!("weather_checks_total", "safe" => is_safe.to_string()).increment(1);
+}
+
+/// A booking was cancelled because of unsafe weather
+pub fn record_flight_cancelled() {
+    metrics::counter!("flights_cancelled_total").increment(1);
+}
+
+/// A weather alert was stored and broadcast
+pub fn record_alert(severity: &str) {
+    metrics::counter!("alerts_generated_total", "severity" => severity.to_string()).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_rendered_in_prometheus_format() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_request("GET".to_string(), "/api/bookings/:id".to_string(), 200, Duration::from_millis(30));
+            record_weather_check(false);
+            record_flight_cancelled();
+            record_alert("high");
+            record_alert("high");
+        });
+
+        let rendered = handle.render();
+
+        assert!(rendered.contains("weather_checks_total{safe=\"false\"} 1"));
+        assert!(rendered.contains("flights_cancelled_total 1"));
+        assert!(rendered.contains("alerts_generated_total{severity=\"high\"} 2"));
+        assert!(rendered.contains(
+            "http_request_duration_seconds_bucket{method=\"GET\",path=\"/api/bookings/:id\",status=\"200\",le=\"0.05\"} 1"
+        ));
+    }
+
+    async fn request_missing_booking(request_id: Option<&str>) -> (Option<String>, serde_json::Value) {
+        use crate::error::{ApiError, ApiResult};
+        use axum::{middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        async fn missing() -> ApiResult<()> {
+            Err(ApiError::not_found("Booking"))
+        }
+
+        let app = Router::new()
+            .route("/", get(missing))
+            .layer(middleware::from_fn(propagate_request_id));
+
+        let mut request = Request::get("/");
+        if let Some(request_id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+        let response = app
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let header = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .map(|value| value.to_str().unwrap().to_string());
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (header, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_error_body_and_header_share_request_id() {
+        let (header, json) = request_missing_booking(Some("support-ticket-42")).await;
+        assert_eq!(header.as_deref(), Some("support-ticket-42"));
+        assert_eq!(json["error"]["request_id"], "support-ticket-42");
+        assert_eq!(json["error"]["code"], "NOT_FOUND");
+
+        // Without one from the client, a generated ID is used in both places
+        let (header, json) = request_missing_booking(None).await;
+        let header = header.unwrap();
+        assert!(uuid::Uuid::parse_str(&header).is_ok());
+        assert_eq!(json["error"]["request_id"], header);
+    }
+}
@@ -0,0 +1,50 @@
+//! Fixtures shared by the server's unit tests
+
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use weather_core::ai::{AiBackend, AiCache, AiRescheduleClient, DEFAULT_AI_CACHE_TTL_HOURS};
+use weather_core::weather::MockWeatherClient;
+
+use crate::AppState;
+
+pub(crate) use weather_core::test_support::setup_test_db;
+
+/// Insert the student pilot `student1` that test bookings belong to
+pub(crate) async fn insert_test_student(db: &SqlitePool) {
+    sqlx::query(
+        "INSERT INTO students (id, name, email, phone, training_level) VALUES (?, ?, ?, ?, ?)"
+    )
+    .bind("student1")
+    .bind("John Doe")
+    .bind("john@example.com")
+    .bind("+1234567890")
+    .bind("STUDENT_PILOT")
+    .execute(db)
+    .await
+    .unwrap();
+}
+
+/// AI backend that is always down, forcing the rule-based fallback
+pub(crate) struct FailingBackend;
+
+#[async_trait::async_trait]
+impl AiBackend for FailingBackend {
+    async fn complete(&self, _system: &str, _user: &str) -> anyhow::Result<String> {
+        anyhow::bail!("AI unavailable")
+    }
+}
+
+/// App state over `db` and a mock weather source, with the AI always down
+pub(crate) fn test_state(db: SqlitePool, weather_client: MockWeatherClient) -> AppState {
+    let (notification_tx, _rx) = tokio::sync::broadcast::channel(16);
+    AppState {
+        db,
+        notification_tx,
+        ai_client: Arc::new(AiRescheduleClient::with_backend(
+            Box::new(FailingBackend),
+            Arc::new(AiCache::with_ttl(DEFAULT_AI_CACHE_TTL_HOURS)),
+        )),
+        weather_client: Arc::new(weather_client),
+    }
+}
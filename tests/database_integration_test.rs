@@ -1,24 +1,8 @@
-use weather_core::models::{Booking, BookingStatus, Location, Student, TrainingLevel};
+use weather_core::models::{BOOKING_COLUMNS, SUGGESTED_BY_SYSTEM, SUGGESTED_BY_USER};
+use weather_core::prelude::*;
 use chrono::Utc;
-use sqlx::sqlite::SqlitePoolOptions;
-use sqlx::SqlitePool;
-
-async fn setup_test_db() -> SqlitePool {
-    // Create in-memory database for testing
-    let pool = SqlitePoolOptions::new()
-        .max_connections(1)
-        .connect("sqlite::memory:")
-        .await
-        .expect("Failed to create test database");
-
-    // Run migrations
-    sqlx::migrate!("../migrations")
-        .run(&pool)
-        .await
-        .expect("Failed to run migrations");
 
-    pool
-}
+use weather_core::test_support::setup_test_db;
 
 #[tokio::test]
 async fn test_database_schema_creation() {
@@ -61,7 +45,7 @@ async fn test_student_crud_operations() {
 
     // Read the student
     let student = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
+        "SELECT id, name, email, phone, training_level, notify_email, notify_sms FROM students WHERE id = ?"
     )
     .bind(student_id)
     .fetch_one(&pool)
@@ -82,7 +66,7 @@ async fn test_student_crud_operations() {
         .expect("Failed to update student");
 
     let updated = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
+        "SELECT id, name, email, phone, training_level, notify_email, notify_sms FROM students WHERE id = ?"
     )
     .bind(student_id)
     .fetch_one(&pool)
@@ -99,7 +83,7 @@ async fn test_student_crud_operations() {
         .expect("Failed to delete student");
 
     let result = sqlx::query_as::<_, Student>(
-        "SELECT id, name, email, phone, training_level FROM students WHERE id = ?"
+        "SELECT id, name, email, phone, training_level, notify_email, notify_sms FROM students WHERE id = ?"
     )
     .bind(student_id)
     .fetch_optional(&pool)
@@ -134,6 +118,7 @@ async fn test_booking_with_location_json() {
         lat: 33.8113,
         lon: -118.1515,
         name: "KTOA".to_string(),
+        elevation_ft: None,
     };
     let location_json = serde_json::to_string(&location).expect("Failed to serialize location");
 
@@ -154,9 +139,7 @@ async fn test_booking_with_location_json() {
     .expect("Failed to insert booking");
 
     // Fetch and verify
-    let booking = sqlx::query_as::<_, Booking>(
-        "SELECT id, student_id, scheduled_date, departure_location, status FROM bookings WHERE id = ?"
-    )
+    let booking = sqlx::query_as::<_, Booking>(&format!("SELECT {} FROM bookings WHERE id = ?", BOOKING_COLUMNS))
     .bind(booking_id)
     .fetch_one(&pool)
     .await
@@ -181,6 +164,7 @@ async fn test_foreign_key_constraints() {
         lat: 33.8113,
         lon: -118.1515,
         name: "KTOA".to_string(),
+        elevation_ft: None,
     };
     let location_json = serde_json::to_string(&location).unwrap();
 
@@ -223,6 +207,7 @@ async fn test_cascade_delete() {
         lat: 33.8113,
         lon: -118.1515,
         name: "KTOA".to_string(),
+        elevation_ft: None,
     };
     let location_json = serde_json::to_string(&location).unwrap();
 
@@ -357,6 +342,7 @@ async fn test_booking_status_transitions() {
         lat: 33.8113,
         lon: -118.1515,
         name: "KTOA".to_string(),
+        elevation_ft: None,
     };
     let location_json = serde_json::to_string(&location).unwrap();
 
@@ -394,3 +380,76 @@ async fn test_booking_status_transitions() {
 
     pool.close().await;
 }
+
+#[tokio::test]
+async fn test_reschedule_events_from_scheduler_and_user() {
+    let pool = setup_test_db().await;
+
+    sqlx::query(
+        "INSERT INTO students (id, name, email, phone, training_level) VALUES (?, ?, ?, ?, ?)"
+    )
+    .bind("student_reschedule")
+    .bind("Test Student")
+    .bind("test@example.com")
+    .bind("+1234567890")
+    .bind(TrainingLevel::StudentPilot.as_str())
+    .execute(&pool)
+    .await
+    .expect("Failed to insert student");
+
+    let scheduled_date = Utc::now();
+    sqlx::query(
+        "INSERT INTO bookings (id, student_id, aircraft_type, scheduled_date, departure_location, status)
+         VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind("booking_reschedule")
+    .bind("student_reschedule")
+    .bind("Cessna 172")
+    .bind(scheduled_date)
+    .bind(r#"{"lat":33.8113,"lon":-118.1515,"name":"KTOA"}"#)
+    .bind(BookingStatus::Scheduled.as_str())
+    .execute(&pool)
+    .await
+    .expect("Failed to insert booking");
+
+    // Scheduler cancellation, then the user's reschedule
+    RescheduleEvent::record(
+        &pool,
+        "booking_reschedule",
+        scheduled_date,
+        scheduled_date,
+        SUGGESTED_BY_SYSTEM,
+        Some("Visibility 2.0 SM below minimum 5.0 SM"),
+    )
+    .await
+    .expect("Failed to record scheduler event");
+
+    let new_date = scheduled_date + chrono::Duration::days(1);
+    RescheduleEvent::record(
+        &pool,
+        "booking_reschedule",
+        scheduled_date,
+        new_date,
+        SUGGESTED_BY_USER,
+        Some("User requested reschedule"),
+    )
+    .await
+    .expect("Failed to record user event");
+
+    let events = sqlx::query_as::<_, RescheduleEvent>(
+        "SELECT id, booking_id, original_date, new_date, suggested_by, ai_suggestions, reason, created_at
+         FROM reschedule_events WHERE booking_id = ? ORDER BY created_at"
+    )
+    .bind("booking_reschedule")
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch reschedule events");
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].suggested_by, SUGGESTED_BY_SYSTEM);
+    assert_eq!(events[1].suggested_by, SUGGESTED_BY_USER);
+    assert_eq!(events[1].new_date, new_date);
+    assert!(events.iter().all(|e| e.reason.is_some()));
+
+    pool.close().await;
+}
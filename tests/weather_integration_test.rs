@@ -1,6 +1,10 @@
-use weather_core::weather::{calculate_weather_score, default_weather_minimums, is_flight_safe, WeatherData};
-use weather_core::models::TrainingLevel;
+use weather_core::prelude::*;
+use weather_core::weather::{is_flight_safe_message, SqliteForecastCache};
 use chrono::Utc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use weather_core::test_support::setup_test_db;
 
 #[test]
 fn test_student_pilot_weather_safety_integration() {
@@ -17,12 +21,15 @@ fn test_student_pilot_weather_safety_integration() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
-    let (is_safe, reason) = is_flight_safe(
+    let (is_safe, reason) = is_flight_safe_message(
         &TrainingLevel::StudentPilot,
         &perfect_weather,
         student_minimums,
+        None,
+        None,
     );
     assert!(is_safe, "Perfect weather should be safe for student pilot: {:?}", reason);
 
@@ -39,12 +46,15 @@ fn test_student_pilot_weather_safety_integration() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
     let (is_safe, _) = is_flight_safe(
         &TrainingLevel::StudentPilot,
         &marginal_weather,
         student_minimums,
+        None,
+        None,
     );
     assert!(is_safe, "Marginal weather at minimums should be safe");
 
@@ -58,12 +68,15 @@ fn test_student_pilot_weather_safety_integration() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
-    let (is_safe, reason) = is_flight_safe(
+    let (is_safe, reason) = is_flight_safe_message(
         &TrainingLevel::StudentPilot,
         &unsafe_weather,
         student_minimums,
+        None,
+        None,
     );
     assert!(!is_safe, "High winds should be unsafe for student pilot");
     assert!(reason.unwrap().contains("Wind"), "Reason should mention wind");
@@ -78,12 +91,15 @@ fn test_student_pilot_weather_safety_integration() {
         has_thunderstorms: true,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
-    let (is_safe, reason) = is_flight_safe(
+    let (is_safe, reason) = is_flight_safe_message(
         &TrainingLevel::StudentPilot,
         &thunderstorm_weather,
         student_minimums,
+        None,
+        None,
     );
     assert!(!is_safe, "Thunderstorms should always be unsafe");
     assert!(reason.unwrap().contains("Thunderstorms"), "Reason should mention thunderstorms");
@@ -103,6 +119,7 @@ fn test_training_level_progression() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
     // Student pilot - should be unsafe
@@ -110,6 +127,8 @@ fn test_training_level_progression() {
         &TrainingLevel::StudentPilot,
         &marginal_weather,
         minimums.get(&TrainingLevel::StudentPilot).unwrap(),
+        None,
+        None,
     );
     assert!(!student_safe, "Marginal weather should be unsafe for student pilot");
 
@@ -118,6 +137,8 @@ fn test_training_level_progression() {
         &TrainingLevel::PrivatePilot,
         &marginal_weather,
         minimums.get(&TrainingLevel::PrivatePilot).unwrap(),
+        None,
+        None,
     );
     assert!(private_safe, "Marginal weather should be safe for private pilot");
 
@@ -126,6 +147,8 @@ fn test_training_level_progression() {
         &TrainingLevel::InstrumentRated,
         &marginal_weather,
         minimums.get(&TrainingLevel::InstrumentRated).unwrap(),
+        None,
+        None,
     );
     assert!(instrument_safe, "Marginal weather should be safe for instrument rated");
 }
@@ -143,6 +166,7 @@ fn test_weather_scoring_consistency() {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now(),
+                ..Default::default()
             },
             "perfect",
         ),
@@ -156,6 +180,7 @@ fn test_weather_scoring_consistency() {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now(),
+                ..Default::default()
             },
             "good",
         ),
@@ -169,6 +194,7 @@ fn test_weather_scoring_consistency() {
                 has_thunderstorms: false,
                 has_icing: false,
                 date_time: Utc::now(),
+                ..Default::default()
             },
             "marginal",
         ),
@@ -182,6 +208,7 @@ fn test_weather_scoring_consistency() {
                 has_thunderstorms: false,
                 has_icing: true,
                 date_time: Utc::now(),
+                ..Default::default()
             },
             "poor",
         ),
@@ -221,12 +248,15 @@ fn test_edge_cases() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
     let (is_safe, _) = is_flight_safe(
         &TrainingLevel::StudentPilot,
         &at_minimums,
         student_minimums,
+        None,
+        None,
     );
     assert!(is_safe, "Weather exactly at minimums should be safe");
 
@@ -240,12 +270,15 @@ fn test_edge_cases() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
     let (is_safe, _) = is_flight_safe(
         &TrainingLevel::StudentPilot,
         &below_minimums,
         student_minimums,
+        None,
+        None,
     );
     assert!(!is_safe, "Weather just below minimums should be unsafe");
 
@@ -259,12 +292,15 @@ fn test_edge_cases() {
         has_thunderstorms: false,
         has_icing: false,
         date_time: Utc::now(),
+        ..Default::default()
     };
 
     let (is_safe, _) = is_flight_safe(
         &TrainingLevel::StudentPilot,
         &no_ceiling,
         student_minimums,
+        None,
+        None,
     );
     // This should be safe as unlimited ceiling is ideal
     assert!(is_safe, "Unlimited ceiling should be safe");
@@ -285,12 +321,15 @@ fn test_multiple_violations() {
         has_thunderstorms: false,
         has_icing: true, // Icing conditions
         date_time: Utc::now(),
+        ..Default::default()
     };
 
-    let (is_safe, reason) = is_flight_safe(
+    let (is_safe, reason) = is_flight_safe_message(
         &TrainingLevel::StudentPilot,
         &bad_weather,
         student_minimums,
+        None,
+        None,
     );
 
     assert!(!is_safe, "Multiple violations should result in unsafe");
@@ -301,3 +340,68 @@ fn test_multiple_violations() {
     // Should have multiple semicolon-separated reasons
     assert!(reason_str.contains(";"), "Should have multiple reasons separated by semicolons");
 }
+
+/// One Call response with two hourly forecast entries
+fn onecall_forecast() -> serde_json::Value {
+    let hour = |dt: i64| {
+        serde_json::json!({
+            "dt": dt, "temp": 293.15, "feels_like": 293.15, "pressure": 1013,
+            "humidity": 50, "dew_point": 283.15, "uvi": 3, "clouds": 10,
+            "visibility": 10000, "wind_speed": 3.0, "wind_deg": 270,
+            "weather": [{ "id": 800, "main": "Clear", "description": "clear sky", "icon": "01d" }]
+        })
+    };
+
+    serde_json::json!({
+        "lat": 33.81,
+        "lon": -118.15,
+        "timezone": "America/Los_Angeles",
+        "timezone_offset": -28800,
+        "current": hour(1700000000),
+        "hourly": [hour(1700000000), hour(1700003600)],
+        "daily": []
+    })
+}
+
+fn client_for(server: &MockServer) -> WeatherClient {
+    WeatherClient::new("test_key".to_string(), Some(format!("{}/data/2.5", server.uri())))
+        .with_onecall_base_url(format!("{}/data/3.0", server.uri()))
+}
+
+#[tokio::test]
+async fn test_cached_forecast_survives_restart_without_http_call() {
+    let db = setup_test_db().await;
+
+    let upstream = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/3.0/onecall"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(onecall_forecast()))
+        .expect(1)
+        .mount(&upstream)
+        .await;
+
+    let first = client_for(&upstream)
+        .with_forecast_cache(SqliteForecastCache::with_max_age(db.clone(), chrono::Duration::minutes(30)))
+        .fetch_forecast(33.8113, -118.1515)
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+
+    // A fresh client, as after a restart, pointed at an API that fails every call
+    let failing = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&failing)
+        .await;
+
+    let second = client_for(&failing)
+        .with_forecast_cache(SqliteForecastCache::with_max_age(db.clone(), chrono::Duration::minutes(30)))
+        .fetch_forecast(33.8114, -118.1516)
+        .await
+        .unwrap();
+
+    assert_eq!(second.len(), 2);
+    assert_eq!(second[1].date_time, first[1].date_time);
+    assert_eq!(second[0].utc_offset_seconds, Some(-28800));
+}